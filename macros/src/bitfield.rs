@@ -1,22 +1,32 @@
 use crate::{
     bits::{Bits, BitsSpan},
-    utils::{maybe_const_assert, parse_parens},
+    stats,
+    utils::{
+        arbitrary_uint, is_int_storage, maybe_const_assert, mentions_type_params, parse_parens,
+        to_snake_case, to_upper_camel_case, tokens_to_doc_string, Casing, LinearExpr,
+    },
 };
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, quote_spanned};
 use std::mem::replace;
 use syn::{
     braced, bracketed, parenthesized,
-    parse::{Parse, ParseStream, Result},
+    parse::{Parse, ParseStream, Parser, Result},
+    parse_quote, parse_quote_spanned,
     punctuated::Punctuated,
     spanned::Spanned,
-    token, Attribute, Error, Expr, ExprParen, ExprPath, Generics, Ident, Token, Type, Visibility,
+    token, Attribute, Error, Expr, ExprParen, ExprPath, Generics, Ident, LitBool, LitInt, LitStr,
+    Pat, Path, Token, Type, Visibility,
 };
 
 mod kw {
     syn::custom_keyword!(nested);
+    syn::custom_keyword!(calc);
+    syn::custom_keyword!(join);
+    syn::custom_keyword!(reserved);
 
     syn::custom_keyword!(get);
+    syn::custom_keyword!(get_cached);
     syn::custom_keyword!(set);
 
     syn::custom_keyword!(get_fn);
@@ -44,15 +54,93 @@ mod kw {
     syn::custom_keyword!(unwrap_get_fn);
     syn::custom_keyword!(unwrap_set_fn);
 
+    syn::custom_keyword!(nonzero);
+
+    syn::custom_keyword!(duration);
+    syn::custom_keyword!(floor);
+    syn::custom_keyword!(ceil);
+    syn::custom_keyword!(nearest);
+
+    syn::custom_keyword!(truncate);
+
+    syn::custom_keyword!(merge_or);
+    syn::custom_keyword!(merge_last);
+
+    syn::custom_keyword!(unit);
+    syn::custom_keyword!(safety);
+
+    syn::custom_keyword!(write_once);
+    syn::custom_keyword!(locked_by);
+
+    syn::custom_keyword!(bits);
+
+    syn::custom_keyword!(range);
+
+    syn::custom_keyword!(ignore_writes);
+
+    syn::custom_keyword!(byte_swap);
+    syn::custom_keyword!(nibble_swap);
+    syn::custom_keyword!(le);
+    syn::custom_keyword!(be);
+    syn::custom_keyword!(trigger);
+    syn::custom_keyword!(latched);
+    syn::custom_keyword!(export);
+    syn::custom_keyword!(get_vis);
+    syn::custom_keyword!(set_vis);
+    syn::custom_keyword!(name);
+
     syn::custom_keyword!(read_only);
     syn::custom_keyword!(ro);
     syn::custom_keyword!(write_only);
     syn::custom_keyword!(wo);
 
+    syn::custom_keyword!(options);
+    syn::custom_keyword!(inline);
+    syn::custom_keyword!(hint);
+    syn::custom_keyword!(always);
+    syn::custom_keyword!(never);
+    syn::custom_keyword!(none);
+    syn::custom_keyword!(must_use);
+    syn::custom_keyword!(with);
+    syn::custom_keyword!(all);
+    syn::custom_keyword!(vis);
+    syn::custom_keyword!(bit_order);
+    syn::custom_keyword!(lsb0);
+    syn::custom_keyword!(msb0);
+    syn::custom_keyword!(deny_gaps);
+    syn::custom_keyword!(valid_name);
+    syn::custom_keyword!(remote_name);
+    syn::custom_keyword!(export_prefix);
+    syn::custom_keyword!(debug_name);
+    syn::custom_keyword!(debug_raw);
+    syn::custom_keyword!(get_prefix);
+    syn::custom_keyword!(view);
+    syn::custom_keyword!(flatten);
+    syn::custom_keyword!(group);
+    syn::custom_keyword!(repeat);
+    syn::custom_keyword!(stride);
+    syn::custom_keyword!(rename_all);
+    syn::custom_keyword!(no_with);
+    syn::custom_keyword!(no_set);
+    syn::custom_keyword!(no_copy);
+    syn::custom_keyword!(split_impls);
+    syn::custom_keyword!(minimal);
+    syn::custom_keyword!(transform);
+
     syn::custom_keyword!(Debug);
+    syn::custom_keyword!(Display);
     syn::custom_keyword!(FromStorage);
     syn::custom_keyword!(IntoStorage);
     syn::custom_keyword!(DerefStorage);
+    syn::custom_keyword!(Merge);
+    syn::custom_keyword!(Valid);
+    syn::custom_keyword!(Flags);
+    syn::custom_keyword!(Remote);
+
+    #[cfg(feature = "ufmt")]
+    syn::custom_keyword!(uDebug);
+    #[cfg(feature = "ufmt")]
+    syn::custom_keyword!(uDisplay);
 }
 
 fn parse_accessor_fn(input: ParseStream) -> Result<Expr> {
@@ -62,6 +150,8 @@ fn parse_accessor_fn(input: ParseStream) -> Result<Expr> {
         .or_else(|_| input.parse::<ExprPath>().map(Expr::Path))
 }
 
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
 enum AccessorKind {
     Default,
     ConvTy(Type),
@@ -78,6 +168,7 @@ enum AccessorKind {
         ty: Type,
         has_safe_accessor: bool,
     },
+    CachedConvTy(Type),
     TryConvTy(Type),
     TryGetFn {
         fn_: Expr,
@@ -97,6 +188,23 @@ enum AccessorKind {
 }
 
 impl AccessorKind {
+    /// Returns the type values are converted to or from, if a conversion other than the default
+    /// one is used.
+    fn conv_ty(&self) -> Option<&Type> {
+        match self {
+            AccessorKind::ConvTy(ty)
+            | AccessorKind::ConvFn { ty, .. }
+            | AccessorKind::UnsafeConvTy { ty, .. }
+            | AccessorKind::UnsafeConvFn { ty, .. }
+            | AccessorKind::CachedConvTy(ty)
+            | AccessorKind::TryConvTy(ty)
+            | AccessorKind::UnwrapConvTy(ty)
+            | AccessorKind::UnwrapConvFn { ty, .. } => Some(ty),
+            AccessorKind::TrySetFn { input_ty, .. } => Some(input_ty),
+            AccessorKind::Default | AccessorKind::TryGetFn { .. } | AccessorKind::Disabled => None,
+        }
+    }
+
     fn is_unsafe(&self) -> bool {
         matches!(
             self,
@@ -111,27 +219,89 @@ impl AccessorKind {
     }
 }
 
+#[derive(Clone, Copy)]
+enum MergeKind {
+    Or,
+    Last,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum WriteGuardKind {
+    /// Writes fail if the guard bit is set, and set it otherwise.
+    Once,
+    /// Writes fail if the guard bit is set.
+    LockedBy,
+}
+
+/// A single-bit field guarding writes to another field.
+#[derive(Clone)]
+struct WriteGuard {
+    kind: WriteGuardKind,
+    field: Ident,
+}
+
+#[derive(Clone, Copy)]
+enum SwapKind {
+    Bytes,
+    Nibbles,
+}
+
+#[derive(Clone)]
 struct SingleField {
     get_kind: AccessorKind,
     set_kind: AccessorKind,
+    truncate: Option<proc_macro2::Span>,
+    merge_kind: Option<MergeKind>,
+    unit: Option<LitStr>,
+    safety: Option<LitStr>,
+    write_guard: Option<WriteGuard>,
+    trigger: Option<Option<Expr>>,
+    latched: bool,
+    /// Whether the field's accessors use `Option<NonZero<T>>`s, from the `nonzero` option.
+    nonzero: bool,
+    swap: Option<SwapKind>,
+    /// The span of the field's `le` or `be` byte order specifier, if any.
+    byte_order: Option<proc_macro2::Span>,
+    /// The width of the field's `arbitrary-int` type and its span, if any.
+    uint_bits: Option<(u8, proc_macro2::Span)>,
+    range: Option<Expr>,
+    ignore_writes: Option<Option<Expr>>,
+    export: Option<proc_macro2::Span>,
+    get_vis: Option<Visibility>,
+    set_vis: Option<Visibility>,
 }
 
+#[derive(Clone)]
 struct NestedField {
     is_readable: bool,
     is_writable: bool,
 }
 
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
 enum FieldContent {
     Single(SingleField),
     Nested(NestedField),
 }
 
+#[derive(Clone)]
 struct Field {
     attrs: Vec<Attribute>,
     vis: Visibility,
     ident: Ident,
     bits: Bits,
     ty: Type,
+    /// The number of elements of array fields, which are split into one field per element.
+    array_len: Option<usize>,
+    /// The raw value the field is set to in the generated `DEFAULT` constant.
+    default: Option<Expr>,
+    /// The base name of the field's accessors set with the `name` option, if any.
+    name: Option<Ident>,
+    /// The base name of the field's accessors, resolved from the `name` and `rename_all` options.
+    accessor_name: Ident,
+    /// The name of the field's getter, the accessor name prefixed according to the `get_prefix`
+    /// option.
+    getter_ident: Ident,
     content: FieldContent,
 }
 
@@ -143,653 +313,3391 @@ impl Field {
         }
     }
 
+    fn is_writable(&self) -> bool {
+        match &self.content {
+            FieldContent::Single(content) => !matches!(content.set_kind, AccessorKind::Disabled),
+            FieldContent::Nested(content) => content.is_writable,
+        }
+    }
+
+    /// Returns the type the field's getter (or setters, if it's write-only) converts to, if any.
+    fn conv_ty(&self) -> Option<&Type> {
+        match &self.content {
+            FieldContent::Single(content) => content
+                .get_kind
+                .conv_ty()
+                .or_else(|| content.set_kind.conv_ty()),
+            FieldContent::Nested(_) => None,
+        }
+    }
+
     fn has_unsafe_getter(&self) -> bool {
         match &self.content {
             FieldContent::Single(content) => content.get_kind.is_unsafe(),
             FieldContent::Nested(_) => false,
         }
     }
+
+    fn has_cached_getter(&self) -> bool {
+        matches!(
+            &self.content,
+            FieldContent::Single(SingleField {
+                get_kind: AccessorKind::CachedConvTy(_),
+                ..
+            })
+        )
+    }
+
+    fn has_fallible_getter(&self) -> bool {
+        matches!(
+            &self.content,
+            FieldContent::Single(SingleField {
+                get_kind: AccessorKind::TryConvTy(_) | AccessorKind::TryGetFn { .. },
+                ..
+            })
+        )
+    }
+
+    /// Returns whether indexed accessors are generated for an array field, which is only the case
+    /// if its elements are accessed without conversions or checks.
+    fn has_indexed_accessors(&self) -> bool {
+        let FieldContent::Single(content) = &self.content else {
+            return false;
+        };
+        // Fields of `arbitrary-int` types are converted to the same type on both sides
+        self.array_len.is_some()
+            && (content.uint_bits.is_some()
+                || matches!(
+                    content.get_kind,
+                    AccessorKind::Default | AccessorKind::Disabled
+                ) && matches!(
+                    content.set_kind,
+                    AccessorKind::Default | AccessorKind::Disabled
+                ))
+            && content.write_guard.is_none()
+            && content.range.is_none()
+            && content.trigger.is_none()
+    }
+
+    /// Returns the names of the accessors generated for the field.
+    fn accessor_idents(&self) -> Vec<Ident> {
+        let ident = &self.accessor_name;
+        let mut idents = Vec::new();
+        if let FieldContent::Single(SingleField {
+            trigger: Some(_), ..
+        }) = &self.content
+        {
+            idents.push(format_ident!("trigger_{}", ident));
+            return idents;
+        }
+        if self.is_readable() {
+            idents.push(self.getter_ident.clone());
+        }
+        if self.is_writable() {
+            if self.is_readable() && matches!(self.content, FieldContent::Nested(_)) {
+                idents.push(format_ident!("{}_mut", ident));
+            }
+            idents.push(format_ident!("with_{}", ident));
+            idents.push(format_ident!("set_{}", ident));
+            if let FieldContent::Single(SingleField { latched: true, .. }) = &self.content {
+                idents.push(format_ident!("ack_{}", ident));
+            }
+        }
+        idents
+    }
+
+    fn unit(&self) -> Option<&LitStr> {
+        match &self.content {
+            FieldContent::Single(content) => content.unit.as_ref(),
+            FieldContent::Nested(_) => None,
+        }
+    }
+
+    /// Returns the field's `cfg` attributes, which are applied to all generated code referring to
+    /// the field.
+    fn cfg_attrs(&self) -> Vec<&Attribute> {
+        self.attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .collect()
+    }
+
+    /// Returns an expression evaluating to whether the field is enabled by its `cfg` attributes.
+    fn cfg_enabled(&self) -> proc_macro2::TokenStream {
+        let predicates = self
+            .cfg_attrs()
+            .into_iter()
+            .filter_map(|attr| attr.meta.require_list().ok())
+            .map(|list| &list.tokens);
+        quote! { ::core::cfg!(all(#(#predicates),*)) }
+    }
+
+    /// Returns an expression evaluating to a reference to the field's value, as formatted by the
+    /// automatic `Debug` and `Display` implementations.
+    fn fmt_value(&self) -> proc_macro2::TokenStream {
+        let ident = &self.getter_ident;
+        if self.has_unsafe_getter() {
+            quote! { unsafe { &self.#ident() } }
+        } else if self.has_cached_getter() {
+            quote! { self.#ident(&mut ::proc_bitfield::cache::Cached::new()) }
+        } else {
+            quote! { &self.#ident() }
+        }
+    }
 }
 
 struct AutoImpls {
     debug: bool,
+    display: bool,
     from_storage: bool,
     into_storage: bool,
     deref_storage: bool,
+    merge: bool,
+    valid: bool,
+    flags: bool,
+    remote: bool,
+    u_debug: bool,
+    u_display: bool,
 }
 
-struct Struct {
-    outer_attrs: Vec<Attribute>,
+/// Parses an automatic implementation of a `ufmt` trait, returning whether one was found.
+#[cfg(feature = "ufmt")]
+fn parse_ufmt_auto_impl(input: ParseStream, auto_impls: &mut AutoImpls) -> bool {
+    if input.parse::<kw::uDebug>().is_ok() {
+        auto_impls.u_debug = true;
+    } else if input.parse::<kw::uDisplay>().is_ok() {
+        auto_impls.u_display = true;
+    } else {
+        return false;
+    }
+    true
+}
+
+#[cfg(not(feature = "ufmt"))]
+fn parse_ufmt_auto_impl(_input: ParseStream, _auto_impls: &mut AutoImpls) -> bool {
+    false
+}
+
+struct ConstFieldValue {
+    ident: Ident,
+    value: Expr,
+}
+
+impl Parse for ConstFieldValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let value = input.parse()?;
+        Ok(ConstFieldValue { ident, value })
+    }
+}
+
+struct Const {
+    attrs: Vec<Attribute>,
     vis: Visibility,
     ident: Ident,
-    storage_vis: Visibility,
-    storage_ty: Type,
-    auto_impls: AutoImpls,
-    has_generics: bool,
-    generics: Generics,
-    fields: Punctuated<Field, Token![,]>,
+    values: Punctuated<ConstFieldValue, Token![,]>,
 }
 
-impl Parse for Struct {
+fn parse_const(input: ParseStream, attrs: Vec<Attribute>, vis: Visibility) -> Result<Const> {
+    input.parse::<Token![const]>()?;
+    let ident = input.parse()?;
+    input.parse::<Token![=]>()?;
+    let content;
+    braced!(content in input);
+    let values = content.parse_terminated(ConstFieldValue::parse, Token![,])?;
+    Ok(Const {
+        attrs,
+        vis,
+        ident,
+        values,
+    })
+}
+
+struct CalcParam {
+    pat: Pat,
+    ty: Type,
+}
+
+impl Parse for CalcParam {
     fn parse(input: ParseStream) -> Result<Self> {
-        let outer_attrs = input.call(Attribute::parse_outer)?;
-        let vis = input.parse()?;
-        input.parse::<Token![struct]>()?;
-        let ident = input.parse::<Ident>()?;
+        let pat = Pat::parse_single(input)?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        Ok(CalcParam { pat, ty })
+    }
+}
 
-        let has_generics = input.peek(Token![<]);
-        let mut generics = input.parse::<Generics>()?;
+enum JoinConv {
+    None,
+    ConvTy(Type),
+    TryConvTy(Type),
+    UnwrapConvTy(Type),
+}
 
-        let (storage_vis, storage_ty) = {
-            let content;
-            parenthesized!(content in input);
-            (content.parse()?, content.parse()?)
-        };
+#[allow(clippy::large_enum_variant)]
+enum CalcBody {
+    Expr(Expr),
+    /// The raw values of the given fields concatenated together, most significant first.
+    Join {
+        raw_ty: Type,
+        conv: JoinConv,
+        parts: Vec<Ident>,
+    },
+}
 
-        let mut auto_impls = AutoImpls {
-            debug: false,
-            from_storage: false,
-            into_storage: false,
-            deref_storage: false,
-        };
-        if input.parse::<Token![:]>().is_ok() {
-            loop {
-                if input.is_empty() {
-                    break;
-                }
-                if input.parse::<kw::Debug>().is_ok() {
-                    auto_impls.debug = true;
-                } else if input.parse::<kw::FromStorage>().is_ok() {
-                    auto_impls.from_storage = true;
-                } else if input.parse::<kw::IntoStorage>().is_ok() {
-                    auto_impls.into_storage = true;
-                } else if input.parse::<kw::DerefStorage>().is_ok() {
-                    auto_impls.deref_storage = true;
-                } else {
-                    break;
-                }
-                if input.parse::<Token![,]>().is_err() {
-                    break;
-                }
-            }
-        }
+/// A range of bits declared as reserved, which has no accessors.
+struct Reserved {
+    span: proc_macro2::Span,
+    bits: Bits,
+    /// The number of fields declared before the reserved range.
+    field_index: usize,
+}
 
-        if input.peek(Token![where]) {
-            *generics.make_where_clause() = input.parse()?;
-        };
+fn parse_reserved(input: ParseStream, vis: Visibility, field_index: usize) -> Result<Reserved> {
+    let span = input.parse::<kw::reserved>()?.span;
+    if !matches!(vis, Visibility::Inherited) {
+        return Err(Error::new(
+            vis.span(),
+            "reserved bits can't have a visibility",
+        ));
+    }
+    input.parse::<Token![@]>()?;
+    Ok(Reserved {
+        span,
+        bits: input.parse()?,
+        field_index,
+    })
+}
 
-        let lookahead = input.lookahead1();
-        if !lookahead.peek(token::Brace) {
+struct Calc {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    params: Punctuated<CalcParam, Token![,]>,
+    ty: Type,
+    body: CalcBody,
+    /// The number of fields declared before the computed accessor.
+    field_index: usize,
+}
+
+fn parse_calc(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    field_index: usize,
+) -> Result<Calc> {
+    input.parse::<kw::calc>()?;
+    let ident = input.parse()?;
+    let params = if input.peek(token::Paren) {
+        parse_parens(input)?.parse_terminated(CalcParam::parse, Token![,])?
+    } else {
+        Punctuated::new()
+    };
+    input.parse::<Token![->]>()?;
+    let ty = input.parse()?;
+    input.parse::<Token![=]>()?;
+    let expr = input.parse()?;
+    Ok(Calc {
+        attrs,
+        vis,
+        ident,
+        params,
+        ty,
+        body: CalcBody::Expr(expr),
+        field_index,
+    })
+}
+
+fn parse_join(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    field_index: usize,
+) -> Result<Calc> {
+    input.parse::<kw::join>()?;
+    let ident = input.parse()?;
+    input.parse::<Token![:]>()?;
+    let raw_ty = input.parse::<Type>()?;
+    let conv = if input.peek(token::Bracket) {
+        let options_content;
+        bracketed!(options_content in input);
+        let lookahead = options_content.lookahead1();
+        let conv = if lookahead.peek(kw::try_get) {
+            options_content.parse::<kw::try_get>()?;
+            JoinConv::TryConvTy(options_content.parse()?)
+        } else if lookahead.peek(kw::unwrap_get) {
+            options_content.parse::<kw::unwrap_get>()?;
+            JoinConv::UnwrapConvTy(options_content.parse()?)
+        } else if lookahead.peek(kw::get) {
+            options_content.parse::<kw::get>()?;
+            JoinConv::ConvTy(options_content.parse()?)
+        } else {
             return Err(lookahead.error());
+        };
+        options_content.parse::<Option<Token![,]>>()?;
+        if !options_content.is_empty() {
+            return Err(options_content.error("expected a single getter conversion"));
         }
+        conv
+    } else {
+        JoinConv::None
+    };
+    input.parse::<Token![=]>()?;
+    let mut parts = vec![input.parse::<Ident>()?];
+    while input.peek(Token![+]) && input.peek2(Token![+]) {
+        input.parse::<Token![+]>()?;
+        input.parse::<Token![+]>()?;
+        parts.push(input.parse()?);
+    }
+    let ty = match &conv {
+        JoinConv::None => raw_ty.clone(),
+        JoinConv::ConvTy(ty) | JoinConv::UnwrapConvTy(ty) => ty.clone(),
+        JoinConv::TryConvTy(ty) => parse_quote! {
+            ::core::result::Result<#ty, <#ty as ::core::convert::TryFrom<#raw_ty>>::Error>
+        },
+    };
+    Ok(Calc {
+        attrs,
+        vis,
+        ident,
+        params: Punctuated::new(),
+        ty,
+        body: CalcBody::Join {
+            raw_ty,
+            conv,
+            parts,
+        },
+        field_index,
+    })
+}
 
-        let content;
-        braced!(content in input);
-        assert!(
-            content.call(Attribute::parse_inner)?.is_empty(),
-            "Inner attributes are not supported right now"
-        );
-        let fields = content.parse_terminated(
-            |input| {
-                let attrs = input.call(Attribute::parse_outer)?;
-                let vis = input.parse()?;
-                let ident = input.parse()?;
-                input.parse::<Token![:]>()?;
-                let is_nested = input.parse::<kw::nested>().is_ok();
-                let ty = input.parse::<Type>()?;
-
-                let content = if is_nested {
-                    let mut is_readable = true;
-                    let mut is_writable = true;
-                    let lookahead = input.lookahead1();
-                    if lookahead.peek(token::Bracket) {
-                        let options_content;
-                        bracketed!(options_content in input);
-
-                        macro_rules! check_accessor_conflict {
-                            ($ident: ident, $name: literal, $other: ident, $span: ident) => {
-                                if !$ident {
-                                    return Err(Error::new(
-                                        $span,
-                                        concat!("Duplicate ", $name, " specifiers"),
-                                    ));
-                                }
-                                if !$other {
-                                    return Err(Error::new(
-                                        $span,
-                                        "Conflicting read_only and write_only specifiers",
-                                    ));
-                                }
-                            };
-                        }
+#[derive(Clone, Copy)]
+enum InlinePolicy {
+    Hint,
+    Always,
+    Never,
+    None,
+}
 
-                        while !options_content.is_empty() {
-                            let lookahead = options_content.lookahead1();
-                            if lookahead.peek(kw::read_only) || lookahead.peek(kw::ro) {
-                                let span = options_content
-                                    .parse::<kw::read_only>()
-                                    .map(|kw| kw.span)
-                                    .or_else(|_| {
-                                        options_content.parse::<kw::ro>().map(|kw| kw.span)
-                                    })?;
-                                check_accessor_conflict!(
-                                    is_writable,
-                                    "read_only",
-                                    is_readable,
-                                    span
-                                );
-                                is_writable = false;
-                            } else if lookahead.peek(kw::write_only) || lookahead.peek(kw::wo) {
-                                let span = options_content
-                                    .parse::<kw::write_only>()
-                                    .map(|kw| kw.span)
-                                    .or_else(|_| {
-                                        options_content.parse::<kw::wo>().map(|kw| kw.span)
-                                    })?;
-                                check_accessor_conflict!(
-                                    is_readable,
-                                    "write_only",
-                                    is_writable,
-                                    span
-                                );
-                                is_readable = false;
-                            } else {
-                                return Err(lookahead.error());
-                            }
+#[derive(Clone, Copy)]
+enum MustUsePolicy {
+    With,
+    All,
+    None,
+}
 
-                            let had_comma = options_content.parse::<Token![,]>().is_ok();
-                            if !options_content.is_empty() && !had_comma {
-                                return Err(
-                                    options_content.error("expected comma between field options")
-                                );
-                            }
-                        }
-                    }
-                    FieldContent::Nested(NestedField {
-                        is_readable,
-                        is_writable,
-                    })
-                } else {
-                    let mut get = AccessorKind::Default;
-                    let mut set = AccessorKind::Default;
-                    let lookahead = input.lookahead1();
-                    if lookahead.peek(token::Bracket) {
-                        let options_content;
-                        bracketed!(options_content in input);
-
-                        macro_rules! check_conversion_ty_conflict {
-                            ($($ident: ident),*; $span: expr) => {
-                                if $(!matches!(&$ident, AccessorKind::Default))||* {
-                                    return Err(Error::new(
-                                        $span,
-                                        "Conflicting conversion type definitions",
-                                    ));
-                                }
-                            };
-                        }
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BitOrder {
+    Lsb0,
+    Msb0,
+}
 
-                        macro_rules! check_accessor_conflict {
-                            ($ident: ident, $name: literal, $other: ident, $span: ident) => {
-                                if matches!(&$ident, AccessorKind::Disabled) {
-                                    return Err(Error::new(
-                                        $span,
-                                        concat!("Duplicate ", $name, " specifiers"),
-                                    ));
-                                }
-                                if matches!(&$other, AccessorKind::Disabled) {
-                                    return Err(Error::new(
-                                        $span,
-                                        "Conflicting read_only and write_only specifiers",
-                                    ));
-                                }
-                            };
-                        }
+/// Options controlling the attributes and qualifiers of the generated accessors and metadata
+/// items, specified in the bitfield's header as `options(...)`.
+struct Options {
+    inline: InlinePolicy,
+    must_use: MustUsePolicy,
+    const_: Option<proc_macro2::Span>,
+    vis: Option<Visibility>,
+    bit_order: BitOrder,
+    deny_gaps: Option<proc_macro2::Span>,
+    valid_name: Option<Ident>,
+    remote_name: Option<Ident>,
+    export_prefix: Option<Ident>,
+    debug_name: Option<LitStr>,
+    debug_raw: Option<LitBool>,
+    get_prefix: Option<LitStr>,
+    name: Option<LitStr>,
+    rename_all: Option<Casing>,
+    no_with: Option<proc_macro2::Span>,
+    no_set: Option<proc_macro2::Span>,
+    no_copy: Option<proc_macro2::Span>,
+    split_impls: Option<usize>,
+    minimal: Option<proc_macro2::Span>,
+    /// The type implementing `Transform` that raw values are encoded with, if any.
+    transform: Option<Type>,
+}
 
-                        fn parse_return_ty(input: ParseStream) -> Result<Result<Type>> {
-                            if let Err(err) = input.parse::<Token![->]>() {
-                                return Ok(Err(err));
-                            }
-                            Ok(input.parse())
-                        }
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            inline: InlinePolicy::Hint,
+            must_use: MustUsePolicy::With,
+            const_: None,
+            vis: None,
+            bit_order: BitOrder::Lsb0,
+            deny_gaps: None,
+            valid_name: None,
+            remote_name: None,
+            export_prefix: None,
+            debug_name: None,
+            debug_raw: None,
+            get_prefix: None,
+            name: None,
+            rename_all: None,
+            no_with: None,
+            no_set: None,
+            no_copy: None,
+            split_impls: None,
+            minimal: None,
+            transform: None,
+        }
+    }
+}
 
-                        fn parse_parenthesized_ty(input: ParseStream) -> Result<Result<Type>> {
-                            Ok(match parse_parens(input) {
-                                Ok(content) => content.parse(),
-                                Err(err) => Err(err),
-                            })
-                        }
+impl Options {
+    fn inline_attr(&self) -> proc_macro2::TokenStream {
+        match self.inline {
+            InlinePolicy::Hint => quote! { #[inline] },
+            InlinePolicy::Always => quote! { #[inline(always)] },
+            InlinePolicy::Never => quote! { #[inline(never)] },
+            InlinePolicy::None => quote! {},
+        }
+    }
 
-                        while !options_content.is_empty() {
-                            // Infallible conversions
-                            if let Ok(kw) = options_content.parse::<kw::get>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                get = AccessorKind::ConvTy(options_content.parse()?);
-                            } else if let Ok(kw) = options_content.parse::<kw::set>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                set = AccessorKind::ConvTy(options_content.parse()?);
-                            }
-                            // Unsafe conversions
-                            else if let Ok(kw) = options_content.parse::<kw::unsafe_get>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                get = AccessorKind::UnsafeConvTy {
-                                    ty: options_content.parse()?,
-                                    has_safe_accessor,
-                                };
-                            } else if let Ok(kw) = options_content.parse::<kw::unsafe_set>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                set = AccessorKind::UnsafeConvTy {
-                                    ty: options_content.parse()?,
-                                    has_safe_accessor,
-                                };
-                            } else if let Ok(kw) = options_content.parse::<kw::unsafe_both>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::UnsafeConvTy {
-                                    ty: ty.clone(),
-                                    has_safe_accessor,
-                                };
-                                set = AccessorKind::UnsafeConvTy {
-                                    ty,
-                                    has_safe_accessor,
-                                };
-                            } else if let Ok(kw) = options_content.parse::<Token![unsafe]>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::UnsafeConvTy {
-                                    ty: ty.clone(),
-                                    has_safe_accessor,
-                                };
-                                set = AccessorKind::ConvTy(ty);
-                            }
-                            // Fallible conversions
-                            else if let Ok(kw) = options_content.parse::<kw::try_get>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                get = AccessorKind::TryConvTy(options_content.parse()?);
-                            } else if let Ok(kw) = options_content.parse::<kw::try_set>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                set = AccessorKind::TryConvTy(options_content.parse()?);
-                            } else if let Ok(kw) = options_content.parse::<kw::try_both>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::TryConvTy(ty.clone());
-                                set = AccessorKind::TryConvTy(ty);
-                            } else if let Ok(kw) = options_content.parse::<Token![try]>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::TryConvTy(ty.clone());
-                                set = AccessorKind::ConvTy(ty);
-                            }
-                            // Unwrapping conversions
-                            else if let Ok(kw) = options_content.parse::<kw::unwrap_get>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                get = AccessorKind::UnwrapConvTy(options_content.parse()?);
-                            } else if let Ok(kw) = options_content.parse::<kw::unwrap_set>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                set = AccessorKind::UnwrapConvTy(options_content.parse()?);
-                            } else if let Ok(kw) = options_content.parse::<kw::unwrap_both>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::UnwrapConvTy(ty.clone());
-                                set = AccessorKind::UnwrapConvTy(ty);
-                            } else if let Ok(kw) = options_content.parse::<kw::unwrap>() {
-                                check_conversion_ty_conflict!(get, set; kw.span);
-                                let ty: Type = options_content.parse()?;
-                                get = AccessorKind::UnwrapConvTy(ty.clone());
-                                set = AccessorKind::ConvTy(ty);
-                            }
-                            // Infallible fn conversions
-                            else if let Ok(kw) = options_content.parse::<kw::get_fn>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_return_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                get = AccessorKind::ConvFn { fn_, ty };
-                            } else if let Ok(kw) = options_content.parse::<kw::set_fn>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_parenthesized_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                set = AccessorKind::ConvFn { fn_, ty };
-                            }
-                            // Unsafe fn conversions
-                            else if let Ok(kw) = options_content.parse::<kw::unsafe_get_fn>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_return_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                get = AccessorKind::UnsafeConvFn {
-                                    fn_,
-                                    ty,
-                                    has_safe_accessor,
-                                };
-                            } else if let Ok(kw) = options_content.parse::<kw::unsafe_set_fn>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                let has_safe_accessor =
-                                    options_content.parse::<Token![!]>().is_ok();
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_parenthesized_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                set = AccessorKind::UnsafeConvFn {
-                                    fn_,
-                                    ty,
-                                    has_safe_accessor,
-                                };
-                            }
-                            // Fallible fn conversions
-                            else if let Ok(kw) = options_content.parse::<kw::try_get_fn>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let result_ty = parse_return_ty(&options_content)??;
-                                get = AccessorKind::TryGetFn { fn_, result_ty };
-                            } else if let Ok(kw) = options_content.parse::<kw::try_set_fn>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let input_ty = parse_parenthesized_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                let result_ty = parse_return_ty(&options_content)??;
-                                set = AccessorKind::TrySetFn {
-                                    fn_,
-                                    input_ty,
-                                    result_ty,
-                                };
-                            }
-                            // Unwrapping fn conversions
-                            else if let Ok(kw) = options_content.parse::<kw::unwrap_get_fn>() {
-                                check_conversion_ty_conflict!(get; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_return_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                get = AccessorKind::UnwrapConvFn { fn_, ty };
-                            } else if let Ok(kw) = options_content.parse::<kw::unwrap_set_fn>() {
-                                check_conversion_ty_conflict!(set; kw.span);
-                                let fn_ = parse_accessor_fn(&options_content)?;
-                                let ty = parse_parenthesized_ty(&options_content)?
-                                    .unwrap_or_else(|_| ty.clone());
-                                set = AccessorKind::UnwrapConvFn { fn_, ty };
-                            }
-                            // Access restrictions
-                            else if let Ok(span) = options_content
-                                .parse::<kw::read_only>()
-                                .map(|kw| kw.span)
-                                .or_else(|_| options_content.parse::<kw::ro>().map(|kw| kw.span))
-                            {
-                                check_accessor_conflict!(set, "read_only", get, span);
-                                set = AccessorKind::Disabled;
-                            } else if let Ok(span) = options_content
-                                .parse::<kw::write_only>()
-                                .map(|kw| kw.span)
-                                .or_else(|_| options_content.parse::<kw::wo>().map(|kw| kw.span))
-                            {
-                                check_accessor_conflict!(get, "write_only", set, span);
-                                get = AccessorKind::Disabled;
-                            }
-                            // Infallible conversion (without keywords)
-                            else {
-                                let ty: Type = options_content.parse()?;
-                                check_conversion_ty_conflict!(get, set; ty.span());
-                                get = AccessorKind::ConvTy(ty.clone());
-                                set = AccessorKind::ConvTy(ty);
-                            }
+    /// Returns the `#[must_use]` attribute for getters if required.
+    fn get_must_use_attr(&self) -> proc_macro2::TokenStream {
+        match self.must_use {
+            MustUsePolicy::All => quote! { #[must_use] },
+            MustUsePolicy::With | MustUsePolicy::None => quote! {},
+        }
+    }
 
-                            let had_comma = options_content.parse::<Token![,]>().is_ok();
-                            if !options_content.is_empty() && !had_comma {
-                                return Err(
-                                    options_content.error("expected comma between field options")
-                                );
-                            }
-                        }
-                    }
-                    FieldContent::Single(SingleField {
-                        get_kind: get,
-                        set_kind: set,
-                    })
-                };
-                input.parse::<Token![@]>()?;
-                let bits = input.parse()?;
-                Ok(Field {
-                    attrs,
-                    vis,
-                    ident,
-                    bits,
-                    ty,
-                    content,
-                })
+    /// Returns the `#[must_use]` attribute for `with_*` functions if required.
+    fn with_must_use_attr(&self) -> proc_macro2::TokenStream {
+        match self.must_use {
+            // Point out the in-place alternative, as discarding the modified copy is always a bug
+            MustUsePolicy::With | MustUsePolicy::All if self.no_set.is_none() => quote! {
+                #[must_use = "`with_*` functions return a modified copy of the bitfield, leaving \
+                              it unchanged; use the `set_*` functions to modify it in place"]
             },
-            Token![,],
-        )?;
-
-        Ok(Struct {
-            outer_attrs,
-            vis,
-            ident,
-            storage_vis,
-            storage_ty,
-            auto_impls,
-            has_generics,
-            generics,
-            fields,
-        })
+            MustUsePolicy::With | MustUsePolicy::All => quote! {
+                #[must_use = "`with_*` functions return a modified copy of the bitfield, leaving \
+                              it unchanged"]
+            },
+            MustUsePolicy::None => quote! {},
+        }
     }
 }
 
-pub fn bitfield(input: TokenStream) -> TokenStream {
-    let Struct {
-        outer_attrs,
-        vis,
-        ident,
-        storage_vis,
-        storage_ty,
-        auto_impls,
-        has_generics,
-        generics,
-        fields,
-    } = syn::parse_macro_input!(input);
+fn parse_options(input: ParseStream, options: &mut Options) -> Result<()> {
+    let mut inline = false;
+    let mut must_use = false;
+    let mut bit_order = false;
+    let content = parse_parens(input)?;
+    while !content.is_empty() {
+        let lookahead = content.lookahead1();
+        if lookahead.peek(kw::inline) {
+            let kw = content.parse::<kw::inline>()?;
+            if replace(&mut inline, true) {
+                return Err(Error::new(kw.span, "Duplicate inline option"));
+            }
+            content.parse::<Token![=]>()?;
+            let lookahead = content.lookahead1();
+            options.inline = if lookahead.peek(kw::hint) {
+                content.parse::<kw::hint>()?;
+                InlinePolicy::Hint
+            } else if lookahead.peek(kw::always) {
+                content.parse::<kw::always>()?;
+                InlinePolicy::Always
+            } else if lookahead.peek(kw::never) {
+                content.parse::<kw::never>()?;
+                InlinePolicy::Never
+            } else if lookahead.peek(kw::none) {
+                content.parse::<kw::none>()?;
+                InlinePolicy::None
+            } else {
+                return Err(lookahead.error());
+            };
+        } else if lookahead.peek(kw::must_use) {
+            let kw = content.parse::<kw::must_use>()?;
+            if replace(&mut must_use, true) {
+                return Err(Error::new(kw.span, "Duplicate must_use option"));
+            }
+            content.parse::<Token![=]>()?;
+            let lookahead = content.lookahead1();
+            options.must_use = if lookahead.peek(kw::with) {
+                content.parse::<kw::with>()?;
+                MustUsePolicy::With
+            } else if lookahead.peek(kw::all) {
+                content.parse::<kw::all>()?;
+                MustUsePolicy::All
+            } else if lookahead.peek(kw::none) {
+                content.parse::<kw::none>()?;
+                MustUsePolicy::None
+            } else {
+                return Err(lookahead.error());
+            };
+        } else if lookahead.peek(Token![const]) {
+            let token = content.parse::<Token![const]>()?;
+            if options.const_.replace(token.span).is_some() {
+                return Err(Error::new(token.span, "Duplicate const option"));
+            }
+        } else if lookahead.peek(kw::vis) {
+            let kw = content.parse::<kw::vis>()?;
+            content.parse::<Token![=]>()?;
+            if options.vis.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate vis option"));
+            }
+        } else if lookahead.peek(kw::valid_name) {
+            let kw = content.parse::<kw::valid_name>()?;
+            content.parse::<Token![=]>()?;
+            if options.valid_name.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate valid_name option"));
+            }
+        } else if lookahead.peek(kw::remote_name) {
+            let kw = content.parse::<kw::remote_name>()?;
+            content.parse::<Token![=]>()?;
+            if options.remote_name.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate remote_name option"));
+            }
+        } else if lookahead.peek(kw::export_prefix) {
+            let kw = content.parse::<kw::export_prefix>()?;
+            content.parse::<Token![=]>()?;
+            if options.export_prefix.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate export_prefix option"));
+            }
+        } else if lookahead.peek(kw::debug_name) {
+            let kw = content.parse::<kw::debug_name>()?;
+            content.parse::<Token![=]>()?;
+            if options.debug_name.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate debug_name option"));
+            }
+        } else if lookahead.peek(kw::debug_raw) {
+            let kw = content.parse::<kw::debug_raw>()?;
+            content.parse::<Token![=]>()?;
+            if options.debug_raw.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate debug_raw option"));
+            }
+        } else if lookahead.peek(kw::name) {
+            let kw = content.parse::<kw::name>()?;
+            content.parse::<Token![=]>()?;
+            if options.name.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate name option"));
+            }
+        } else if lookahead.peek(kw::rename_all) {
+            let kw = content.parse::<kw::rename_all>()?;
+            content.parse::<Token![=]>()?;
+            let casing_lit = content.parse::<LitStr>()?;
+            let casing = Casing::from_name(&casing_lit.value()).ok_or_else(|| {
+                Error::new(
+                    casing_lit.span(),
+                    "Unknown casing, expected one of \"lowercase\", \"UPPERCASE\", \
+                     \"PascalCase\", \"camelCase\", \"snake_case\" or \"SCREAMING_SNAKE_CASE\"",
+                )
+            })?;
+            if options.rename_all.replace(casing).is_some() {
+                return Err(Error::new(kw.span, "Duplicate rename_all option"));
+            }
+        } else if lookahead.peek(kw::get_prefix) {
+            let kw = content.parse::<kw::get_prefix>()?;
+            content.parse::<Token![=]>()?;
+            let prefix = content.parse::<LitStr>()?;
+            if syn::parse_str::<Ident>(&format!("{}x", prefix.value())).is_err() {
+                return Err(Error::new(prefix.span(), "Invalid getter name prefix"));
+            }
+            if options.get_prefix.replace(prefix).is_some() {
+                return Err(Error::new(kw.span, "Duplicate get_prefix option"));
+            }
+        } else if lookahead.peek(kw::no_with) {
+            let kw = content.parse::<kw::no_with>()?;
+            if options.no_with.replace(kw.span).is_some() {
+                return Err(Error::new(kw.span, "Duplicate no_with option"));
+            }
+        } else if lookahead.peek(kw::no_set) {
+            let kw = content.parse::<kw::no_set>()?;
+            if options.no_set.replace(kw.span).is_some() {
+                return Err(Error::new(kw.span, "Duplicate no_set option"));
+            }
+        } else if lookahead.peek(kw::no_copy) {
+            let kw = content.parse::<kw::no_copy>()?;
+            if options.no_copy.replace(kw.span).is_some() {
+                return Err(Error::new(kw.span, "Duplicate no_copy option"));
+            }
+        } else if lookahead.peek(kw::transform) {
+            let kw = content.parse::<kw::transform>()?;
+            content.parse::<Token![=]>()?;
+            if options.transform.replace(content.parse()?).is_some() {
+                return Err(Error::new(kw.span, "Duplicate transform option"));
+            }
+        } else if lookahead.peek(kw::minimal) {
+            let kw = content.parse::<kw::minimal>()?;
+            if options.minimal.replace(kw.span).is_some() {
+                return Err(Error::new(kw.span, "Duplicate minimal option"));
+            }
+        } else if lookahead.peek(kw::split_impls) {
+            let kw = content.parse::<kw::split_impls>()?;
+            content.parse::<Token![=]>()?;
+            let len_lit = content.parse::<LitInt>()?;
+            let len = len_lit.base10_parse::<usize>()?;
+            if len == 0 {
+                return Err(Error::new(
+                    len_lit.span(),
+                    "split_impls requires a non-zero number of accessors per impl block",
+                ));
+            }
+            if options.split_impls.replace(len).is_some() {
+                return Err(Error::new(kw.span, "Duplicate split_impls option"));
+            }
+        } else if lookahead.peek(kw::deny_gaps) {
+            let kw = content.parse::<kw::deny_gaps>()?;
+            if options.deny_gaps.replace(kw.span).is_some() {
+                return Err(Error::new(kw.span, "Duplicate deny_gaps option"));
+            }
+        } else if lookahead.peek(kw::bit_order) {
+            let kw = content.parse::<kw::bit_order>()?;
+            if replace(&mut bit_order, true) {
+                return Err(Error::new(kw.span, "Duplicate bit_order option"));
+            }
+            content.parse::<Token![=]>()?;
+            let lookahead = content.lookahead1();
+            options.bit_order = if lookahead.peek(kw::lsb0) {
+                content.parse::<kw::lsb0>()?;
+                BitOrder::Lsb0
+            } else if lookahead.peek(kw::msb0) {
+                content.parse::<kw::msb0>()?;
+                BitOrder::Msb0
+            } else {
+                return Err(lookahead.error());
+            };
+        } else {
+            return Err(lookahead.error());
+        }
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+    Ok(())
+}
 
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let has_type_params = generics.type_params().next().is_some();
+/// A non-storage member of the bitfield struct, declared after its storage as `Type = init`.
+struct ExtraMember {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ty: Type,
+    init: Expr,
+}
 
-    let type_params_phantom_data = if has_type_params {
-        let type_params = generics.type_params();
-        quote! { , ::core::marker::PhantomData::<(#(#type_params),*)> }
+struct Struct {
+    outer_attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    storage_vis: Visibility,
+    private_storage: bool,
+    storage_ty: Type,
+    storage_bits: Option<u32>,
+    extra_members: Vec<ExtraMember>,
+    auto_impls: AutoImpls,
+    options: Options,
+    has_generics: bool,
+    generics: Generics,
+    fields: Vec<Field>,
+    consts: Vec<Const>,
+    calcs: Vec<Calc>,
+    reserved: Vec<Reserved>,
+    /// The bitfield structs generated for the bitfield's views, each accessed through a nested
+    /// field spanning the whole storage.
+    views: Vec<proc_macro2::TokenStream>,
+    repeats: Vec<Repeat>,
+}
+
+/// A field declared in a repeat block, whose instances get indexed accessors like array fields.
+struct Repeat {
+    /// The field as declared, with its element count set to the number of instances.
+    field: Field,
+    /// The indices of the field's instances among the bitfield's fields.
+    instances: Vec<usize>,
+}
+
+fn parse_field(input: ParseStream, attrs: Vec<Attribute>, vis: Visibility) -> Result<Field> {
+    let ident: Ident = input.parse()?;
+    input.parse::<Token![:]>()?;
+    let nested_prefix = input.parse::<kw::nested>().is_ok();
+    let ty = input.parse::<Type>()?;
+    let array_len = if input.parse::<Token![;]>().is_ok() {
+        let len_lit = input.parse::<LitInt>()?;
+        let len = len_lit.base10_parse::<usize>()?;
+        if len == 0 {
+            return Err(Error::new(len_lit.span(), "array fields can't be empty"));
+        }
+        Some(len)
     } else {
-        quote! {}
+        None
+    };
+    // Nested bitfield fields can also be declared with a `nested` option instead of the prefix
+    let is_nested = nested_prefix || {
+        let fork = input.fork();
+        let options_content;
+        fork.peek(token::Bracket) && {
+            bracketed!(options_content in fork);
+            options_content
+                .parse::<proc_macro2::TokenStream>()?
+                .into_iter()
+                .any(|tt| matches!(&tt, proc_macro2::TokenTree::Ident(ident) if ident == "nested"))
+        }
     };
 
-    let mut last_bits_span = None;
-
-    let field_fns = fields.iter().map(
-        |Field {
-             attrs,
-             vis,
-             ident,
-             bits,
-             ty: field_ty,
-             content
-         }| {
-            let storage_ty_bits = quote! { {::core::mem::size_of::<#storage_ty>() << 3} };
-            let field_ty_bits = quote! { {::core::mem::size_of::<#field_ty>() << 3} };
+    // Fields of `arbitrary-int` types are stored as their underlying builtin types (or as `bool`s
+    // for `u1`, like other single-bit fields), converting to and from them in their accessors
+    let (ty, uint_ty) = match (!is_nested).then(|| arbitrary_uint(&ty)).flatten() {
+        Some((1, _)) => (parse_quote! { bool }, Some((ty, 1))),
+        Some((bits, underlying_ty)) => (underlying_ty, Some((ty, bits))),
+        None => (ty, None),
+    };
 
-            let bits_span = match bits.clone().into_span(last_bits_span.as_ref()) {
-                Ok(bits_span) => bits_span,
-                Err(err) => return err.to_compile_error(),
-            };
-            last_bits_span = Some(bits_span.clone());
+    let mut name = None;
+    let content = if is_nested {
+        let mut is_readable = true;
+        let mut is_writable = true;
+        let lookahead = input.lookahead1();
+        if lookahead.peek(token::Bracket) {
+            let options_content;
+            bracketed!(options_content in input);
 
-            let mut bits_span_asserts = {
-                let assert_is_const = !has_generics;
-                let assert = maybe_const_assert(assert_is_const);
-                let mut asserts = match &bits_span {
-                    BitsSpan::Single(bit) => {
-                        quote_spanned! {
-                            ident.span() =>
-                            #assert(#bit < #storage_ty_bits);
-                        }
+            macro_rules! check_accessor_conflict {
+                ($ident: ident, $name: literal, $other: ident, $span: ident) => {
+                    if !$ident {
+                        return Err(Error::new(
+                            $span,
+                            concat!("Duplicate ", $name, " specifiers"),
+                        ));
                     }
-                    BitsSpan::Range { start, end } => {
-                        quote_spanned! {
-                            ident.span() =>
-                            #assert(#end > #start);
-                            #assert(#start < #storage_ty_bits && #end <= #storage_ty_bits);
-                            #assert(#end - #start <= #field_ty_bits);
-                        }
-                    }
-                    BitsSpan::Full => {
-                        quote! {}
+                    if !$other {
+                        return Err(Error::new(
+                            $span,
+                            "Conflicting read_only and write_only specifiers",
+                        ));
                     }
                 };
-                move || {
-                    if assert_is_const {
-                        replace(&mut asserts, quote!{})
-                    } else {
-                        asserts.clone()
+            }
+
+            let mut has_nested_option = false;
+            while !options_content.is_empty() {
+                let lookahead = options_content.lookahead1();
+                if lookahead.peek(kw::nested) {
+                    let kw = options_content.parse::<kw::nested>()?;
+                    if nested_prefix || has_nested_option {
+                        return Err(Error::new(kw.span, "Duplicate nested specifiers"));
                     }
+                    has_nested_option = true;
+                } else if lookahead.peek(kw::read_only) || lookahead.peek(kw::ro) {
+                    let span = options_content
+                        .parse::<kw::read_only>()
+                        .map(|kw| kw.span)
+                        .or_else(|_| options_content.parse::<kw::ro>().map(|kw| kw.span))?;
+                    check_accessor_conflict!(is_writable, "read_only", is_readable, span);
+                    is_writable = false;
+                } else if lookahead.peek(kw::write_only) || lookahead.peek(kw::wo) {
+                    let span = options_content
+                        .parse::<kw::write_only>()
+                        .map(|kw| kw.span)
+                        .or_else(|_| options_content.parse::<kw::wo>().map(|kw| kw.span))?;
+                    check_accessor_conflict!(is_readable, "write_only", is_writable, span);
+                    is_readable = false;
+                } else if lookahead.peek(kw::name) {
+                    parse_name_option(&options_content, &mut name)?;
+                } else {
+                    return Err(lookahead.error());
                 }
-            };
 
-            match content {
-                FieldContent::Single(SingleField {
-                    get_kind,
-                    set_kind,
-                }) => {
-                    let set_fn_ident = format_ident!("set_{}", ident);
-                    let with_fn_ident = format_ident!("with_{}", ident);
+                let had_comma = options_content.parse::<Token![,]>().is_ok();
+                if !options_content.is_empty() && !had_comma {
+                    return Err(options_content.error("expected comma between field options"));
+                }
+            }
+        }
+        FieldContent::Nested(NestedField {
+            is_readable,
+            is_writable,
+        })
+    } else {
+        let mut get = AccessorKind::Default;
+        let mut set = AccessorKind::Default;
+        let mut truncate = None;
+        let mut merge_kind = None;
+        let mut unit = None;
+        let mut safety = None;
+        let mut write_guard: Option<(proc_macro2::Span, WriteGuard)> = None;
+        let mut trigger: Option<(proc_macro2::Span, Option<Expr>)> = None;
+        let mut latched = None;
+        let mut nonzero = false;
+        let mut swap = None;
+        let mut byte_order = None;
+        let mut range: Option<(proc_macro2::Span, Expr)> = None;
+        let mut ignore_writes: Option<(proc_macro2::Span, Option<Expr>)> = None;
+        let mut export = None;
+        let mut get_vis: Option<(proc_macro2::Span, Visibility)> = None;
+        let mut set_vis: Option<(proc_macro2::Span, Visibility)> = None;
+        let lookahead = input.lookahead1();
+        if lookahead.peek(token::Bracket) {
+            let options_content;
+            bracketed!(options_content in input);
 
-                    let getter = if !matches!(&get_kind, AccessorKind::Disabled) {
-                        let (calc_get_result, get_output_ty) = match get_kind {
-                            AccessorKind::Default => (quote! { raw_value }, quote! { #field_ty }),
-                            AccessorKind::Disabled => unreachable!(),
+            macro_rules! check_conversion_ty_conflict {
+                ($($ident: ident),*; $span: expr) => {
+                    if $(!matches!(&$ident, AccessorKind::Default))||* {
+                        return Err(Error::new(
+                            $span,
+                            "Conflicting conversion type definitions",
+                        ));
+                    }
+                };
+            }
 
-                            AccessorKind::ConvTy(ty) => (
-                                quote! {
-                                    <#ty as ::core::convert::From<#field_ty>>::from(raw_value)
-                                },
-                                quote! { #ty },
-                            ),
-                            AccessorKind::UnsafeConvTy { ty, has_safe_accessor } => {
-                                let unsafe_ = has_safe_accessor.then(|| quote! { unsafe })
-                                    .into_iter();
-                                (
-                                    quote! {
-                                        #(#unsafe_)* {
-                                            <
-                                                #ty as ::proc_bitfield::UnsafeFrom<#field_ty>
-                                            >::unsafe_from(raw_value)
-                                        }
-                                    },
-                                    quote! { #ty },
-                                )
-                            },
-                            AccessorKind::TryConvTy(ty) => (
-                                quote! {
-                                    <
-                                        #ty as ::core::convert::TryFrom<#field_ty>
-                                    >::try_from(raw_value)
-                                },
-                                quote! {
-                                    ::core::result::Result<
-                                        #ty,
-                                        <#ty as ::core::convert::TryFrom<#field_ty>>::Error,
-                                    >
-                                },
-                            ),
-                            AccessorKind::UnwrapConvTy(ty) => (
-                                quote! {
-                                    <
-                                        #ty as ::core::convert::TryFrom<#field_ty>
-                                    >::try_from(raw_value).unwrap()
-                                },
-                                quote! { #ty },
-                            ),
+            macro_rules! check_accessor_conflict {
+                ($ident: ident, $name: literal, $other: ident, $span: ident) => {
+                    if matches!(&$ident, AccessorKind::Disabled) {
+                        return Err(Error::new(
+                            $span,
+                            concat!("Duplicate ", $name, " specifiers"),
+                        ));
+                    }
+                    if matches!(&$other, AccessorKind::Disabled) {
+                        return Err(Error::new(
+                            $span,
+                            "Conflicting read_only and write_only specifiers",
+                        ));
+                    }
+                };
+            }
 
-                            AccessorKind::ConvFn { fn_, ty } => (
-                                quote! { #fn_(raw_value) },
-                                quote! { #ty },
-                            ),
-                            AccessorKind::UnsafeConvFn { fn_, ty, has_safe_accessor } => {
-                                let unsafe_ = has_safe_accessor.then(|| quote! { unsafe })
-                                    .into_iter();
-                                (
-                                    quote! { #(#unsafe_)* { #fn_(raw_value) } },
-                                    quote! { #ty },
-                                )
-                            },
+            fn parse_return_ty(input: ParseStream) -> Result<Result<Type>> {
+                if let Err(err) = input.parse::<Token![->]>() {
+                    return Ok(Err(err));
+                }
+                Ok(input.parse())
+            }
 
-                            AccessorKind::TryGetFn { fn_, result_ty } => (
-                                quote! { #fn_(raw_value) },
-                                quote! { #result_ty },
-                            ),
-                            AccessorKind::TrySetFn { .. } => unreachable!(),
-                            AccessorKind::UnwrapConvFn { fn_, ty } => (
-                                quote! { #fn_(raw_value).unwrap() },
-                                quote! { #ty },
-                            )
-                        };
+            fn parse_parenthesized_ty(input: ParseStream) -> Result<Result<Type>> {
+                Ok(match parse_parens(input) {
+                    Ok(content) => content.parse(),
+                    Err(err) => Err(err),
+                })
+            }
 
-                        let get_raw_value = match &bits_span {
-                            BitsSpan::Single(bit) => {
-                                quote_spanned! {
-                                    ident.span() =>
-                                    let raw_value = <#storage_ty as ::proc_bitfield::Bit>
-                                        ::bit::<#bit>(&self.0);
-                                }
-                            }
-                            BitsSpan::Range { start, end } => {
-                                quote_spanned! {
-                                    ident.span() =>
-                                    let raw_value = <
-                                        #storage_ty as ::proc_bitfield::Bits<#field_ty>
-                                    >::bits::<#start, #end>(&self.0);
-                                }
-                            }
-                            BitsSpan::Full => {
-                                quote_spanned! {
-                                    ident.span() =>
-                                    let raw_value = <
-                                        #storage_ty as ::proc_bitfield::Bits<#field_ty>
-                                    >::bits::<0, #storage_ty_bits>(&self.0);
-                                }
-                            }
+            while !options_content.is_empty() {
+                // Infallible conversions
+                if let Ok(kw) = options_content.parse::<kw::get>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    get = AccessorKind::ConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::get_cached>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    get = AccessorKind::CachedConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::set>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    set = AccessorKind::ConvTy(options_content.parse()?);
+                }
+                // Unsafe conversions
+                else if let Ok(kw) = options_content.parse::<kw::unsafe_get>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    get = AccessorKind::UnsafeConvTy {
+                        ty: options_content.parse()?,
+                        has_safe_accessor,
+                    };
+                } else if let Ok(kw) = options_content.parse::<kw::unsafe_set>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    set = AccessorKind::UnsafeConvTy {
+                        ty: options_content.parse()?,
+                        has_safe_accessor,
+                    };
+                } else if let Ok(kw) = options_content.parse::<kw::unsafe_both>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::UnsafeConvTy {
+                        ty: ty.clone(),
+                        has_safe_accessor,
+                    };
+                    set = AccessorKind::UnsafeConvTy {
+                        ty,
+                        has_safe_accessor,
+                    };
+                } else if let Ok(kw) = options_content.parse::<Token![unsafe]>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::UnsafeConvTy {
+                        ty: ty.clone(),
+                        has_safe_accessor,
+                    };
+                    set = AccessorKind::ConvTy(ty);
+                }
+                // Fallible conversions
+                else if let Ok(kw) = options_content.parse::<kw::try_get>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    get = AccessorKind::TryConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::try_set>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    set = AccessorKind::TryConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::try_both>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::TryConvTy(ty.clone());
+                    set = AccessorKind::TryConvTy(ty);
+                } else if let Ok(kw) = options_content.parse::<Token![try]>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::TryConvTy(ty.clone());
+                    set = AccessorKind::ConvTy(ty);
+                }
+                // Unwrapping conversions
+                else if let Ok(kw) = options_content.parse::<kw::unwrap_get>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    get = AccessorKind::UnwrapConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::unwrap_set>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    set = AccessorKind::UnwrapConvTy(options_content.parse()?);
+                } else if let Ok(kw) = options_content.parse::<kw::unwrap_both>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::UnwrapConvTy(ty.clone());
+                    set = AccessorKind::UnwrapConvTy(ty);
+                } else if let Ok(kw) = options_content.parse::<kw::unwrap>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let ty: Type = options_content.parse()?;
+                    get = AccessorKind::UnwrapConvTy(ty.clone());
+                    set = AccessorKind::ConvTy(ty);
+                }
+                // Infallible fn conversions
+                else if let Ok(kw) = options_content.parse::<kw::get_fn>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty = parse_return_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    get = AccessorKind::ConvFn { fn_, ty };
+                } else if let Ok(kw) = options_content.parse::<kw::set_fn>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty =
+                        parse_parenthesized_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    set = AccessorKind::ConvFn { fn_, ty };
+                }
+                // Unsafe fn conversions
+                else if let Ok(kw) = options_content.parse::<kw::unsafe_get_fn>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty = parse_return_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    get = AccessorKind::UnsafeConvFn {
+                        fn_,
+                        ty,
+                        has_safe_accessor,
+                    };
+                } else if let Ok(kw) = options_content.parse::<kw::unsafe_set_fn>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    let has_safe_accessor = options_content.parse::<Token![!]>().is_ok();
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty =
+                        parse_parenthesized_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    set = AccessorKind::UnsafeConvFn {
+                        fn_,
+                        ty,
+                        has_safe_accessor,
+                    };
+                }
+                // Fallible fn conversions
+                else if let Ok(kw) = options_content.parse::<kw::try_get_fn>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let result_ty = parse_return_ty(&options_content)??;
+                    get = AccessorKind::TryGetFn { fn_, result_ty };
+                } else if let Ok(kw) = options_content.parse::<kw::try_set_fn>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let input_ty =
+                        parse_parenthesized_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    let result_ty = parse_return_ty(&options_content)??;
+                    set = AccessorKind::TrySetFn {
+                        fn_,
+                        input_ty,
+                        result_ty,
+                    };
+                }
+                // Unwrapping fn conversions
+                else if let Ok(kw) = options_content.parse::<kw::unwrap_get_fn>() {
+                    check_conversion_ty_conflict!(get; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty = parse_return_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    get = AccessorKind::UnwrapConvFn { fn_, ty };
+                } else if let Ok(kw) = options_content.parse::<kw::unwrap_set_fn>() {
+                    check_conversion_ty_conflict!(set; kw.span);
+                    let fn_ = parse_accessor_fn(&options_content)?;
+                    let ty =
+                        parse_parenthesized_ty(&options_content)?.unwrap_or_else(|_| ty.clone());
+                    set = AccessorKind::UnwrapConvFn { fn_, ty };
+                }
+                // Zero-as-`None` conversions
+                else if let Ok(kw) = options_content.parse::<kw::nonzero>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let conv_ty: Type = parse_quote_spanned! {
+                        kw.span =>
+                        ::core::option::Option<::core::num::NonZero<#ty>>
+                    };
+                    get = AccessorKind::ConvFn {
+                        fn_: parse_quote_spanned! { kw.span => ::core::num::NonZero::new },
+                        ty: conv_ty.clone(),
+                    };
+                    set = AccessorKind::ConvFn {
+                        fn_: parse_quote_spanned! {
+                            kw.span =>
+                            (|value: #conv_ty| value.map_or(0, ::core::num::NonZero::get))
+                        },
+                        ty: conv_ty,
+                    };
+                    nonzero = true;
+                }
+                // Tick count conversions
+                else if let Ok(kw) = options_content.parse::<kw::duration>() {
+                    check_conversion_ty_conflict!(get, set; kw.span);
+                    let content = parse_parens(&options_content)?;
+                    let hz = content.parse::<Expr>()?;
+                    let rounding = if content.parse::<Token![,]>().is_ok() && !content.is_empty() {
+                        let lookahead = content.lookahead1();
+                        let rounding = if lookahead.peek(kw::floor) {
+                            quote! { Floor }
+                        } else if lookahead.peek(kw::ceil) {
+                            quote! { Ceil }
+                        } else if lookahead.peek(kw::nearest) {
+                            quote! { Nearest }
+                        } else {
+                            return Err(lookahead.error());
                         };
-
-                        let get_unsafe = get_kind.is_unsafe()
-                            .then(|| quote! { unsafe })
-                            .into_iter();
-                        let bits_span_asserts = bits_span_asserts();
-                        quote! {
-                            #(#attrs)*
-                            #[inline]
-                            #[allow(clippy::identity_op)]
-                            #vis #(#get_unsafe)* fn #ident(&self) -> #get_output_ty {
-                                #bits_span_asserts
-                                #get_raw_value
-                                #calc_get_result
-                            }
-                        }
+                        content.parse::<Ident>()?;
+                        rounding
                     } else {
-                        quote! {}
+                        quote! { Floor }
                     };
-
-                    let setters = if !matches!(&set_kind, AccessorKind::Disabled) {
-                        let (
-                            calc_set_with_raw_value,
-                            set_with_input_ty,
-                            set_ok,
-                            set_output_ty,
-                            with_ok,
-                            with_output_ty,
-                        ) = match set_kind {
-                            AccessorKind::Default => (
-                                quote! { value },
+                    if !content.is_empty() {
+                        return Err(content.error("unexpected tokens after rounding policy"));
+                    }
+                    let conv_ty: Type = parse_quote_spanned! {
+                        kw.span => ::core::time::Duration
+                    };
+                    get = AccessorKind::ConvFn {
+                        fn_: parse_quote_spanned! {
+                            kw.span =>
+                            (|raw: #ty| ::proc_bitfield::ops::ticks_to_duration(
+                                raw as u64,
+                                #hz,
+                                ::proc_bitfield::ops::Rounding::#rounding,
+                            ))
+                        },
+                        ty: conv_ty.clone(),
+                    };
+                    set = AccessorKind::ConvFn {
+                        fn_: parse_quote_spanned! {
+                            kw.span =>
+                            (|value: #conv_ty| ::proc_bitfield::ops::duration_to_ticks(
+                                value,
+                                #hz,
+                                ::proc_bitfield::ops::Rounding::#rounding,
+                            ) as #ty)
+                        },
+                        ty: conv_ty,
+                    };
+                }
+                // Access restrictions
+                else if let Ok(span) = options_content
+                    .parse::<kw::read_only>()
+                    .map(|kw| kw.span)
+                    .or_else(|_| options_content.parse::<kw::ro>().map(|kw| kw.span))
+                {
+                    check_accessor_conflict!(set, "read_only", get, span);
+                    set = AccessorKind::Disabled;
+                } else if let Ok(span) = options_content
+                    .parse::<kw::write_only>()
+                    .map(|kw| kw.span)
+                    .or_else(|_| options_content.parse::<kw::wo>().map(|kw| kw.span))
+                {
+                    check_accessor_conflict!(get, "write_only", set, span);
+                    get = AccessorKind::Disabled;
+                }
+                // Range truncation
+                else if let Ok(kw) = options_content.parse::<kw::truncate>() {
+                    if truncate.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate truncate specifiers"));
+                    }
+                    truncate = Some(kw.span);
+                }
+                // Merge behavior
+                else if let Ok(kw) = options_content.parse::<kw::merge_or>() {
+                    if merge_kind.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate merge specifiers"));
+                    }
+                    merge_kind = Some(MergeKind::Or);
+                } else if let Ok(kw) = options_content.parse::<kw::merge_last>() {
+                    if merge_kind.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate merge specifiers"));
+                    }
+                    merge_kind = Some(MergeKind::Last);
+                }
+                // Unit annotation
+                else if let Ok(kw) = options_content.parse::<kw::unit>() {
+                    if unit.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate unit specifiers"));
+                    }
+                    unit = Some(options_content.parse::<LitStr>()?);
+                }
+                // Safety documentation
+                else if let Ok(kw) = options_content.parse::<kw::safety>() {
+                    if safety.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate safety specifiers"));
+                    }
+                    safety = Some((kw.span, options_content.parse::<LitStr>()?));
+                }
+                // Write guards
+                else if let Ok(span) = options_content
+                    .parse::<kw::write_once>()
+                    .map(|kw| (kw.span, WriteGuardKind::Once))
+                    .or_else(|_| {
+                        options_content
+                            .parse::<kw::locked_by>()
+                            .map(|kw| (kw.span, WriteGuardKind::LockedBy))
+                    })
+                    .map(|(span, kind)| {
+                        if write_guard.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "Conflicting write_once and locked_by specifiers",
+                            ));
+                        }
+                        let field = options_content.parse::<Ident>()?;
+                        write_guard = Some((span, WriteGuard { kind, field }));
+                        Ok(span)
+                    })
+                {
+                    span?;
+                }
+                // Self-clearing trigger bits
+                else if let Ok(kw) = options_content.parse::<kw::trigger>() {
+                    if trigger.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate trigger specifiers"));
+                    }
+                    let hook = if options_content.is_empty() || options_content.peek(Token![,]) {
+                        None
+                    } else {
+                        Some(parse_accessor_fn(&options_content)?)
+                    };
+                    trigger = Some((kw.span, hook));
+                }
+                // Sticky latched bits
+                else if let Ok(kw) = options_content.parse::<kw::latched>() {
+                    if latched.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate latched specifiers"));
+                    }
+                    latched = Some(kw.span);
+                }
+                // C ABI accessor functions
+                else if let Ok(kw) = options_content.parse::<kw::export>() {
+                    if export.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate export specifiers"));
+                    }
+                    export = Some(kw.span);
+                }
+                // Accessor name override
+                else if options_content.peek(kw::name) && options_content.peek2(Token![=]) {
+                    parse_name_option(&options_content, &mut name)?;
+                }
+                // Accessor visibility overrides
+                else if let Ok(kw) = options_content.parse::<kw::get_vis>() {
+                    if get_vis.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate get_vis specifiers"));
+                    }
+                    get_vis = Some((kw.span, options_content.parse()?));
+                } else if let Ok(kw) = options_content.parse::<kw::set_vis>() {
+                    if set_vis.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate set_vis specifiers"));
+                    }
+                    set_vis = Some((kw.span, options_content.parse()?));
+                }
+                // Legal value ranges
+                else if let Ok(kw) = options_content.parse::<kw::range>() {
+                    if range.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate range specifiers"));
+                    }
+                    range = Some((kw.span, options_content.parse::<Expr>()?));
+                }
+                // Discarded writes
+                else if let Ok(kw) = options_content.parse::<kw::ignore_writes>() {
+                    if ignore_writes.is_some() {
+                        return Err(Error::new(kw.span, "Duplicate ignore_writes specifiers"));
+                    }
+                    let fixed_value = if options_content.peek(token::Paren) {
+                        Some(parse_parens(&options_content)?.parse::<Expr>()?)
+                    } else {
+                        None
+                    };
+                    ignore_writes = Some((kw.span, fixed_value));
+                }
+                // Raw value byte order swaps
+                else if let Ok(span) = options_content
+                    .parse::<kw::byte_swap>()
+                    .map(|kw| (kw.span, SwapKind::Bytes))
+                    .or_else(|_| {
+                        options_content
+                            .parse::<kw::nibble_swap>()
+                            .map(|kw| (kw.span, SwapKind::Nibbles))
+                    })
+                    .map(|(span, kind)| {
+                        if byte_order.is_some() {
+                            return Err(Error::new(span, "Conflicting byte order specifiers"));
+                        }
+                        if swap.is_some() {
+                            return Err(Error::new(
+                                span,
+                                "Conflicting byte_swap and nibble_swap specifiers",
+                            ));
+                        }
+                        swap = Some(kind);
+                        Ok(span)
+                    })
+                {
+                    span?;
+                }
+                // Field byte orders, relative to the little-endian order of byte array storage
+                else if let Ok(span) = options_content
+                    .parse::<kw::be>()
+                    .map(|kw| (kw.span, Some(SwapKind::Bytes)))
+                    .or_else(|_| options_content.parse::<kw::le>().map(|kw| (kw.span, None)))
+                    .map(|(span, kind)| {
+                        if swap.is_some() || byte_order.is_some() {
+                            return Err(Error::new(span, "Conflicting byte order specifiers"));
+                        }
+                        swap = kind;
+                        byte_order = Some(span);
+                        Ok(span)
+                    })
+                {
+                    span?;
+                }
+                // Infallible conversion (without keywords)
+                else {
+                    let ty: Type = options_content.parse()?;
+                    check_conversion_ty_conflict!(get, set; ty.span());
+                    get = AccessorKind::ConvTy(ty.clone());
+                    set = AccessorKind::ConvTy(ty);
+                }
+
+                let had_comma = options_content.parse::<Token![,]>().is_ok();
+                if !options_content.is_empty() && !had_comma {
+                    return Err(options_content.error("expected comma between field options"));
+                }
+            }
+        }
+        if let Some((uint_ty, bits)) = &uint_ty {
+            let span = uint_ty.span();
+            let (get_fn, set_fn) = if *bits == 1 {
+                (
+                    parse_quote_spanned! { span => (|raw: bool| <#uint_ty>::new(raw as u8)) },
+                    parse_quote_spanned! {
+                        span => (|value: #uint_ty| <#uint_ty>::value(value) != 0)
+                    },
+                )
+            } else {
+                (
+                    parse_quote_spanned! { span => <#uint_ty>::new },
+                    parse_quote_spanned! { span => <#uint_ty>::value },
+                )
+            };
+            for (kind, fn_) in [(&mut get, get_fn), (&mut set, set_fn)] {
+                match kind {
+                    AccessorKind::Default => {
+                        *kind = AccessorKind::ConvFn {
+                            fn_,
+                            ty: uint_ty.clone(),
+                        }
+                    }
+                    AccessorKind::Disabled => {}
+                    _ => {
+                        return Err(Error::new(
+                            span,
+                            "fields of arbitrary-int types can't have conversions",
+                        ))
+                    }
+                }
+            }
+        }
+        if let Some((span, _)) = &write_guard {
+            if matches!(
+                set,
+                AccessorKind::Disabled | AccessorKind::TryConvTy(_) | AccessorKind::TrySetFn { .. }
+            ) {
+                return Err(Error::new(
+                    *span,
+                    "write_once and locked_by can't be specified for read-only fields or fields \
+                     with fallible setter conversions",
+                ));
+            }
+        }
+        if let Some((span, _)) = &trigger {
+            if !matches!(get, AccessorKind::Default)
+                || !matches!(set, AccessorKind::Default)
+                || write_guard.is_some()
+                || latched.is_some()
+            {
+                return Err(Error::new(
+                    *span,
+                    "trigger can't be combined with access restrictions, conversions, write guards \
+                     or latched",
+                ));
+            }
+            get = AccessorKind::Disabled;
+            set = AccessorKind::Disabled;
+        }
+        if let Some(span) = latched {
+            if !matches!(set, AccessorKind::Default) {
+                return Err(Error::new(
+                    span,
+                    "latched can't be specified for read-only fields or fields with setter \
+                     conversions",
+                ));
+            }
+        }
+        if let Some((span, _)) = &range {
+            if !matches!(get, AccessorKind::Default | AccessorKind::Disabled)
+                || matches!(
+                    set,
+                    AccessorKind::TryConvTy(_) | AccessorKind::TrySetFn { .. }
+                )
+                || write_guard.is_some()
+                || trigger.is_some()
+                || latched.is_some()
+            {
+                return Err(Error::new(
+                    *span,
+                    "range can't be specified for fields with getter conversions, fallible setter \
+                     conversions, write guards, trigger or latched",
+                ));
+            }
+        }
+        if let Some((span, _)) = &ignore_writes {
+            if matches!(set, AccessorKind::Disabled)
+                || write_guard.is_some()
+                || trigger.is_some()
+                || latched.is_some()
+                || range.is_some()
+            {
+                return Err(Error::new(
+                    *span,
+                    "ignore_writes can't be specified for read-only fields or combined with write \
+                     guards, trigger, latched or range",
+                ));
+            }
+        }
+        if let Some((span, _)) = &get_vis {
+            if matches!(get, AccessorKind::Disabled) {
+                return Err(Error::new(
+                    *span,
+                    "get_vis can't be specified for write-only or trigger fields",
+                ));
+            }
+        }
+        if let Some((span, _)) = &set_vis {
+            if matches!(set, AccessorKind::Disabled) && trigger.is_none() {
+                return Err(Error::new(
+                    *span,
+                    "set_vis can't be specified for read-only fields",
+                ));
+            }
+        }
+        if let Some(span) = export {
+            if trigger.is_some() || swap.is_some() {
+                return Err(Error::new(
+                    span,
+                    "export can't be combined with trigger, byte_swap or nibble_swap",
+                ));
+            }
+        }
+        if let Some((span, _)) = &safety {
+            if !get.is_unsafe() && !set.is_unsafe() {
+                return Err(Error::new(
+                    *span,
+                    "safety can only be specified for fields with unsafe accessors",
+                ));
+            }
+        }
+        FieldContent::Single(SingleField {
+            get_kind: get,
+            set_kind: set,
+            truncate,
+            merge_kind,
+            unit,
+            safety: safety.map(|(_, safety)| safety),
+            write_guard: write_guard.map(|(_, write_guard)| write_guard),
+            trigger: trigger.map(|(_, hook)| hook),
+            latched: latched.is_some(),
+            nonzero,
+            swap,
+            byte_order,
+            uint_bits: uint_ty.as_ref().map(|(ty, bits)| (*bits, ty.span())),
+            range: range.map(|(_, range)| range),
+            ignore_writes: ignore_writes.map(|(_, fixed_value)| fixed_value),
+            export,
+            get_vis: get_vis.map(|(_, vis)| vis),
+            set_vis: set_vis.map(|(_, vis)| vis),
+        })
+    };
+    let default = if let Ok(eq) = input.parse::<Token![=]>() {
+        if matches!(content, FieldContent::Nested(_)) {
+            return Err(Error::new(
+                eq.span,
+                "nested bitfield fields can't have default values",
+            ));
+        }
+        Some(input.parse::<Expr>()?)
+    } else {
+        None
+    };
+    let bits_span = input.span();
+    let bits = if input.is_empty() || input.peek(Token![,]) {
+        // Fields without a bit range are packed above the previous one, spanning the full width
+        // of their type
+        let is_bool = matches!(&ty, Type::Path(ty) if ty.path.is_ident("bool"));
+        if is_bool && array_len.is_none() {
+            Bits::SinglePack {
+                above_below_span: bits_span,
+                above: true,
+            }
+        } else {
+            // `u8; 3` would otherwise silently declare an array of 3 whole bytes, when it's easily
+            // mistaken for a 3-bit field packed in declaration order
+            if let Some(len) = array_len.filter(|_| is_int_storage(&ty)) {
+                let ty = quote! { #ty };
+                return Err(Error::new(
+                    bits_span,
+                    format!(
+                        "`{ty}; {len}` without a bit range would be an array of {len} full-width \
+                         `{ty}` elements; use `@ above; {len}` for a {len}-bit field packed above \
+                         the previous one, or give the array an explicit bit range"
+                    ),
+                ));
+            }
+            let elem_bits: Expr = if is_bool {
+                parse_quote! { 1 }
+            } else if let Some((_, bits)) = uint_ty {
+                let bits = bits as usize;
+                parse_quote! { #bits }
+            } else {
+                parse_quote! { (::core::mem::size_of::<#ty>() << 3) }
+            };
+            let len = array_len.unwrap_or(1);
+            Bits::Pack {
+                above_below_span: bits_span,
+                above: true,
+                length: parse_quote! { #elem_bits * #len },
+            }
+        }
+    } else {
+        input.parse::<Token![@]>()?;
+        input.parse()?
+    };
+    if matches!(content, FieldContent::Nested(_)) && matches!(bits, Bits::Strided { .. }) {
+        return Err(Error::new(
+            bits_span,
+            "nested bitfield fields can't be strided",
+        ));
+    }
+    if let FieldContent::Single(SingleField {
+        truncate: Some(span),
+        ..
+    }) = &content
+    {
+        if !matches!(bits, Bits::RangeFull) {
+            return Err(Error::new(
+                *span,
+                "truncate can only be specified for fields spanning the full range (`..`)",
+            ));
+        }
+    }
+    if array_len.is_some() && matches!(content, FieldContent::Nested(_)) {
+        return Err(Error::new(
+            bits_span,
+            "nested bitfield fields can't be arrays",
+        ));
+    }
+    let accessor_name = name.clone().unwrap_or_else(|| ident.clone());
+    Ok(Field {
+        attrs,
+        vis,
+        ident,
+        bits,
+        ty,
+        array_len,
+        default,
+        name,
+        getter_ident: accessor_name.clone(),
+        accessor_name,
+        content,
+    })
+}
+
+/// Parses the `name = "..."` field option, setting the base name of the field's accessors.
+fn parse_name_option(input: ParseStream, name: &mut Option<Ident>) -> Result<()> {
+    let kw = input.parse::<kw::name>()?;
+    input.parse::<Token![=]>()?;
+    let lit = input.parse::<LitStr>()?;
+    if name.replace(lit.parse()?).is_some() {
+        return Err(Error::new(kw.span, "Duplicate name specifiers"));
+    }
+    Ok(())
+}
+
+/// Parses a view of a bitfield's storage (`view name [as StructName] { fields }`), returning the
+/// bitfield struct declaration for its own fields and the nested field accessing it.
+fn parse_view(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    parent: &Struct,
+) -> Result<(proc_macro2::TokenStream, Field)> {
+    let kw = input.parse::<kw::view>()?;
+    let ident = input.parse::<Ident>()?;
+    let struct_ident = if input.parse::<Token![as]>().is_ok() {
+        Some(input.parse::<Ident>()?)
+    } else {
+        None
+    };
+    if parent.has_generics {
+        return Err(Error::new(
+            kw.span,
+            "views aren't supported for generic bitfields",
+        ));
+    }
+    let content;
+    braced!(content in input);
+    let view_fields = content.parse::<proc_macro2::TokenStream>()?;
+
+    let parent_ident = &parent.ident;
+    let view_ident = struct_ident.unwrap_or_else(|| {
+        format_ident!(
+            "{}{}",
+            parent_ident,
+            to_upper_camel_case(&ident.to_string()),
+            span = ident.span()
+        )
+    });
+    let storage = match &parent.storage_bits {
+        Some(bits) => {
+            let bits = proc_macro2::Literal::u32_unsuffixed(*bits);
+            quote! { bits #bits }
+        }
+        None => {
+            let storage_ty = &parent.storage_ty;
+            quote! { #storage_ty }
+        }
+    };
+    let storage_vis = if parent.private_storage {
+        quote! { priv }
+    } else {
+        let storage_vis = &parent.storage_vis;
+        quote! { #storage_vis }
+    };
+    let derives = parent
+        .outer_attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"));
+    let auto_impls = &parent.auto_impls;
+    let mut view_auto_impls = Vec::new();
+    if auto_impls.debug || auto_impls.display {
+        view_auto_impls.push(quote! { Debug });
+    }
+    if auto_impls.u_debug || auto_impls.u_display {
+        view_auto_impls.push(quote! { uDebug });
+    }
+    let mut view_options = Vec::new();
+    if let BitOrder::Msb0 = parent.options.bit_order {
+        view_options.push(quote! { bit_order = msb0 });
+    }
+    if parent.options.no_copy.is_some() {
+        view_options.push(quote! { no_copy });
+    }
+    if !view_options.is_empty() {
+        view_auto_impls.push(quote! { options(#(#view_options),*) });
+    }
+    let view_auto_impls = (!view_auto_impls.is_empty()).then(|| quote! { : #(#view_auto_impls),* });
+    let doc = format!("The `{ident}` view of [`{parent_ident}`]'s storage.");
+    let view = quote! {
+        ::proc_bitfield::bitfield! {
+            #[doc = #doc]
+            #(#attrs)*
+            #(#derives)*
+            #vis struct #view_ident(#storage_vis #storage) #view_auto_impls {
+                #view_fields
+            }
+        }
+    };
+
+    let field = (|input: ParseStream| parse_field(input, attrs, vis))
+        .parse2(quote! { #ident: nested #view_ident @ .. })?;
+    Ok((view, field))
+}
+
+/// Parses the fields and reserved ranges of a flattened bitfield template, in the resolved form
+/// `flatten (offset) (storage) { ... }` passed back by the template's macro, moving their bit
+/// ranges up by the given offset.
+fn parse_flattened(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    bitfield: &mut Struct,
+) -> Result<()> {
+    let kw = input.parse::<kw::flatten>()?;
+    if !matches!(vis, Visibility::Inherited) {
+        return Err(Error::new(
+            vis.span(),
+            "flattened bitfields can't have a visibility",
+        ));
+    }
+    let offset = parse_parens(input)?.parse::<Expr>()?;
+    let storage_ty_bits = {
+        let content = parse_parens(input)?;
+        if content.parse::<Token![priv]>().is_err() {
+            content.parse::<Visibility>()?;
+        }
+        if content.parse::<kw::bits>().is_ok() {
+            let bits = content.parse::<LitInt>()?;
+            quote! { #bits }
+        } else {
+            let ty = content.parse::<Type>()?;
+            quote! { (::core::mem::size_of::<#ty>() << 3) }
+        }
+    };
+    let content;
+    braced!(content in input);
+    let mut first = true;
+    while !content.is_empty() {
+        let mut entry_attrs = attrs.clone();
+        entry_attrs.extend(content.call(Attribute::parse_outer)?);
+        let vis = content.parse()?;
+        if content.peek(kw::reserved) && content.peek2(Token![@]) {
+            let mut reserved = parse_reserved(&content, vis, bitfield.fields.len())?;
+            reserved.bits = reserved.bits.flatten(&offset, &storage_ty_bits, first);
+            bitfield.reserved.push(reserved);
+        } else if content.peek(Token![const])
+            || (content.peek(kw::calc) || content.peek(kw::join) || content.peek(kw::view))
+                && content.peek2(Ident)
+            || content.peek(kw::flatten)
+        {
+            return Err(Error::new(
+                kw.span,
+                "only fields and reserved ranges can be flattened",
+            ));
+        } else {
+            let mut field = parse_field(&content, entry_attrs, vis)?;
+            field.bits = field.bits.flatten(&offset, &storage_ty_bits, first);
+            bitfield.fields.push(field);
+        }
+        first = false;
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+    Ok(())
+}
+
+/// Parses a group of fields (`group name { fields }`), adding its fields to the bitfield with their
+/// names prefixed by the group's name.
+fn parse_group(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    bitfield: &mut Struct,
+) -> Result<()> {
+    let kw = input.parse::<kw::group>()?;
+    if !matches!(vis, Visibility::Inherited) {
+        return Err(Error::new(
+            vis.span(),
+            "field groups can't have a visibility",
+        ));
+    }
+    let prefix = input.parse::<Ident>()?;
+    let content;
+    braced!(content in input);
+    while !content.is_empty() {
+        let mut entry_attrs = attrs.clone();
+        entry_attrs.extend(content.call(Attribute::parse_outer)?);
+        let vis = content.parse()?;
+        if content.peek(kw::reserved) && content.peek2(Token![@]) {
+            let reserved = parse_reserved(&content, vis, bitfield.fields.len())?;
+            bitfield.reserved.push(reserved);
+        } else if content.peek(Token![const])
+            || (content.peek(kw::calc)
+                || content.peek(kw::join)
+                || content.peek(kw::view)
+                || content.peek(kw::group))
+                && content.peek2(Ident)
+            || content.peek(kw::flatten)
+            || content.peek(kw::repeat) && content.peek2(LitInt)
+        {
+            return Err(Error::new(
+                kw.span,
+                "field groups can only contain fields and reserved ranges",
+            ));
+        } else {
+            let mut field = parse_field(&content, entry_attrs, vis)?;
+            for ident in [Some(&mut field.ident), field.name.as_mut()]
+                .into_iter()
+                .flatten()
+            {
+                *ident = format_ident!("{}_{}", prefix, ident, span = ident.span());
+            }
+            field.accessor_name = field.name.clone().unwrap_or_else(|| field.ident.clone());
+            field.getter_ident = field.accessor_name.clone();
+            bitfield.fields.push(field);
+        }
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+    Ok(())
+}
+
+/// Parses a repeat block (`repeat count stride step { fields }`), adding `count` instances of its
+/// fields and reserved ranges to the bitfield, each instance moved up by `step` bits from the
+/// previous one and with its fields' names suffixed by its index.
+fn parse_repeat(
+    input: ParseStream,
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    bitfield: &mut Struct,
+) -> Result<()> {
+    let kw = input.parse::<kw::repeat>()?;
+    if !matches!(vis, Visibility::Inherited) {
+        return Err(Error::new(
+            vis.span(),
+            "repeat blocks can't have a visibility",
+        ));
+    }
+    let count_lit = input.parse::<LitInt>()?;
+    let count = count_lit.base10_parse::<usize>()?;
+    if count == 0 {
+        return Err(Error::new(
+            count_lit.span(),
+            "repeat blocks must have at least one instance",
+        ));
+    }
+    input.parse::<kw::stride>()?;
+    let stride = Expr::parse_without_eager_brace(input)?;
+    let content;
+    braced!(content in input);
+    let mut entries = Vec::new();
+    while !content.is_empty() {
+        let mut entry_attrs = attrs.clone();
+        entry_attrs.extend(content.call(Attribute::parse_outer)?);
+        let vis = content.parse()?;
+        let entry = if content.peek(kw::reserved) && content.peek2(Token![@]) {
+            Err(parse_reserved(&content, vis, 0)?)
+        } else if content.peek(Token![const])
+            || (content.peek(kw::calc)
+                || content.peek(kw::join)
+                || content.peek(kw::view)
+                || content.peek(kw::group))
+                && content.peek2(Ident)
+            || content.peek(kw::flatten)
+            || content.peek(kw::repeat) && content.peek2(LitInt)
+        {
+            return Err(Error::new(
+                kw.span,
+                "repeat blocks can only contain fields and reserved ranges",
+            ));
+        } else {
+            let field = parse_field(&content, entry_attrs, vis)?;
+            if field.array_len.is_some() {
+                return Err(Error::new(
+                    field.ident.span(),
+                    "repeated fields can't be arrays",
+                ));
+            }
+            Ok(field)
+        };
+        let (bits, span) = match &entry {
+            Ok(field) => (&field.bits, field.ident.span()),
+            Err(reserved) => (&reserved.bits, reserved.span),
+        };
+        // Instances are placed at fixed offsets from each other, so their bit ranges can't depend
+        // on the end of the storage, nor can the first entry depend on the previous instance's
+        match bits {
+            Bits::RangeFull | Bits::RangeFrom { .. } => {
+                return Err(Error::new(
+                    span,
+                    "entries of repeat blocks must span bounded bit ranges",
+                ));
+            }
+            Bits::SinglePack { .. } | Bits::Pack { .. } if entries.is_empty() => {
+                return Err(Error::new(
+                    span,
+                    "the first entry of a repeat block can't be packed",
+                ));
+            }
+            _ => {}
+        }
+        entries.push(entry);
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+
+    let mut repeats = entries
+        .iter()
+        .filter_map(|entry| entry.as_ref().ok())
+        .map(|field| Repeat {
+            field: Field {
+                array_len: Some(count),
+                ..field.clone()
+            },
+            instances: Vec::with_capacity(count),
+        })
+        .collect::<Vec<_>>();
+    for i in 0..count {
+        let offset: Expr = parse_quote! { #i * (#stride) };
+        let mut repeats = repeats.iter_mut();
+        for entry in &entries {
+            match entry {
+                Ok(field) => {
+                    let mut field = field.clone();
+                    for ident in [Some(&mut field.ident), field.name.as_mut()]
+                        .into_iter()
+                        .flatten()
+                    {
+                        *ident = format_ident!("{}_{}", ident, i, span = ident.span());
+                    }
+                    field.accessor_name = field.name.clone().unwrap_or_else(|| field.ident.clone());
+                    field.getter_ident = field.accessor_name.clone();
+                    field.bits = field.bits.shift(&offset);
+                    if let Some(repeat) = repeats.next() {
+                        repeat.instances.push(bitfield.fields.len());
+                    }
+                    bitfield.fields.push(field);
+                }
+                Err(reserved) => bitfield.reserved.push(Reserved {
+                    span: reserved.span,
+                    bits: reserved.bits.clone().shift(&offset),
+                    field_index: bitfield.fields.len(),
+                }),
+            }
+        }
+    }
+    bitfield.repeats.append(&mut repeats);
+    Ok(())
+}
+
+/// Resolves all of the tokens' spans at the macro's call site, so that tokens passed through a
+/// template's macro and the template's own tokens share the same hygiene.
+fn resolve_at_call_site(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    use proc_macro2::{Group, Span, TokenTree};
+
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = &tt {
+                let mut resolved =
+                    Group::new(group.delimiter(), resolve_at_call_site(group.stream()));
+                resolved.set_span(group.span());
+                tt = TokenTree::Group(resolved);
+            }
+            tt.set_span(tt.span().resolved_at(Span::call_site()));
+            tt
+        })
+        .collect()
+}
+
+/// Finds the first `flatten template!(...) @ offset` entry in a bitfield's body and expands to a
+/// call to the template's macro, which calls back into `bitfield!` with the entry replaced by the
+/// template's fields (as the fields of other bitfields can't be inspected by the macro).
+///
+/// Returns the input to parse if there are no such entries, with its spans resolved at the call
+/// site if it contains fields flattened by a previous callback.
+fn flatten_callback(
+    input: &proc_macro2::TokenStream,
+) -> std::result::Result<proc_macro2::TokenStream, Result<proc_macro2::TokenStream>> {
+    use proc_macro2::{Delimiter, TokenTree};
+
+    let mut header = input.clone().into_iter().collect::<Vec<_>>();
+    let body = match header.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        _ => return Ok(input.clone()),
+    };
+    let tokens = body.stream().into_iter().collect::<Vec<_>>();
+    let is_comma = |tt: &TokenTree| matches!(tt, TokenTree::Punct(punct) if punct.as_char() == ',');
+    let is_flatten_entry = |i: usize| {
+        let after_attr_or_comma = i == 0
+            || is_comma(&tokens[i - 1])
+            || matches!(
+                &tokens[i - 1],
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket
+            );
+        after_attr_or_comma && matches!(&tokens[i], TokenTree::Ident(ident) if ident == "flatten")
+    };
+    let Some(start) = (0..tokens.len())
+        .find(|&i| is_flatten_entry(i) && matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))))
+    else {
+        let is_flattened = (0..tokens.len()).any(|i| {
+            is_flatten_entry(i)
+                && matches!(
+                    tokens.get(i + 1),
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis
+                )
+        });
+        return Ok(if is_flattened {
+            resolve_at_call_site(input.clone())
+        } else {
+            input.clone()
+        });
+    };
+    let end = (start..tokens.len())
+        .find(|&i| is_comma(&tokens[i]))
+        .unwrap_or(tokens.len());
+    let before = &tokens[..start];
+    let after = tokens.get(end + 1..).unwrap_or_default();
+    let entry = tokens[start + 1..end].iter().cloned().collect();
+
+    Err((|input: ParseStream| {
+        let path = input.parse::<Path>()?;
+        input.parse::<Token![!]>()?;
+        let args = parse_parens(input)?.parse::<proc_macro2::TokenStream>()?;
+        input.parse::<Token![@]>()?;
+        let offset = input.parse::<Expr>()?;
+        Ok(quote! {
+            #path! {
+                @flatten [#(#header)*] { #(#before)* } { #(#after)* } (#offset), #args
+            }
+        })
+    })
+    .parse2(entry))
+}
+
+impl Parse for Struct {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let outer_attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse::<Ident>()?;
+
+        let has_generics = input.peek(Token![<]);
+        let mut generics = input.parse::<Generics>()?;
+
+        let (storage_vis, private_storage, storage_ty, storage_bits, extra_members) = {
+            let content;
+            parenthesized!(content in input);
+            let (storage_vis, private_storage) = if content.parse::<Token![priv]>().is_ok() {
+                (Visibility::Inherited, true)
+            } else {
+                (content.parse()?, false)
+            };
+            // Arbitrary storage widths, backed by the narrowest unsigned integer type that fits
+            let (storage_ty, storage_bits) = if content.parse::<kw::bits>().is_ok() {
+                let bits_lit = content.parse::<LitInt>()?;
+                let bits = bits_lit.base10_parse::<u32>()?;
+                let storage_ty: Type = match bits {
+                    1..=8 => parse_quote! { u8 },
+                    9..=16 => parse_quote! { u16 },
+                    17..=32 => parse_quote! { u32 },
+                    33..=64 => parse_quote! { u64 },
+                    65..=128 => parse_quote! { u128 },
+                    _ => {
+                        return Err(Error::new(
+                            bits_lit.span(),
+                            "storage widths must be between 1 and 128 bits",
+                        ))
+                    }
+                };
+                (storage_ty, Some(bits))
+            } else {
+                let elem_ty = content.parse::<Type>()?;
+                // Multi-word storage, as an array of the given integer type
+                let storage_ty = if content.parse::<Token![;]>().is_ok() {
+                    let len = content.parse::<Expr>()?;
+                    parse_quote! { [#elem_ty; #len] }
+                } else {
+                    elem_ty
+                };
+                (storage_ty, None)
+            };
+            let mut extra_members = Vec::new();
+            while content.parse::<Token![,]>().is_ok() && !content.is_empty() {
+                let attrs = content.call(Attribute::parse_outer)?;
+                let vis = content.parse()?;
+                let ty = content.parse()?;
+                if !content.peek(Token![=]) {
+                    return Err(content.error(
+                        "expected `=` followed by the extra member's initial value; multi-word \
+                         storage can be declared as an array instead, i.e. `(pub u64; 2)`",
+                    ));
+                }
+                content.parse::<Token![=]>()?;
+                let init = content.parse()?;
+                extra_members.push(ExtraMember {
+                    attrs,
+                    vis,
+                    ty,
+                    init,
+                });
+            }
+            (
+                storage_vis,
+                private_storage,
+                storage_ty,
+                storage_bits,
+                extra_members,
+            )
+        };
+
+        let mut auto_impls = AutoImpls {
+            debug: false,
+            display: false,
+            from_storage: false,
+            into_storage: false,
+            deref_storage: false,
+            merge: false,
+            valid: false,
+            flags: false,
+            remote: false,
+            u_debug: false,
+            u_display: false,
+        };
+        let mut options = Options::default();
+        let mut has_options = false;
+        if input.parse::<Token![:]>().is_ok() {
+            loop {
+                if input.is_empty() {
+                    break;
+                }
+                if input.parse::<kw::Debug>().is_ok() {
+                    auto_impls.debug = true;
+                } else if input.parse::<kw::Display>().is_ok() {
+                    auto_impls.display = true;
+                } else if let Ok(kw) = input.parse::<kw::FromStorage>() {
+                    if private_storage {
+                        return Err(Error::new(
+                            kw.span,
+                            "`FromStorage` can't be implemented for bitfields with private storage",
+                        ));
+                    }
+                    auto_impls.from_storage = true;
+                } else if input.parse::<kw::IntoStorage>().is_ok() {
+                    auto_impls.into_storage = true;
+                } else if let Ok(kw) = input.parse::<kw::DerefStorage>() {
+                    if private_storage {
+                        return Err(Error::new(
+                            kw.span,
+                            "`DerefStorage` can't be implemented for bitfields with private storage",
+                        ));
+                    }
+                    auto_impls.deref_storage = true;
+                } else if input.parse::<kw::Merge>().is_ok() {
+                    auto_impls.merge = true;
+                } else if input.parse::<kw::Valid>().is_ok() {
+                    auto_impls.valid = true;
+                } else if input.parse::<kw::Flags>().is_ok() {
+                    auto_impls.flags = true;
+                } else if let Ok(kw) = input.parse::<kw::Remote>() {
+                    if private_storage {
+                        return Err(Error::new(
+                            kw.span,
+                            "`Remote` can't be implemented for bitfields with private storage",
+                        ));
+                    }
+                    if has_generics {
+                        return Err(Error::new(
+                            kw.span,
+                            "`Remote` can't be implemented for generic bitfields",
+                        ));
+                    }
+                    auto_impls.remote = true;
+                } else if let Ok(kw) = input.parse::<kw::options>() {
+                    if replace(&mut has_options, true) {
+                        return Err(Error::new(kw.span, "Duplicate options"));
+                    }
+                    parse_options(input, &mut options)?;
+                    if let Some(span) = options.const_ {
+                        if has_generics {
+                            return Err(Error::new(
+                                span,
+                                "const accessors aren't supported for generic bitfields",
+                            ));
+                        }
+                    }
+                    if let Some(span) = options.deny_gaps {
+                        if has_generics {
+                            return Err(Error::new(
+                                span,
+                                "gap checking isn't supported for generic bitfields",
+                            ));
+                        }
+                    }
+                } else if !parse_ufmt_auto_impl(input, &mut auto_impls) {
+                    break;
+                }
+                if input.parse::<Token![,]>().is_err() {
+                    break;
+                }
+            }
+            if let Some(span) = options.minimal {
+                let AutoImpls {
+                    debug,
+                    display,
+                    from_storage,
+                    into_storage,
+                    deref_storage,
+                    merge,
+                    valid,
+                    flags,
+                    remote,
+                    u_debug,
+                    u_display,
+                } = auto_impls;
+                if debug
+                    || display
+                    || from_storage
+                    || into_storage
+                    || deref_storage
+                    || merge
+                    || valid
+                    || flags
+                    || remote
+                    || u_debug
+                    || u_display
+                {
+                    return Err(Error::new(
+                        span,
+                        "minimal bitfields can't have automatic implementations",
+                    ));
+                }
+            }
+            if let Some(transform) = &options.transform {
+                if private_storage {
+                    return Err(Error::new(
+                        transform.span(),
+                        "transform can't be used for bitfields with private storage",
+                    ));
+                }
+            }
+            if let Some(valid_name) = &options.valid_name {
+                if !auto_impls.valid {
+                    return Err(Error::new(
+                        valid_name.span(),
+                        "valid_name requires the Valid automatic implementation",
+                    ));
+                }
+            }
+            if let Some(remote_name) = &options.remote_name {
+                if !auto_impls.remote {
+                    return Err(Error::new(
+                        remote_name.span(),
+                        "remote_name requires the Remote automatic implementation",
+                    ));
+                }
+            }
+            if !auto_impls.debug && !auto_impls.u_debug {
+                let span = options
+                    .debug_name
+                    .as_ref()
+                    .map(LitStr::span)
+                    .or_else(|| options.debug_raw.as_ref().map(LitBool::span));
+                if let Some(span) = span {
+                    return Err(Error::new(
+                        span,
+                        "debug_name and debug_raw require the Debug or uDebug automatic \
+                         implementations",
+                    ));
+                }
+            }
+        }
+
+        if input.peek(Token![where]) {
+            *generics.make_where_clause() = input.parse()?;
+        };
+
+        let lookahead = input.lookahead1();
+        if !lookahead.peek(token::Brace) {
+            return Err(lookahead.error());
+        }
+
+        let content;
+        braced!(content in input);
+        assert!(
+            content.call(Attribute::parse_inner)?.is_empty(),
+            "Inner attributes are not supported right now"
+        );
+        let mut bitfield = Struct {
+            outer_attrs,
+            vis,
+            ident,
+            storage_vis,
+            private_storage,
+            storage_ty,
+            storage_bits,
+            extra_members,
+            auto_impls,
+            options,
+            has_generics,
+            generics,
+            fields: Vec::new(),
+            consts: Vec::new(),
+            calcs: Vec::new(),
+            reserved: Vec::new(),
+            views: Vec::new(),
+            repeats: Vec::new(),
+        };
+        while !content.is_empty() {
+            let attrs = content.call(Attribute::parse_outer)?;
+            let vis = content.parse()?;
+            let field_index = bitfield.fields.len();
+            if content.peek(Token![const]) {
+                bitfield.consts.push(parse_const(&content, attrs, vis)?);
+                if content.parse::<Token![;]>().is_ok() {
+                    continue;
+                }
+            } else if content.peek(kw::calc) && content.peek2(Ident) {
+                bitfield
+                    .calcs
+                    .push(parse_calc(&content, attrs, vis, field_index)?);
+            } else if content.peek(kw::join) && content.peek2(Ident) {
+                bitfield
+                    .calcs
+                    .push(parse_join(&content, attrs, vis, field_index)?);
+            } else if content.peek(kw::reserved) && content.peek2(Token![@]) {
+                bitfield
+                    .reserved
+                    .push(parse_reserved(&content, vis, field_index)?);
+            } else if content.peek(kw::flatten) && content.peek2(token::Paren) {
+                parse_flattened(&content, attrs, vis, &mut bitfield)?;
+            } else if content.peek(kw::group) && content.peek2(Ident) && content.peek3(token::Brace)
+            {
+                parse_group(&content, attrs, vis, &mut bitfield)?;
+            } else if content.peek(kw::repeat) && content.peek2(LitInt) {
+                parse_repeat(&content, attrs, vis, &mut bitfield)?;
+            } else if content.peek(kw::view)
+                && content.peek2(Ident)
+                && (content.peek3(token::Brace) || content.peek3(Token![as]))
+            {
+                let (view, field) = parse_view(&content, attrs, vis, &bitfield)?;
+                bitfield.views.push(view);
+                bitfield.fields.push(field);
+            } else {
+                bitfield.fields.push(parse_field(&content, attrs, vis)?);
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+
+        // Nested fields (including views) are accessed through the parent's `Bitfield`
+        // implementation, which minimal bitfields don't have
+        if bitfield.options.minimal.is_some() {
+            if let Some(field) = bitfield
+                .fields
+                .iter()
+                .find(|field| matches!(field.content, FieldContent::Nested(_)))
+            {
+                return Err(Error::new(
+                    field.ident.span(),
+                    "nested fields and views aren't supported for minimal bitfields",
+                ));
+            }
+        }
+
+        Ok(bitfield)
+    }
+}
+
+/// Generates a constant expression equivalent to `WithBits::with_bits`/`WithBit::with_bit` on an
+/// integer storage value named `raw`, to be used where trait methods can't be called.
+/// Returns an expression evaluating to the number of the given fields that are enabled by their
+/// `cfg` attributes.
+fn enabled_field_count<'a>(fields: impl Iterator<Item = &'a Field>) -> proc_macro2::TokenStream {
+    let (unconditional, conditional): (Vec<_>, Vec<_>) =
+        fields.partition(|field| field.cfg_attrs().is_empty());
+    let unconditional = unconditional.len();
+    let enabled = conditional.iter().map(|field| field.cfg_enabled());
+    quote! { (#unconditional #(+ #enabled as usize)*) }
+}
+
+/// Checks that no two accessors (or other generated methods) share a name, so that collisions are
+/// reported at the declaration of the accessor generated last instead of in generated code.
+///
+/// Accessors with different `cfg` attributes are allowed to share a name, as they're assumed to be
+/// mutually exclusive (i.e. the same field laid out differently in two chip variants).
+fn check_accessor_names<'a>(
+    fields: &'a [Field],
+    indexed_fields: impl Iterator<Item = (&'a Field, &'static str)>,
+    calcs: &[Calc],
+) -> Result<()> {
+    let cfg_key = |attrs: &[Attribute]| {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .map(|attr| quote! { #attr }.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut names: Vec<(String, String, String)> = ["field_at_bit", "write_layout_json"]
+        .into_iter()
+        .map(|name| {
+            (
+                name.to_string(),
+                "the bitfield's layout metadata".to_string(),
+                String::new(),
+            )
+        })
+        .collect();
+    let field_accessors = fields.iter().map(|field| (field, "field"));
+    let indexed_accessors = indexed_fields.filter(|(field, _)| field.has_indexed_accessors());
+    let accessors = field_accessors
+        .chain(indexed_accessors)
+        .flat_map(|(field, kind)| {
+            let cfg = cfg_key(&field.attrs);
+            field
+                .accessor_idents()
+                .into_iter()
+                .map(move |accessor| (accessor, format!("{} `{}`", kind, field.ident), cfg.clone()))
+        })
+        .chain(calcs.iter().map(|calc| {
+            let kind = match calc.body {
+                CalcBody::Expr(_) => "computed accessor",
+                CalcBody::Join { .. } => "joined accessor",
+            };
+            (
+                calc.ident.clone(),
+                format!("{} `{}`", kind, calc.ident),
+                cfg_key(&calc.attrs),
+            )
+        }));
+    for (accessor, owner, cfg) in accessors {
+        let name = accessor.to_string();
+        if let Some((_, other_owner, _)) = names.iter().find(|(other, _, other_cfg)| {
+            *other == name && (cfg.is_empty() || other_cfg.is_empty() || cfg == *other_cfg)
+        }) {
+            return Err(Error::new(
+                accessor.span(),
+                format!("Duplicate accessor name `{name}`, already generated for {other_owner}"),
+            ));
+        }
+        names.push((name, owner, cfg));
+    }
+    Ok(())
+}
+
+fn const_with_bits(
+    storage_ty: &Type,
+    storage_ty_bits: &proc_macro2::TokenStream,
+    field_ty: &Type,
+    bits_span: &BitsSpan,
+    value: &Expr,
+) -> proc_macro2::TokenStream {
+    let (start, end) = match bits_span {
+        BitsSpan::Single(bit) => {
+            return quote! {
+                (raw & !((1 as #storage_ty) << #bit)) | ((#value) as #storage_ty) << #bit
+            };
+        }
+        BitsSpan::Range { start, end } => (start.clone(), end.clone()),
+        BitsSpan::Strided { start, step, count } => {
+            return quote! {{
+                let value = (#value) as #field_ty as #storage_ty;
+                let mut raw = raw;
+                let mut i = 0;
+                while i < #count {
+                    let bit = (#start) + i * (#step);
+                    raw = (raw & !((1 as #storage_ty) << bit)) | (value >> i & 1) << bit;
+                    i += 1;
+                }
+                raw
+            }};
+        }
+        BitsSpan::Full => (quote! { 0 }, storage_ty_bits.clone()),
+    };
+    quote! {{
+        let mask = ((1 as #storage_ty) << ((#end) - (#start) - 1) << 1).wrapping_sub(1) << (#start);
+        (raw & !mask) | (((#value) as #field_ty as #storage_ty) << (#start) & mask)
+    }}
+}
+
+/// Returns an expression reading a field's raw value from the integer storage value `raw`, usable
+/// in const contexts.
+fn const_get_bits(
+    storage_ty: &Type,
+    storage_ty_bits: &proc_macro2::TokenStream,
+    field_ty: &Type,
+    bits_span: &BitsSpan,
+) -> proc_macro2::TokenStream {
+    let field_ty_bits = quote! { {::core::mem::size_of::<#field_ty>() << 3} };
+    let (start, end) = match bits_span {
+        BitsSpan::Single(bit) => {
+            return quote! { raw & (1 as #storage_ty) << (#bit) != 0 };
+        }
+        BitsSpan::Range { start, end } => (start.clone(), end.clone()),
+        BitsSpan::Strided { start, step, count } => {
+            return quote! {{
+                let mut value: #field_ty = 0;
+                let mut i = 0;
+                while i < #count {
+                    value |= (((raw >> ((#start) + i * (#step))) & 1) as #field_ty) << i;
+                    i += 1;
+                }
+                value << (#field_ty_bits - (#count)) >> (#field_ty_bits - (#count))
+            }};
+        }
+        BitsSpan::Full => (quote! { 0 }, storage_ty_bits.clone()),
+    };
+    quote! {
+        ((raw >> (#start)) as #field_ty) << (#field_ty_bits - ((#end) - (#start)))
+            >> (#field_ty_bits - ((#end) - (#start)))
+    }
+}
+
+pub fn bitfield(input: TokenStream) -> TokenStream {
+    let input: TokenStream = match flatten_callback(&input.into()) {
+        Ok(input) => input.into(),
+        Err(output) => return output.unwrap_or_else(Error::into_compile_error).into(),
+    };
+
+    let Struct {
+        outer_attrs,
+        vis,
+        ident,
+        storage_vis,
+        private_storage,
+        storage_ty,
+        storage_bits,
+        extra_members,
+        auto_impls,
+        options,
+        has_generics,
+        generics,
+        fields,
+        consts,
+        calcs,
+        reserved,
+        views,
+        repeats,
+    } = syn::parse_macro_input!(input);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Non-generic bitfields backed by integers derive `Clone` and `Copy` automatically, unless
+    // opted out of or if either of them is already derived
+    let derives_clone_or_copy = outer_attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            path.segments
+                .last()
+                .is_some_and(|seg| seg.ident == "Clone" || seg.ident == "Copy")
+        });
+    let auto_derives: Vec<Path> = if options.no_copy.is_none()
+        && options.minimal.is_none()
+        && !has_generics
+        && extra_members.is_empty()
+        && is_int_storage(&storage_ty)
+        && !derives_clone_or_copy
+    {
+        vec![
+            parse_quote! { ::core::clone::Clone },
+            parse_quote! { ::core::marker::Copy },
+        ]
+    } else {
+        Vec::new()
+    };
+    let auto_derive_attr = (!auto_derives.is_empty()).then(|| {
+        quote! { #[derive(#(#auto_derives),*)] }
+    });
+
+    let has_type_params = generics.type_params().next().is_some();
+
+    let type_params_phantom_data = if has_type_params {
+        let type_params = generics.type_params().map(|param| &param.ident);
+        quote! { , ::core::marker::PhantomData::<(#(#type_params),*)> }
+    } else {
+        quote! {}
+    };
+
+    // Constructors from raw storage values initialize the extra members to their declared values,
+    // while functions returning modified copies of a bitfield carry them over
+    let extra_members_init = {
+        let inits = extra_members.iter().map(|member| &member.init);
+        quote! { #(, #inits)* #type_params_phantom_data }
+    };
+    let extra_members_of = |base: proc_macro2::TokenStream| {
+        let indices = (1..=extra_members.len()).map(syn::Index::from);
+        quote! { #(, #base.#indices)* #type_params_phantom_data }
+    };
+    let extra_members_of_self = extra_members_of(quote! { self });
+    let extra_members_of_raw_result = extra_members_of(quote! { raw_result });
+    let extra_members_of_other = extra_members_of(quote! { other });
+
+    let storage_ty_bits = match storage_bits {
+        Some(bits) => quote! { {#bits as usize} },
+        None => quote! { {::core::mem::size_of::<#storage_ty>() << 3} },
+    };
+
+    // The width of generic storage types can't be used in const generic arguments, so bit ranges
+    // can't depend on it
+    let generic_storage =
+        storage_bits.is_none() && mentions_type_params(quote! { #storage_ty }, &generics);
+    // Positions relative to the storage's most significant bit depend on its width
+    let msb_relative_span = fields
+        .iter()
+        .filter(|field| field.bits.mentions_msb())
+        .map(|field| field.ident.span())
+        .chain(
+            reserved
+                .iter()
+                .filter(|reserved| reserved.bits.mentions_msb())
+                .map(|reserved| reserved.span),
+        )
+        .next();
+    if generic_storage {
+        if let Some(field) = fields
+            .iter()
+            .find(|field| matches!(field.bits, Bits::RangeFull | Bits::RangeFrom { .. }))
+        {
+            return Error::new(
+                field.ident.span(),
+                "`..` and open-ended range fields aren't supported for bitfields with generic \
+                 storage types",
+            )
+            .to_compile_error()
+            .into();
+        }
+        if let Some(span) = msb_relative_span {
+            return Error::new(
+                span,
+                "`msb`-relative bit positions aren't supported for bitfields with generic storage \
+                 types",
+            )
+            .to_compile_error()
+            .into();
+        }
+        if options.bit_order == BitOrder::Msb0 {
+            return Error::new(
+                ident.span(),
+                "MSB0 bit numbering isn't supported for bitfields with generic storage types",
+            )
+            .to_compile_error()
+            .into();
+        }
+        // `From<Bitfield<T>> for T` would implement a foreign trait for an uncovered type
+        if auto_impls.into_storage {
+            return Error::new(
+                ident.span(),
+                "IntoStorage isn't supported for bitfields with generic storage types",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    if options.bit_order == BitOrder::Msb0 {
+        if let Some(span) = msb_relative_span {
+            return Error::new(
+                span,
+                "`msb`-relative bit positions can't be used with MSB0 bit numbering",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    // Raw storage values for bitfields with arbitrary widths have their unused high bits cleared
+    // when converted to bitfields
+    let masked_storage = match storage_bits {
+        Some(bits) if bits < 128 => {
+            let mask = proc_macro2::Literal::u128_unsuffixed((1_u128 << bits) - 1);
+            quote! { storage & #mask }
+        }
+        _ => quote! { storage },
+    };
+
+    // Array fields are split into one field per element, so calcs' positions are remapped
+    let mut expanded_fields = Vec::with_capacity(fields.len());
+    let mut expanded_field_indices = Vec::with_capacity(fields.len() + 1);
+    let mut array_fields = Vec::new();
+    let mut bits_spans = Vec::with_capacity(fields.len());
+    let mut last_bits_span = None;
+    // Reserved ranges are placed in declaration order along with fields, so they can be packed
+    let mut reserved_spans = Vec::with_capacity(reserved.len());
+    let mut place_reserved = |field_index: usize,
+                              last_bits_span: &mut Option<BitsSpan>|
+     -> Result<()> {
+        for reserved in reserved
+            .iter()
+            .filter(|reserved| reserved.field_index == field_index)
+        {
+            let bits_span = reserved
+                .bits
+                .clone()
+                .into_span(last_bits_span.as_ref(), Some(&storage_ty_bits))?;
+            *last_bits_span = Some(bits_span.clone());
+            reserved_spans.push(match options.bit_order {
+                BitOrder::Lsb0 => bits_span,
+                BitOrder::Msb0 => bits_span.msb0_to_lsb0(&storage_ty_bits).ok_or_else(|| {
+                    Error::new(
+                        reserved.span,
+                        "strided reserved bits can't be used with MSB0 bit numbering",
+                    )
+                })?,
+            });
+        }
+        Ok(())
+    };
+    let field_count = fields.len();
+    let get_prefix = options.get_prefix.as_ref().map(LitStr::value);
+    let getter_ident = |accessor_name: &Ident| match &get_prefix {
+        Some(prefix) => format_ident!("{}{}", prefix, accessor_name),
+        None => accessor_name.clone(),
+    };
+    for (field_index, mut field) in fields.into_iter().enumerate() {
+        if let Err(err) = place_reserved(field_index, &mut last_bits_span) {
+            return err.to_compile_error().into();
+        }
+        if let (None, Some(casing)) = (&field.name, options.rename_all) {
+            let ident = &field.ident;
+            field.accessor_name =
+                format_ident!("{}", casing.apply(&ident.to_string()), span = ident.span());
+        }
+        field.getter_ident = getter_ident(&field.accessor_name);
+        expanded_field_indices.push(expanded_fields.len());
+        let mut bits_span = match field
+            .bits
+            .clone()
+            .into_span(last_bits_span.as_ref(), Some(&storage_ty_bits))
+        {
+            Ok(bits_span) => bits_span,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let truncate = match &field.content {
+            FieldContent::Single(SingleField { truncate, .. }) => *truncate,
+            FieldContent::Nested(_) => None,
+        };
+        if truncate.is_some() {
+            // Only span the low bits of the storage that fit in the field's type
+            let field_ty = &field.ty;
+            let field_ty_bits = quote! { {::core::mem::size_of::<#field_ty>() << 3} };
+            bits_span = BitsSpan::Range {
+                start: quote! { 0 },
+                end: quote! {{
+                    if #field_ty_bits < #storage_ty_bits {
+                        #field_ty_bits
+                    } else {
+                        #storage_ty_bits
+                    }
+                }},
+            };
+        }
+        last_bits_span = Some(bits_span.clone());
+        // Bit positions counted from the most significant bit are translated once fields have
+        // been placed, so that packing and array elements follow the declared numbering
+        let to_lsb0 = |bits_span: BitsSpan| match options.bit_order {
+            BitOrder::Msb0 if truncate.is_none() => {
+                bits_span.msb0_to_lsb0(&storage_ty_bits).ok_or_else(|| {
+                    Error::new(
+                        field.ident.span(),
+                        "strided fields can't be used with MSB0 bit numbering",
+                    )
+                })
+            }
+            _ => Ok(bits_span),
+        };
+        if let Some(len) = field.array_len {
+            let (start, end) = match &bits_span {
+                BitsSpan::Range { start, end } => (start.clone(), end.clone()),
+                BitsSpan::Full => (quote! { 0 }, storage_ty_bits.clone()),
+                _ => {
+                    return Error::new(field.ident.span(), "array fields must span a range of bits")
+                        .to_compile_error()
+                        .into()
+                }
+            };
+            let width = quote! { (((#end) - (#start)) / #len) };
+            let is_bool = matches!(&field.ty, Type::Path(ty) if ty.path.is_ident("bool"));
+            let assert = maybe_const_assert(!has_generics);
+            array_fields.push((
+                field.clone(),
+                "array field",
+                (expanded_fields.len()..expanded_fields.len() + len).collect::<Vec<_>>(),
+                quote! { #assert(((#end) - (#start)) % #len == 0); },
+            ));
+            for i in 0..len {
+                let mut element = field.clone();
+                element.ident = format_ident!("{}_{}", field.ident, i);
+                element.accessor_name = format_ident!("{}_{}", field.accessor_name, i);
+                element.getter_ident = getter_ident(&element.accessor_name);
+                element.array_len = None;
+                expanded_fields.push(element);
+                // Boolean elements are accessed as single bits
+                let element_bits_span = if is_bool {
+                    BitsSpan::Single(quote! { {(#start) + #i} })
+                } else {
+                    BitsSpan::Range {
+                        start: quote! { {(#start) + #i * #width} },
+                        end: quote! { {(#start) + (#i + 1) * #width} },
+                    }
+                };
+                match to_lsb0(element_bits_span) {
+                    Ok(bits_span) => bits_spans.push(bits_span),
+                    Err(err) => return err.to_compile_error().into(),
+                }
+            }
+        } else {
+            match to_lsb0(bits_span) {
+                Ok(bits_span) => bits_spans.push(bits_span),
+                Err(err) => return err.to_compile_error().into(),
+            }
+            expanded_fields.push(field);
+        }
+    }
+    if let Err(err) = place_reserved(field_count, &mut last_bits_span) {
+        return err.to_compile_error().into();
+    }
+    expanded_field_indices.push(expanded_fields.len());
+    let fields = expanded_fields;
+    // Fields declared in repeat blocks get indexed accessors dispatching to their instances
+    for Repeat {
+        mut field,
+        instances,
+    } in repeats
+    {
+        if let (None, Some(casing)) = (&field.name, options.rename_all) {
+            let ident = &field.ident;
+            field.accessor_name =
+                format_ident!("{}", casing.apply(&ident.to_string()), span = ident.span());
+        }
+        field.getter_ident = getter_ident(&field.accessor_name);
+        let instances = instances
+            .into_iter()
+            .map(|index| expanded_field_indices[index])
+            .collect();
+        array_fields.push((field, "repeated field", instances, quote! {}));
+    }
+    let calcs = calcs
+        .into_iter()
+        .map(|calc| Calc {
+            field_index: expanded_field_indices[calc.field_index],
+            ..calc
+        })
+        .collect::<Vec<_>>();
+
+    if let Err(err) = check_accessor_names(
+        &fields,
+        array_fields.iter().map(|(field, kind, ..)| (field, *kind)),
+        &calcs,
+    ) {
+        return err.to_compile_error().into();
+    }
+
+    // Resolve the bits guarding writes to write-once and lockable fields
+    let write_guard_bits = match fields
+        .iter()
+        .enumerate()
+        .map(|(field_index, field)| {
+            let FieldContent::Single(SingleField {
+                write_guard:
+                    Some(WriteGuard {
+                        kind,
+                        field: flag_ident,
+                    }),
+                ..
+            }) = &field.content
+            else {
+                return Ok(None);
+            };
+            let i = fields
+                .iter()
+                .position(|field| field.ident == *flag_ident)
+                .ok_or_else(|| Error::new(flag_ident.span(), "Unknown field"))?;
+            match &bits_spans[i] {
+                BitsSpan::Single(bit) if i != field_index => Ok(Some((*kind, bit.clone()))),
+                _ => Err(Error::new(
+                    flag_ident.span(),
+                    "write_once and locked_by must refer to a different single-bit field",
+                )),
+            }
+        })
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(write_guard_bits) => write_guard_bits,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let span_bounds = |bits_span: &BitsSpan| match bits_span {
+        BitsSpan::Single(bit) => (bit.clone(), quote! { (#bit) + 1 }, quote! { 1 }),
+        BitsSpan::Range { start, end } => (start.clone(), end.clone(), quote! { 1 }),
+        BitsSpan::Strided { start, step, count } => (
+            start.clone(),
+            quote! { (#start) + (#step) * ((#count) - 1) + 1 },
+            step.clone(),
+        ),
+        BitsSpan::Full => (quote! { 0 }, storage_ty_bits.clone(), quote! { 1 }),
+    };
+    let field_infos = fields.iter().zip(&bits_spans).map(|(field, bits_span)| {
+        let (start, end, stride) = span_bounds(bits_span);
+        let name = field.ident.to_string();
+        let readable = field.is_readable();
+        let writable = field.is_writable();
+        let field_ty = &field.ty;
+        let nested = matches!(field.content, FieldContent::Nested(_));
+        let unit = match field.unit() {
+            Some(unit) => quote! { ::core::option::Option::Some(#unit) },
+            None => quote! { ::core::option::Option::None },
+        };
+        let cfg_attrs = field.cfg_attrs();
+        quote! {
+            #(#cfg_attrs)*
+            ::proc_bitfield::meta::FieldInfo {
+                name: #name,
+                start: #start,
+                end: #end,
+                stride: #stride,
+                readable: #readable,
+                writable: #writable,
+                ty: ::core::stringify!(#field_ty),
+                unit: #unit,
+                nested: #nested,
+            }
+        }
+    });
+
+    let reserved_infos = reserved_spans.iter().map(|bits_span| {
+        let (start, end, stride) = span_bounds(bits_span);
+        quote! {
+            ::proc_bitfield::meta::FieldInfo {
+                name: "reserved",
+                start: #start,
+                end: #end,
+                stride: #stride,
+                readable: false,
+                writable: false,
+                ty: "",
+                unit: ::core::option::Option::None,
+                nested: false,
+            }
+        }
+    });
+
+    // Fields of `arbitrary-int` types must span exactly as many bits as their types hold
+    let uint_width_asserts = fields
+        .iter()
+        .zip(&bits_spans)
+        .filter_map(|(field, bits_span)| {
+            let FieldContent::Single(SingleField {
+                uint_bits: Some((bits, span)),
+                ..
+            }) = &field.content
+            else {
+                return None;
+            };
+            let width = match bits_span {
+                BitsSpan::Single(_) => quote! { 1 },
+                BitsSpan::Range { start, end } => quote! { (#end) - (#start) },
+                BitsSpan::Strided { count, .. } => count.clone(),
+                BitsSpan::Full => storage_ty_bits.clone(),
+            };
+            let bits = *bits as usize;
+            let message = format!(
+                "the bit range of `{}` must be {bits} bits wide to match its type",
+                field.ident,
+            );
+            let cfg_attrs = field.cfg_attrs();
+            Some(quote_spanned! {
+                *span =>
+                #(#cfg_attrs)*
+                ::core::assert!(#width == #bits, #message);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Reserved ranges have no accessors to check their bit ranges, so they're checked along with
+    // gaps when possible
+    let layout_checks = if has_generics
+        || (reserved_spans.is_empty()
+            && options.deny_gaps.is_none()
+            && uint_width_asserts.is_empty())
+    {
+        quote! {}
+    } else {
+        let gap_check = options.deny_gaps.map(|span| {
+            // Minimal bitfields have no metadata constants, so their field metadata is inlined
+            let (field_infos, reserved_infos) = if options.minimal.is_some() {
+                let field_infos = field_infos.clone();
+                let reserved_infos = reserved_infos.clone();
+                (
+                    quote! { &[#(#field_infos),*] },
+                    quote! { &[#(#reserved_infos),*] },
+                )
+            } else {
+                (quote! { #ident::FIELDS }, quote! { #ident::RESERVED })
+            };
+            quote_spanned! {
+                span =>
+                ::core::assert!(
+                    ::proc_bitfield::meta::first_uncovered_bit(
+                        #storage_ty_bits,
+                        #field_infos,
+                        #reserved_infos,
+                    )
+                    .is_none(),
+                    "some bits aren't spanned by any field or reserved range",
+                );
+            }
+        });
+        let reserved_asserts = reserved_spans.iter().map(|bits_span| {
+            let (start, end, _) = span_bounds(bits_span);
+            quote! {
+                ::core::assert!(
+                    #start < #end && #end <= #storage_ty_bits,
+                    "reserved bits must be within the storage",
+                );
+            }
+        });
+        quote! {
+            #[allow(clippy::int_plus_one)]
+            const _: () = {
+                #(#reserved_asserts)*
+                #(#uint_width_asserts)*
+                #gap_check
+            };
+        }
+    };
+
+    // Fields disabled by `cfg` attributes are missing from `FIELDS`, shifting the indices of the
+    // fields declared after them
+    let field_enum_arms = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, field)| {
+            let conv_ty = field.conv_ty()?;
+            let cfg_attrs = field.cfg_attrs();
+            let pattern = if fields[..i].iter().all(|field| field.cfg_attrs().is_empty()) {
+                quote! { #i }
+            } else {
+                let index = enabled_field_count(fields[..i].iter());
+                quote! { _ if i == #index }
+            };
+            Some(quote! {
+                #(#cfg_attrs)*
+                #pattern => (&EnumProbe::<#conv_ty>::new()).__enum_info(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Generated helper items, as opposed to accessors and constants declared in the bitfield
+    let items_vis = options.vis.clone().unwrap_or_else(|| vis.clone());
+    let inline_attr = options.inline_attr();
+    let get_must_use_attr = options.get_must_use_attr();
+    let with_must_use_attr = options.with_must_use_attr();
+    let has_with = options.no_with.is_none();
+    let has_set = options.no_set.is_none();
+
+    let field_fns = fields.iter().zip(&bits_spans).zip(&write_guard_bits).map(
+        |((
+            Field {
+                attrs,
+                vis,
+                ident,
+                bits: _,
+                array_len: _,
+                default: _,
+                name: _,
+                accessor_name,
+                getter_ident,
+                ty: field_ty,
+                content,
+            },
+            bits_span,
+        ), write_guard_bit)| {
+            let field_ty_bits = quote! { {::core::mem::size_of::<#field_ty>() << 3} };
+
+            let mut bits_span_asserts = {
+                let assert_is_const = !has_generics;
+                let assert = maybe_const_assert(assert_is_const);
+                let mut asserts = match &bits_span {
+                    BitsSpan::Single(bit) => {
+                        quote_spanned! {
+                            ident.span() =>
+                            #assert(#bit < #storage_ty_bits);
+                        }
+                    }
+                    BitsSpan::Range { start, end } => {
+                        quote_spanned! {
+                            ident.span() =>
+                            #assert(#end > #start);
+                            #assert(#start < #storage_ty_bits && #end <= #storage_ty_bits);
+                            #assert(#end - #start <= #field_ty_bits);
+                        }
+                    }
+                    BitsSpan::Strided { start, step, count } => {
+                        quote_spanned! {
+                            ident.span() =>
+                            #assert(#step > 0 && #count > 0);
+                            #assert((#start) + (#step) * ((#count) - 1) < #storage_ty_bits);
+                            #assert(#count <= #field_ty_bits);
+                        }
+                    }
+                    BitsSpan::Full => {
+                        quote_spanned! {
+                            ident.span() =>
+                            #assert(#storage_ty_bits <= #field_ty_bits);
+                        }
+                    }
+                };
+                move || {
+                    if assert_is_const {
+                        replace(&mut asserts, quote!{})
+                    } else {
+                        asserts.clone()
+                    }
+                }
+            };
+
+            match content {
+                FieldContent::Single(SingleField {
+                    get_kind,
+                    set_kind,
+                    truncate: _,
+                    merge_kind: _,
+                    unit,
+                    safety,
+                    write_guard: _,
+                    trigger,
+                    latched,
+                    nonzero,
+                    swap,
+                    byte_order: _,
+                    uint_bits: _,
+                    range,
+                    ignore_writes,
+                    export: _,
+                    get_vis,
+                    set_vis,
+                }) => {
+                    let get_vis = get_vis.as_ref().unwrap_or(vis);
+                    let set_vis = set_vis.as_ref().unwrap_or(vis);
+                    let unit_doc = unit.as_ref().map(|unit| {
+                        let doc = format!("Measured in {}.", unit.value());
+                        quote! {
+                            #[doc = ""]
+                            #[doc = #doc]
+                        }
+                    });
+                    let attrs = attrs
+                        .iter()
+                        .map(|attr| quote! { #attr })
+                        .chain(unit_doc)
+                        .collect::<Vec<_>>();
+                    let set_fn_ident = format_ident!("set_{}", accessor_name);
+                    let with_fn_ident = format_ident!("with_{}", accessor_name);
+                    let safety_doc = |is_unsafe: bool| {
+                        let safety = safety.as_ref().filter(|_| is_unsafe)?;
+                        Some(quote! {
+                            #[doc = ""]
+                            #[doc = "# Safety"]
+                            #[doc = ""]
+                            #[doc = #safety]
+                        })
+                    };
+                    let get_safety_doc = safety_doc(get_kind.is_unsafe());
+                    let set_safety_doc = safety_doc(set_kind.is_unsafe());
+
+                    if let Some(hook) = trigger {
+                        let BitsSpan::Single(bit) = bits_span else {
+                            return Error::new(ident.span(), "trigger fields must span a single bit")
+                                .to_compile_error();
+                        };
+                        let trigger_fn_ident = format_ident!("trigger_{}", accessor_name);
+                        let bits_span_asserts = bits_span_asserts();
+                        let call_hook = hook.as_ref().map(|hook| quote! { #hook(self); });
+                        return quote! {
+                            #(#attrs)*
+                            #inline_attr
+                            #set_vis fn #trigger_fn_ident(&mut self) {
+                                #bits_span_asserts
+                                <#storage_ty as ::proc_bitfield::SetBit>::set_bit::<#bit>(
+                                    &mut self.0,
+                                    true,
+                                );
+                                #call_hook
+                            }
+                        };
+                    }
+
+                    // Swapped fields convert raw values between the storage's and the field's byte
+                    // order on both reads and writes, which are the same operation
+                    let swap_raw_value = match swap {
+                        Some(swap) => {
+                            let width = match bits_span {
+                                BitsSpan::Single(_) => {
+                                    return Error::new(
+                                        ident.span(),
+                                        "byte_swap, nibble_swap and be can't be specified \
+                                         for single-bit fields",
+                                    )
+                                    .to_compile_error();
+                                }
+                                BitsSpan::Range { start, end } => quote! { ((#end) - (#start)) },
+                                BitsSpan::Strided { count, .. } => quote! { (#count) },
+                                BitsSpan::Full => storage_ty_bits.clone(),
+                            };
+                            let swapped = match swap {
+                                SwapKind::Bytes => quote! {
+                                    ::proc_bitfield::ops::swap_bytes(
+                                        raw_value as u128,
+                                        #width as u32,
+                                    )
+                                },
+                                SwapKind::Nibbles => quote! {
+                                    ::proc_bitfield::ops::swap_nibbles(
+                                        raw_value as u128,
+                                        #width as u32,
+                                    )
+                                },
+                            };
+                            let assert = maybe_const_assert(!has_generics);
+                            Some(move |raw_value: proc_macro2::TokenStream| quote! {{
+                                #assert(#width % 8 == 0);
+                                let raw_value = #raw_value;
+                                let swapped = #swapped;
+                                if <#field_ty>::MIN != 0 {
+                                    ((swapped << (128 - #width)) as i128 >> (128 - #width))
+                                        as #field_ty
+                                } else {
+                                    swapped as #field_ty
+                                }
+                            }})
+                        }
+                        None => None,
+                    };
+
+                    let getter = if !matches!(&get_kind, AccessorKind::Disabled) {
+                        let (calc_get_result, get_output_ty) = match get_kind {
+                            AccessorKind::Default => (quote! { raw_value }, quote! { #field_ty }),
+                            AccessorKind::Disabled => unreachable!(),
+
+                            AccessorKind::ConvTy(ty) => (
+                                quote! {
+                                    <#ty as ::core::convert::From<#field_ty>>::from(raw_value)
+                                },
+                                quote! { #ty },
+                            ),
+                            AccessorKind::CachedConvTy(ty) => (
+                                quote! {
+                                    cache.get_or_insert_with(
+                                        raw_value,
+                                        <#ty as ::core::convert::From<#field_ty>>::from,
+                                    )
+                                },
+                                quote! { &'cache #ty },
+                            ),
+                            AccessorKind::UnsafeConvTy { ty, has_safe_accessor } => {
+                                let unsafe_ = has_safe_accessor.then(|| quote! { unsafe })
+                                    .into_iter();
+                                (
+                                    quote! {
+                                        #(#unsafe_)* {
+                                            <
+                                                #ty as ::proc_bitfield::UnsafeFrom<#field_ty>
+                                            >::unsafe_from(raw_value)
+                                        }
+                                    },
+                                    quote! { #ty },
+                                )
+                            },
+                            AccessorKind::TryConvTy(ty) => (
+                                quote! {
+                                    <
+                                        #ty as ::core::convert::TryFrom<#field_ty>
+                                    >::try_from(raw_value)
+                                },
+                                quote! {
+                                    ::core::result::Result<
+                                        #ty,
+                                        <#ty as ::core::convert::TryFrom<#field_ty>>::Error,
+                                    >
+                                },
+                            ),
+                            AccessorKind::UnwrapConvTy(ty) => (
+                                quote! {
+                                    <
+                                        #ty as ::core::convert::TryFrom<#field_ty>
+                                    >::try_from(raw_value).unwrap()
+                                },
+                                quote! { #ty },
+                            ),
+
+                            AccessorKind::ConvFn { fn_, ty } => (
+                                quote! { #fn_(raw_value) },
+                                quote! { #ty },
+                            ),
+                            AccessorKind::UnsafeConvFn { fn_, ty, has_safe_accessor } => {
+                                let unsafe_ = has_safe_accessor.then(|| quote! { unsafe })
+                                    .into_iter();
+                                (
+                                    quote! { #(#unsafe_)* { #fn_(raw_value) } },
+                                    quote! { #ty },
+                                )
+                            },
+
+                            AccessorKind::TryGetFn { fn_, result_ty } => (
+                                quote! { #fn_(raw_value) },
+                                quote! { #result_ty },
+                            ),
+                            AccessorKind::TrySetFn { .. } => unreachable!(),
+                            AccessorKind::UnwrapConvFn { fn_, ty } => (
+                                quote! { #fn_(raw_value).unwrap() },
+                                quote! { #ty },
+                            )
+                        };
+
+                        let get_raw_value = match &bits_span {
+                            BitsSpan::Single(bit) => {
+                                quote_spanned! {
+                                    ident.span() =>
+                                    let raw_value = <#storage_ty as ::proc_bitfield::Bit>
+                                        ::bit::<#bit>(&self.0);
+                                }
+                            }
+                            BitsSpan::Range { start, end } => {
+                                quote_spanned! {
+                                    ident.span() =>
+                                    let raw_value = <
+                                        #storage_ty as ::proc_bitfield::Bits<#field_ty>
+                                    >::bits::<#start, #end>(&self.0);
+                                }
+                            }
+                            BitsSpan::Strided { start, step, count } => {
+                                quote_spanned! {
+                                    ident.span() =>
+                                    let raw_value = <
+                                        #storage_ty as ::proc_bitfield::StridedBits<#field_ty>
+                                    >::strided_bits::<#start, #step, #count>(&self.0);
+                                }
+                            }
+                            BitsSpan::Full => {
+                                quote_spanned! {
+                                    ident.span() =>
+                                    let raw_value = <
+                                        #storage_ty as ::proc_bitfield::Bits<#field_ty>
+                                    >::bits::<0, #storage_ty_bits>(&self.0);
+                                }
+                            }
+                        };
+
+                        let get_unsafe = get_kind.is_unsafe()
+                            .then(|| quote! { unsafe })
+                            .into_iter();
+                        let get_params = if let AccessorKind::CachedConvTy(ty) = get_kind {
+                            quote! {
+                                <'cache>(
+                                    &self,
+                                    cache: &'cache mut ::proc_bitfield::cache::Cached<
+                                        #field_ty,
+                                        #ty,
+                                    >,
+                                )
+                            }
+                        } else {
+                            quote! { (&self) }
+                        };
+                        let get_raw_value = match &swap_raw_value {
+                            Some(swap_raw_value) => {
+                                let swapped = swap_raw_value(quote! { raw_value });
+                                quote! {
+                                    #get_raw_value
+                                    let raw_value = #swapped;
+                                }
+                            }
+                            None => get_raw_value,
+                        };
+                        // Fields ignoring writes can read back a fixed value instead of their bits
+                        let get_raw_value = match ignore_writes {
+                            Some(Some(fixed_value)) => quote! {
+                                let raw_value: #field_ty = #fixed_value;
+                            },
+                            _ => get_raw_value,
+                        };
+                        let bits_span_asserts = bits_span_asserts();
+                        if options.const_.is_some()
+                            && matches!(get_kind, AccessorKind::Default)
+                            && !matches!(ignore_writes, Some(Some(_)))
+                        {
+                            let get_value = const_get_bits(
+                                &storage_ty,
+                                &storage_ty_bits,
+                                field_ty,
+                                bits_span,
+                            );
+                            let get_value = match &swap_raw_value {
+                                Some(swap_raw_value) => {
+                                    let swapped = swap_raw_value(quote! { raw_value });
+                                    quote! {{
+                                        let raw_value: #field_ty = #get_value;
+                                        #swapped
+                                    }}
+                                }
+                                None => get_value,
+                            };
+                            quote! {
+                                #(#attrs)*
+                                #inline_attr
+                                #get_must_use_attr
+                                #[allow(clippy::identity_op)]
+                                #get_vis const fn #getter_ident(&self) -> #field_ty {
+                                    #bits_span_asserts
+                                    let raw = self.0;
+                                    #get_value
+                                }
+                            }
+                        } else {
+                            // Getters converting to other types (i.e. enums) are always
+                            // `#[must_use]` unless disabled, so that their values are dispatched
+                            // on with exhaustive `match`es instead of being discarded
+                            let get_must_use_attr = if matches!(get_kind, AccessorKind::Default)
+                                || matches!(options.must_use, MustUsePolicy::None)
+                            {
+                                get_must_use_attr.clone()
+                            } else {
+                                quote! { #[must_use] }
+                            };
+                            quote! {
+                                #(#attrs)*
+                                #get_safety_doc
+                                #inline_attr
+                                #get_must_use_attr
+                                #[allow(clippy::identity_op)]
+                                #get_vis #(#get_unsafe)* fn #getter_ident #get_params -> #get_output_ty {
+                                    #bits_span_asserts
+                                    #get_raw_value
+                                    #calc_get_result
+                                }
+                            }
+                        }
+                    } else {
+                        quote! {}
+                    };
+
+                    let setters = if !matches!(&set_kind, AccessorKind::Disabled) {
+                        let (
+                            calc_set_with_raw_value,
+                            set_with_input_ty,
+                            set_ok,
+                            set_output_ty,
+                            with_ok,
+                            with_output_ty,
+                        ) = match set_kind {
+                            AccessorKind::Default => (
+                                quote! { value },
                                 field_ty,
                                 quote! {},
                                 quote! { () },
@@ -876,7 +3784,9 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
                                     quote! { Self },
                                 )
                             },
-                            AccessorKind::TryGetFn { .. } => unreachable!(),
+                            AccessorKind::CachedConvTy(_) | AccessorKind::TryGetFn { .. } => {
+                                unreachable!()
+                            }
                             AccessorKind::TrySetFn { fn_, input_ty, result_ty } => (
                                 quote! { #fn_(value)? },
                                 input_ty,
@@ -903,19 +3813,188 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
                             ),
                         };
 
+                        // Guarded fields fail to be written if their guard bit is set, and
+                        // write-once fields also set it on successful writes
+                        let (write_guard_check, set_ok, set_output_ty, with_ok, with_output_ty) =
+                            match write_guard_bit {
+                                Some((kind, guard_bit)) => {
+                                    let (error, set_guard, with_guard) = match kind {
+                                        WriteGuardKind::Once => (
+                                            quote! { ::proc_bitfield::access::AlreadyWritten },
+                                            quote! {
+                                                <#storage_ty as ::proc_bitfield::SetBit>
+                                                    ::set_bit::<#guard_bit>(&mut self.0, true);
+                                            },
+                                            quote! {
+                                                let raw_result = Self(
+                                                    <#storage_ty as ::proc_bitfield::WithBit>
+                                                        ::with_bit::<#guard_bit>(raw_result.0, true)
+                                                    #extra_members_of_raw_result
+                                                );
+                                            },
+                                        ),
+                                        WriteGuardKind::LockedBy => (
+                                            quote! { ::proc_bitfield::access::Locked },
+                                            quote! {},
+                                            quote! {},
+                                        ),
+                                    };
+                                    (
+                                        quote! {
+                                            if <#storage_ty as ::proc_bitfield::Bit>
+                                                ::bit::<#guard_bit>(&self.0)
+                                            {
+                                                return ::core::result::Result::Err(#error);
+                                            }
+                                        },
+                                        quote! {
+                                            #set_guard
+                                            ::core::result::Result::Ok(())
+                                        },
+                                        quote! { ::core::result::Result<(), #error> },
+                                        quote! {{
+                                            #with_guard
+                                            ::core::result::Result::Ok(raw_result)
+                                        }},
+                                        quote! { ::core::result::Result<Self, #error> },
+                                    )
+                                }
+                                None => (quote! {}, set_ok, set_output_ty, with_ok, with_output_ty),
+                            };
+
+                        // Fields with legal ranges fail to be written with values outside of them
+                        let (range_check, calc_set_with_raw_value, set_ok, set_output_ty, with_ok, with_output_ty) =
+                            match range {
+                                Some(range) => (
+                                    quote! {
+                                        let value: #field_ty = #calc_set_with_raw_value;
+                                        if !(#range).contains(&value) {
+                                            return ::core::result::Result::Err(
+                                                ::proc_bitfield::access::OutOfRange,
+                                            );
+                                        }
+                                    },
+                                    quote! { value },
+                                    quote! { ::core::result::Result::Ok(()) },
+                                    quote! {
+                                        ::core::result::Result<(), ::proc_bitfield::access::OutOfRange>
+                                    },
+                                    quote! { ::core::result::Result::Ok(raw_result) },
+                                    quote! {
+                                        ::core::result::Result<
+                                            Self,
+                                            ::proc_bitfield::access::OutOfRange,
+                                        >
+                                    },
+                                ),
+                                None => (
+                                    quote! {},
+                                    calc_set_with_raw_value,
+                                    set_ok,
+                                    set_output_ty,
+                                    with_ok,
+                                    with_output_ty,
+                                ),
+                            };
+
+                        // Values written to zero-as-`None` fields must fit in them, as truncating
+                        // them could turn them into zero, which would then be read back as `None`
+                        let (nonzero_check, calc_set_with_raw_value) = if *nonzero {
+                            let width = match bits_span {
+                                BitsSpan::Single(_) => quote! { 1 },
+                                BitsSpan::Range { start, end } => quote! { ((#end) - (#start)) },
+                                BitsSpan::Strided { count, .. } => quote! { (#count) },
+                                BitsSpan::Full => storage_ty_bits.clone(),
+                            };
+                            (
+                                quote! {
+                                    let value: #field_ty = #calc_set_with_raw_value;
+                                    ::core::assert!(
+                                        if <#field_ty>::MIN != 0 {
+                                            (value as i128) << (128 - #width) >> (128 - #width)
+                                                == value as i128
+                                        } else {
+                                            (value as u128) << (128 - #width) >> (128 - #width)
+                                                == value as u128
+                                        },
+                                        "value doesn't fit in the nonzero field's bits",
+                                    );
+                                },
+                                quote! { value },
+                            )
+                        } else {
+                            (quote! {}, calc_set_with_raw_value)
+                        };
+
+                        let calc_set_with_raw_value = match &swap_raw_value {
+                            Some(swap_raw_value) => swap_raw_value(quote! {
+                                {
+                                    let raw_value: #field_ty = #calc_set_with_raw_value;
+                                    raw_value
+                                }
+                            }),
+                            None => calc_set_with_raw_value,
+                        };
+
+                        // Latched bits can only be set by setters, and stay set until acknowledged
+                        let (latch, ack_fn) = if *latched {
+                            let BitsSpan::Single(bit) = bits_span else {
+                                return Error::new(
+                                    ident.span(),
+                                    "latched fields must span a single bit",
+                                )
+                                .to_compile_error();
+                            };
+                            let ack_fn_ident = format_ident!("ack_{}", accessor_name);
+                            let bits_span_asserts = bits_span_asserts();
+                            (
+                                quote! {
+                                    let value = value
+                                        || <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(
+                                            &self.0,
+                                        );
+                                },
+                                quote! {
+                                    #(#attrs)*
+                                    #inline_attr
+                                    #set_vis fn #ack_fn_ident(&mut self) {
+                                        #bits_span_asserts
+                                        <#storage_ty as ::proc_bitfield::SetBit>::set_bit::<#bit>(
+                                            &mut self.0,
+                                            false,
+                                        );
+                                    }
+                                },
+                            )
+                        } else {
+                            (quote! {}, quote! {})
+                        };
+
                         let with_raw_value = match &bits_span {
                             BitsSpan::Single(bit) => quote_spanned! {
                                 ident.span() =>
                                 Self(<#storage_ty as ::proc_bitfield::WithBit>::with_bit::<#bit>(
                                     self.0,
                                     #calc_set_with_raw_value,
-                                ) #type_params_phantom_data)
+                                ) #extra_members_of_self)
                             },
                             BitsSpan::Range { start, end } => quote_spanned! {
                                 ident.span() =>
                                 Self(<#storage_ty as ::proc_bitfield::WithBits<#field_ty>>
                                     ::with_bits::<#start, #end>(self.0, #calc_set_with_raw_value)
-                                    #type_params_phantom_data
+                                    #extra_members_of_self
+                                )
+                            },
+                            BitsSpan::Strided { start, step, count } => quote_spanned! {
+                                ident.span() =>
+                                Self(
+                                    <
+                                        #storage_ty as ::proc_bitfield::WithStridedBits<#field_ty>
+                                    >::with_strided_bits::<#start, #step, #count>(
+                                        self.0,
+                                        #calc_set_with_raw_value,
+                                    )
+                                    #extra_members_of_self
                                 )
                             },
                             BitsSpan::Full => quote_spanned! {
@@ -927,7 +4006,7 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
                                         self.0,
                                         #calc_set_with_raw_value,
                                     )
-                                    #type_params_phantom_data
+                                    #extra_members_of_self
                                 )
                             },
                         };
@@ -949,6 +4028,15 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
                                     #calc_set_with_raw_value,
                                 )
                             },
+                            BitsSpan::Strided { start, step, count } => quote_spanned! {
+                                ident.span() =>
+                                <
+                                    #storage_ty as ::proc_bitfield::SetStridedBits<#field_ty>
+                                >::set_strided_bits::<#start, #step, #count>(
+                                    &mut self.0,
+                                    #calc_set_with_raw_value,
+                                )
+                            },
                             BitsSpan::Full => quote_spanned! {
                                 ident.span() =>
                                 <#storage_ty as ::proc_bitfield::SetBits<#field_ty>>
@@ -959,192 +4047,1783 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
                             },
                         };
 
-                        let set_with_unsafe = set_kind.is_unsafe().then(|| quote! { unsafe });
-                        let set_with_unsafe_1 = set_with_unsafe.iter();
-                        let bits_span_asserts_1 = bits_span_asserts();
-                        let set_with_unsafe_2 = set_with_unsafe_1.clone();
-                        let bits_span_asserts_2 = bits_span_asserts();
+                        // Fields ignoring writes still convert the values written to them (failing
+                        // if the conversion does), but then discard them
+                        let (with_raw_value, set_raw_value) = if ignore_writes.is_some() {
+                            (
+                                quote! {{
+                                    let _ = #calc_set_with_raw_value;
+                                    self
+                                }},
+                                quote! { let _ = #calc_set_with_raw_value },
+                            )
+                        } else {
+                            (with_raw_value, set_raw_value)
+                        };
+
+                        let set_with_unsafe = set_kind.is_unsafe().then(|| quote! { unsafe });
+                        let set_with_unsafe_1 = set_with_unsafe.iter();
+                        let bits_span_asserts_1 = has_with.then(&mut bits_span_asserts);
+                        let set_with_unsafe_2 = set_with_unsafe_1.clone();
+                        let bits_span_asserts_2 = has_set.then(&mut bits_span_asserts);
+                        if options.const_.is_some()
+                            && matches!(set_kind, AccessorKind::Default)
+                            && write_guard_bit.is_none()
+                            && !*latched
+                            && range.is_none()
+                        {
+                            let (with_value, set_value) = if ignore_writes.is_some() {
+                                (
+                                    quote! {{
+                                        let _ = value;
+                                        self
+                                    }},
+                                    quote! { let _ = value; },
+                                )
+                            } else {
+                                let with_value = const_with_bits(
+                                    &storage_ty,
+                                    &storage_ty_bits,
+                                    field_ty,
+                                    bits_span,
+                                    &parse_quote! { #calc_set_with_raw_value },
+                                );
+                                (
+                                    quote! {{
+                                        let raw = self.0;
+                                        Self(#with_value #extra_members_of_self)
+                                    }},
+                                    quote! {
+                                        let raw = self.0;
+                                        self.0 = #with_value;
+                                    },
+                                )
+                            };
+                            let with_fn = has_with.then(|| {
+                                quote! {
+                                    #(#attrs)*
+                                    #inline_attr
+                                    #with_must_use_attr
+                                    #[allow(clippy::identity_op)]
+                                    #set_vis const fn #with_fn_ident(
+                                        self,
+                                        value: #field_ty,
+                                    ) -> Self {
+                                        #bits_span_asserts_1
+                                        #with_value
+                                    }
+                                }
+                            });
+                            let set_fn = has_set.then(|| {
+                                quote! {
+                                    #(#attrs)*
+                                    #inline_attr
+                                    #[allow(clippy::identity_op)]
+                                    #set_vis const fn #set_fn_ident(&mut self, value: #field_ty) {
+                                        #bits_span_asserts_2
+                                        #set_value
+                                    }
+                                }
+                            });
+                            return quote! {
+                                #getter
+                                #with_fn
+                                #set_fn
+                            };
+                        }
+                        let with_fn = has_with.then(|| {
+                            quote! {
+                                #(#attrs)*
+                                #set_safety_doc
+                                #inline_attr
+                                #with_must_use_attr
+                                #[allow(clippy::identity_op)]
+                                #set_vis #(#set_with_unsafe_1)* fn #with_fn_ident(
+                                    self,
+                                    value: #set_with_input_ty,
+                                ) -> #with_output_ty {
+                                    #bits_span_asserts_1
+                                    #write_guard_check
+                                    #range_check
+                                    #nonzero_check
+                                    #latch
+                                    let raw_result = #with_raw_value;
+                                    #with_ok
+                                }
+                            }
+                        });
+                        let set_fn = has_set.then(|| {
+                            quote! {
+                                #(#attrs)*
+                                #set_safety_doc
+                                #inline_attr
+                                #[allow(clippy::identity_op)]
+                                #set_vis #(#set_with_unsafe_2)* fn #set_fn_ident(
+                                    &mut self,
+                                    value: #set_with_input_ty,
+                                ) -> #set_output_ty {
+                                    #bits_span_asserts_2
+                                    #write_guard_check
+                                    #range_check
+                                    #nonzero_check
+                                    #latch
+                                    #set_raw_value;
+                                    #set_ok
+                                }
+                            }
+                        });
+                        quote! {
+                            #with_fn
+                            #set_fn
+                            #ack_fn
+                        }
+                    } else {
+                        quote! {}
+                    };
+
+                    quote! {
+                        #getter
+                        #setters
+                    }
+                },
+
+                FieldContent::Nested(NestedField { is_readable, is_writable }) => {
+                    let mut_fn_ident = format_ident!("{}_mut", accessor_name);
+                    let set_fn_ident = format_ident!("set_{}", accessor_name);
+                    let with_fn_ident = format_ident!("with_{}", accessor_name);
+
+                    let (start, end) = match bits_span {
+                        BitsSpan::Single(_) => panic!("Nested bitfields can't be single-bit"),
+                        BitsSpan::Strided { .. } => unreachable!(),
+                        BitsSpan::Range { start, end } => {
+                            (start.clone(), end)
+                        }
+                        BitsSpan::Full => {
+                            (quote! { 0 }, &storage_ty_bits)
+                        }
+                    };
+
+                    let getter = if *is_readable {
+                        let bits_span_asserts = bits_span_asserts();
                         quote! {
                             #(#attrs)*
-                            #[inline]
-                            #[must_use]
+                            #inline_attr
+                            #get_must_use_attr
                             #[allow(clippy::identity_op)]
-                            #vis #(#set_with_unsafe_1)* fn #with_fn_ident(
-                                self,
-                                value: #set_with_input_ty,
-                            ) -> #with_output_ty {
-                                #bits_span_asserts_1
-                                let raw_result = #with_raw_value;
-                                #with_ok
+                            #vis fn #getter_ident(&self)
+                                -> ::proc_bitfield::nested::NestedRef<Self, #field_ty, #start, #end>
+                            {
+                                #bits_span_asserts
+                                ::proc_bitfield::nested::NestedRef::new(self)
                             }
+                        }
+                    } else {
+                        quote! {}
+                    };
 
+                    let modifier = if *is_readable && *is_writable {
+                        let bits_span_asserts = bits_span_asserts();
+                        quote! {
                             #(#attrs)*
-                            #[inline]
+                            #inline_attr
                             #[allow(clippy::identity_op)]
-                            #vis #(#set_with_unsafe_2)* fn #set_fn_ident(
-                                &mut self,
-                                value: #set_with_input_ty,
-                            ) -> #set_output_ty {
-                                #bits_span_asserts_2
-                                #set_raw_value;
-                                #set_ok
+                            #vis fn #mut_fn_ident(&mut self)
+                                -> ::proc_bitfield::nested::NestedRefMut<
+                                    Self, #field_ty, #start, #end
+                                >
+                            {
+                                #bits_span_asserts
+                                ::proc_bitfield::nested::NestedRefMut::new(self)
                             }
                         }
                     } else {
                         quote! {}
                     };
 
+                    let with_fn = (*is_writable && has_with).then(|| {
+                        let bits_span_asserts = bits_span_asserts();
+                        quote! {
+                            #(#attrs)*
+                            #inline_attr
+                            #with_must_use_attr
+                            #[allow(clippy::identity_op)]
+                            #vis fn #with_fn_ident(self, value: #field_ty) -> Self {
+                                #bits_span_asserts
+                                Self(
+                                    <#storage_ty as ::proc_bitfield::WithBits<
+                                        <#field_ty as ::proc_bitfield::Bitfield>::Storage>
+                                    >::with_bits::<#start, #end>(
+                                        self.0,
+                                        ::proc_bitfield::Bitfield::into_storage(value),
+                                    )
+                                    #extra_members_of_self
+                                )
+                            }
+                        }
+                    });
+                    let set_fn = (*is_writable && has_set).then(|| {
+                        let bits_span_asserts = bits_span_asserts();
+                        quote! {
+                            #(#attrs)*
+                            #inline_attr
+                            #[allow(clippy::identity_op)]
+                            #vis fn #set_fn_ident(&mut self, value: #field_ty) {
+                                #bits_span_asserts
+                                <#storage_ty as ::proc_bitfield::SetBits<
+                                    <#field_ty as ::proc_bitfield::Bitfield>::Storage>
+                                >::set_bits::<#start, #end>(
+                                    &mut self.0,
+                                    ::proc_bitfield::Bitfield::into_storage(value),
+                                );
+                            }
+                        }
+                    });
+
                     quote! {
                         #getter
-                        #setters
+                        #modifier
+                        #with_fn
+                        #set_fn
+                    }
+                },
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    // Place computed accessors right after the fields declared before them
+    let mut accessor_fns = Vec::with_capacity(field_fns.len() + calcs.len());
+    let mut field_fns = field_fns.into_iter();
+    let mut placed_fields = 0;
+    for Calc {
+        attrs,
+        vis,
+        ident,
+        params,
+        ty,
+        body,
+        field_index,
+    } in &calcs
+    {
+        accessor_fns.extend(field_fns.by_ref().take(field_index - placed_fields));
+        placed_fields = *field_index;
+        let param_pats = params.iter().map(|param| &param.pat);
+        let param_tys = params.iter().map(|param| &param.ty);
+        let expr = match body {
+            CalcBody::Expr(expr) => quote! { #expr },
+            CalcBody::Join {
+                raw_ty,
+                conv,
+                parts,
+            } => {
+                let mut widths = Vec::with_capacity(parts.len());
+                let mut part_values = Vec::with_capacity(parts.len());
+                for part in parts {
+                    let i = match fields.iter().position(|field| field.ident == *part) {
+                        Some(i) if matches!(fields[i].content, FieldContent::Single(_)) => i,
+                        _ => {
+                            return Error::new(part.span(), "Unknown or nested field")
+                                .to_compile_error()
+                                .into()
+                        }
+                    };
+                    let (width, value) = match &bits_spans[i] {
+                        BitsSpan::Single(bit) => (
+                            quote! { 1 },
+                            quote! {
+                                <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(&self.0) as u128
+                            },
+                        ),
+                        BitsSpan::Range { start, end } => (
+                            quote! { ((#end) - (#start)) },
+                            quote! {
+                                <#storage_ty as ::proc_bitfield::Bits<u128>>
+                                    ::bits::<#start, #end>(&self.0)
+                            },
+                        ),
+                        BitsSpan::Strided { start, step, count } => (
+                            quote! { (#count) },
+                            quote! {
+                                <#storage_ty as ::proc_bitfield::StridedBits<u128>>
+                                    ::strided_bits::<#start, #step, #count>(&self.0)
+                            },
+                        ),
+                        BitsSpan::Full => (
+                            storage_ty_bits.clone(),
+                            quote! {
+                                <#storage_ty as ::proc_bitfield::Bits<u128>>
+                                    ::bits::<0, #storage_ty_bits>(&self.0)
+                            },
+                        ),
+                    };
+                    widths.push(width);
+                    part_values.push(value);
+                }
+                let assert = maybe_const_assert(!has_generics);
+                let conv = match conv {
+                    JoinConv::None => quote! { raw_value },
+                    JoinConv::ConvTy(ty) => quote! {
+                        <#ty as ::core::convert::From<#raw_ty>>::from(raw_value)
+                    },
+                    JoinConv::TryConvTy(ty) => quote! {
+                        <#ty as ::core::convert::TryFrom<#raw_ty>>::try_from(raw_value)
+                    },
+                    JoinConv::UnwrapConvTy(ty) => quote! {
+                        <#ty as ::core::convert::TryFrom<#raw_ty>>::try_from(raw_value).unwrap()
+                    },
+                };
+                quote! {
+                    #assert(0 #(+ #widths)* <= ::core::mem::size_of::<#raw_ty>() << 3);
+                    let mut raw_value = 0_u128;
+                    #(
+                        raw_value = raw_value.checked_shl(#widths as u32).unwrap_or(0)
+                            | #part_values;
+                    )*
+                    let raw_value = raw_value as #raw_ty;
+                    #conv
+                }
+            }
+        };
+        accessor_fns.push(quote! {
+            #(#attrs)*
+            #inline_attr
+            #get_must_use_attr
+            #vis fn #ident(&self, #(#param_pats: #param_tys),*) -> #ty {
+                #expr
+            }
+        });
+    }
+    accessor_fns.extend(field_fns);
+
+    // Indexed accessors for array and repeated fields whose elements are accessed without
+    // conversions
+    for (field, _, element_indices, width_check) in &array_fields {
+        let Field {
+            attrs,
+            vis,
+            accessor_name,
+            getter_ident,
+            ty,
+            array_len: Some(len),
+            content:
+                FieldContent::Single(SingleField {
+                    get_vis, set_vis, ..
+                }),
+            ..
+        } = field
+        else {
+            unreachable!()
+        };
+        if !field.has_indexed_accessors() {
+            continue;
+        }
+        let ty = field.conv_ty().unwrap_or(ty);
+        let get_vis = get_vis.as_ref().unwrap_or(vis);
+        let set_vis = set_vis.as_ref().unwrap_or(vis);
+        let indices = 0..*len;
+        let elements = element_indices
+            .iter()
+            .map(|&index| &fields[index])
+            .collect::<Vec<_>>();
+        let element_getter_idents = elements.iter().map(|field| &field.getter_ident);
+        let out_of_bounds = quote! {
+            ::core::panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                #len,
+                index,
+            )
+        };
+        if field.is_readable() {
+            let indices = indices.clone();
+            accessor_fns.push(quote! {
+                #(#attrs)*
+                #inline_attr
+                #get_must_use_attr
+                #get_vis fn #getter_ident(&self, index: usize) -> #ty {
+                    #width_check
+                    match index {
+                        #(#indices => self.#element_getter_idents(),)*
+                        _ => #out_of_bounds,
+                    }
+                }
+            });
+        }
+        if field.is_writable() && has_with {
+            let with_fn_ident = format_ident!("with_{}", accessor_name);
+            let element_with_fn_idents = elements
+                .iter()
+                .map(|field| format_ident!("with_{}", field.accessor_name));
+            let indices = indices.clone();
+            accessor_fns.push(quote! {
+                #(#attrs)*
+                #inline_attr
+                #with_must_use_attr
+                #set_vis fn #with_fn_ident(self, index: usize, value: #ty) -> Self {
+                    #width_check
+                    match index {
+                        #(#indices => self.#element_with_fn_idents(value),)*
+                        _ => #out_of_bounds,
+                    }
+                }
+            });
+        }
+        if field.is_writable() && has_set {
+            let set_fn_ident = format_ident!("set_{}", accessor_name);
+            let element_set_fn_idents = elements
+                .iter()
+                .map(|field| format_ident!("set_{}", field.accessor_name));
+            accessor_fns.push(quote! {
+                #(#attrs)*
+                #inline_attr
+                #set_vis fn #set_fn_ident(&mut self, index: usize, value: #ty) {
+                    #width_check
+                    match index {
+                        #(#indices => self.#element_set_fn_idents(value),)*
+                        _ => #out_of_bounds,
+                    }
+                }
+            });
+        }
+    }
+
+    let mut consts = match consts
+        .iter()
+        .map(
+            |Const {
+                 attrs,
+                 vis,
+                 ident: const_ident,
+                 values,
+             }| {
+                let mut assigned_fields = Vec::with_capacity(values.len());
+                let with_values = values
+                    .iter()
+                    .map(|ConstFieldValue { ident, value }| {
+                        let i = fields
+                            .iter()
+                            .position(|field| field.ident == *ident)
+                            .ok_or_else(|| Error::new(ident.span(), "Unknown field"))?;
+                        if assigned_fields.contains(&i) {
+                            return Err(Error::new(ident.span(), "Duplicate field in constant"));
+                        }
+                        assigned_fields.push(i);
+                        let field = &fields[i];
+                        if !matches!(field.content, FieldContent::Single(_)) {
+                            return Err(Error::new(
+                                ident.span(),
+                                "Constants can't contain nested bitfield fields",
+                            ));
+                        }
+                        let with_value = const_with_bits(
+                            &storage_ty,
+                            &storage_ty_bits,
+                            &field.ty,
+                            &bits_spans[i],
+                            value,
+                        );
+                        let cfg_attrs = field.cfg_attrs();
+                        Ok(quote! {
+                            #(#cfg_attrs)*
+                            let raw: #storage_ty = #with_value;
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(quote! {
+                    #(#attrs)*
+                    #vis const #const_ident: Self = Self(
+                        {
+                            let raw: #storage_ty = 0;
+                            #(#with_values)*
+                            raw
+                        }
+                        #extra_members_init
+                    );
+                })
+            },
+        )
+        .collect::<Result<Vec<_>>>()
+    {
+        Ok(consts) => consts,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut impls = Vec::new();
+
+    // Field byte orders are only meaningful for storage laid out as a sequence of bytes
+    let is_byte_array = matches!(
+        &storage_ty,
+        Type::Array(array) if matches!(
+            &*array.elem,
+            Type::Path(elem) if elem.path.is_ident("u8") || elem.path.is_ident("i8")
+        )
+    );
+    for field in &fields {
+        if let FieldContent::Single(SingleField {
+            byte_order: Some(span),
+            ..
+        }) = &field.content
+        {
+            if !is_byte_array {
+                return Error::new(
+                    *span,
+                    "le and be can only be specified for fields of bitfields stored as byte \
+                     arrays, byte_swap can be used instead",
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
+    // C ABI functions accessing exported fields' raw values, for use from C and assembly code
+    let export_prefix = options.export_prefix.as_ref().map_or_else(
+        || to_snake_case(&ident.to_string()),
+        |prefix| prefix.to_string(),
+    );
+    let mut export_fns = Vec::new();
+    for (field, bits_span) in fields.iter().zip(&bits_spans) {
+        let FieldContent::Single(SingleField {
+            export: Some(span), ..
+        }) = &field.content
+        else {
+            continue;
+        };
+        if has_generics {
+            return Error::new(*span, "export can't be used in generic bitfields")
+                .to_compile_error()
+                .into();
+        }
+        let Field {
+            vis,
+            ident: field_ident,
+            ty: field_ty,
+            ..
+        } = field;
+        let cfg_attrs = field.cfg_attrs();
+        if field.is_readable() {
+            let get_fn_ident = format_ident!("{}_get_{}", export_prefix, field_ident);
+            let get_value = const_get_bits(&storage_ty, &storage_ty_bits, field_ty, bits_span);
+            let doc = format!(
+                "Returns the raw value of the `{field_ident}` field of a raw `{ident}` value."
+            );
+            export_fns.push(quote! {
+                #(#cfg_attrs)*
+                #[doc = #doc]
+                #[unsafe(no_mangle)]
+                #[allow(clippy::identity_op)]
+                #vis extern "C" fn #get_fn_ident(raw: #storage_ty) -> #field_ty {
+                    #get_value
+                }
+            });
+        }
+        if field.is_writable() {
+            let set_fn_ident = format_ident!("{}_set_{}", export_prefix, field_ident);
+            let with_value = const_with_bits(
+                &storage_ty,
+                &storage_ty_bits,
+                field_ty,
+                bits_span,
+                &parse_quote! { value },
+            );
+            let doc = format!(
+                "Returns a raw `{ident}` value with the `{field_ident}` field's raw value set to \
+                 `value`."
+            );
+            export_fns.push(quote! {
+                #(#cfg_attrs)*
+                #[doc = #doc]
+                #[unsafe(no_mangle)]
+                #[allow(clippy::identity_op)]
+                #vis extern "C" fn #set_fn_ident(raw: #storage_ty, value: #field_ty) -> #storage_ty {
+                    #with_value
+                }
+            });
+        }
+    }
+    if !export_fns.is_empty() {
+        impls.push(quote! {
+            impl #ident {
+                #(#export_fns)*
+            }
+        });
+    }
+
+    // Fields' default values are assembled into a `DEFAULT` constant, which `Default` returns
+    let default_with_values = fields
+        .iter()
+        .zip(&bits_spans)
+        .filter_map(|(field, bits_span)| {
+            let value = field.default.as_ref()?;
+            let with_value =
+                const_with_bits(&storage_ty, &storage_ty_bits, &field.ty, bits_span, value);
+            let cfg_attrs = field.cfg_attrs();
+            Some(quote! {
+                #(#cfg_attrs)*
+                let raw: #storage_ty = #with_value;
+            })
+        })
+        .collect::<Vec<_>>();
+    if !default_with_values.is_empty() {
+        consts.push(quote! {
+            /// The default value of this bitfield, with all fields declared with default values
+            /// set to them and all other bits cleared.
+            #items_vis const DEFAULT: Self = Self(
+                {
+                    let raw: #storage_ty = 0;
+                    #(#default_with_values)*
+                    raw
+                }
+                #extra_members_init
+            );
+        });
+        if options.minimal.is_none() {
+            impls.push(quote! {
+                impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+                    #[inline]
+                    fn default() -> Self {
+                        Self::DEFAULT
+                    }
+                }
+            });
+        }
+    }
+
+    // Packed byte array conversions, for bitfields with arbitrary widths spanning whole bytes
+    if let Some(bits) = storage_bits.filter(|bits| bits % 8 == 0) {
+        let len = (bits / 8) as usize;
+        let from_bytes_fns = (!private_storage).then(|| {
+            quote! {
+                /// Creates a bitfield from its raw storage value, packed into a little-endian byte
+                /// array.
+                #[inline]
+                #[allow(clippy::identity_op)]
+                #items_vis const fn from_le_bytes(bytes: [u8; #len]) -> Self {
+                    let mut storage: #storage_ty = 0;
+                    let mut i = 0;
+                    while i < #len {
+                        storage |= (bytes[i] as #storage_ty) << (i << 3);
+                        i += 1;
+                    }
+                    Self(storage #extra_members_init)
+                }
+
+                /// Creates a bitfield from its raw storage value, packed into a big-endian byte
+                /// array.
+                #[inline]
+                #items_vis const fn from_be_bytes(mut bytes: [u8; #len]) -> Self {
+                    let mut i = 0;
+                    while i < #len / 2 {
+                        let byte = bytes[i];
+                        bytes[i] = bytes[#len - 1 - i];
+                        bytes[#len - 1 - i] = byte;
+                        i += 1;
+                    }
+                    Self::from_le_bytes(bytes)
+                }
+            }
+        });
+        impls.push(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Returns the bitfield's raw storage value, packed into a little-endian byte
+                /// array.
+                #[inline]
+                #[allow(clippy::identity_op)]
+                #items_vis const fn to_le_bytes(&self) -> [u8; #len] {
+                    let mut bytes = [0; #len];
+                    let mut i = 0;
+                    while i < #len {
+                        bytes[i] = (self.0 >> (i << 3)) as u8;
+                        i += 1;
+                    }
+                    bytes
+                }
+
+                /// Returns the bitfield's raw storage value, packed into a big-endian byte array.
+                #[inline]
+                #items_vis const fn to_be_bytes(&self) -> [u8; #len] {
+                    let mut bytes = self.to_le_bytes();
+                    let mut i = 0;
+                    while i < #len / 2 {
+                        let byte = bytes[i];
+                        bytes[i] = bytes[#len - 1 - i];
+                        bytes[#len - 1 - i] = byte;
+                        i += 1;
+                    }
+                    bytes
+                }
+
+                #from_bytes_fns
+            }
+        });
+    }
+
+    let fallible_fields = fields.iter().filter(|field| field.has_fallible_getter());
+    let ranged_fields = fields
+        .iter()
+        .filter_map(|field| match &field.content {
+            FieldContent::Single(SingleField {
+                get_kind: AccessorKind::Default,
+                range: Some(range),
+                ..
+            }) => Some((field, range)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // Sets `valid` to whether all fields with fallible getters or legal ranges in `value` contain
+    // valid values
+    let is_valid = {
+        let fallible_checks = fallible_fields.clone().map(|field| {
+            let getter_ident = &field.getter_ident;
+            let cfg_attrs = field.cfg_attrs();
+            quote! {
+                #(#cfg_attrs)*
+                {
+                    valid = valid
+                        && ::proc_bitfield::__private::IsOk::is_ok(&value.#getter_ident());
+                }
+            }
+        });
+        let range_checks = ranged_fields.iter().map(|(field, range)| {
+            let getter_ident = &field.getter_ident;
+            let cfg_attrs = field.cfg_attrs();
+            quote! {
+                #(#cfg_attrs)*
+                {
+                    valid = valid && (#range).contains(&value.#getter_ident());
+                }
+            }
+        });
+        quote! {
+            #[allow(unused_mut)]
+            let mut valid = true;
+            #(#fallible_checks)*
+            #(#range_checks)*
+        }
+    };
+
+    if !ranged_fields.is_empty() {
+        impls.push(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Returns whether all of this bitfield's fields with legal ranges contain values
+                /// within them, and all of its fields with fallible getters contain valid values.
+                #[inline]
+                #get_must_use_attr
+                #items_vis fn is_valid(&self) -> bool {
+                    let value = self;
+                    #is_valid
+                    valid
+                }
+            }
+        });
+    }
+
+    if private_storage {
+        impls.push(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Creates a bitfield from its raw storage value, returning it back if any of the
+                /// fields with fallible getters or legal ranges contains an invalid value.
+                #vis fn try_from_storage(
+                    storage: #storage_ty,
+                ) -> ::core::result::Result<Self, #storage_ty> {
+                    let value = Self(#masked_storage #extra_members_init);
+                    #is_valid
+                    if valid {
+                        ::core::result::Result::Ok(value)
+                    } else {
+                        ::core::result::Result::Err(value.0)
+                    }
+                }
+
+                /// Creates a bitfield from its raw storage value, without checking the validity of
+                /// its fields.
+                ///
+                /// # Safety
+                ///
+                /// All fields with fallible getters must contain valid values, as code relying on
+                /// this bitfield's invariants might otherwise behave incorrectly.
+                #[inline]
+                #vis const unsafe fn from_storage_unchecked(storage: #storage_ty) -> Self {
+                    Self(#masked_storage #extra_members_init)
+                }
+
+                /// Returns a reference to this bitfield's raw storage value.
+                #[inline]
+                #vis const fn storage(&self) -> &#storage_ty {
+                    &self.0
+                }
+            }
+        });
+    } else if options.minimal.is_none() {
+        impls.push(quote! {
+            impl #impl_generics ::proc_bitfield::Bitfield for #ident #ty_generics #where_clause {
+                type Storage = #storage_ty;
+
+                #[inline]
+                fn from_storage(storage: Self::Storage) -> Self {
+                    Self(#masked_storage #extra_members_init)
+                }
+
+                #[inline]
+                fn into_storage(self) -> Self::Storage {
+                    self.0
+                }
+
+                #[inline]
+                fn storage(&self) -> &Self::Storage {
+                    &self.0
+                }
+
+                #[inline]
+                fn storage_mut(&mut self) -> &mut Self::Storage {
+                    &mut self.0
+                }
+            }
+        });
+    }
+
+    if auto_impls.valid {
+        let valid_ident = options
+            .valid_name
+            .clone()
+            .unwrap_or_else(|| format_ident!("Valid{}", ident));
+        let valid_doc = format!(
+            "A [`{ident}`] whose fields with fallible getters are all known to contain valid \
+             values.\n\nAll of the wrapped bitfield's methods are available through `Deref`, \
+             with its fallible getters being replaced by infallible ones.",
+        );
+        // Forward the bitfield's derives to the wrapper, except for `Default`, as the default
+        // value isn't necessarily valid
+        let derives = match outer_attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("derive"))
+            .map(|attr| attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated))
+            .collect::<Result<Vec<_>>>()
+        {
+            Ok(derives) => derives
+                .into_iter()
+                .flatten()
+                .filter(|path| {
+                    path.segments
+                        .last()
+                        .is_none_or(|seg| seg.ident != "Default")
+                })
+                .chain(auto_derives.iter().cloned())
+                .collect::<Vec<_>>(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let debug_impl = auto_impls.debug.then(|| {
+            quote! {
+                impl #impl_generics ::core::fmt::Debug for #valid_ident #ty_generics #where_clause {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::core::fmt::Debug::fmt(&self.0, f)
+                    }
+                }
+            }
+        });
+        let getters = fallible_fields.map(|field| {
+            let Field {
+                attrs,
+                vis,
+                getter_ident,
+                ..
+            } = field;
+            let vis = match &field.content {
+                FieldContent::Single(SingleField {
+                    get_vis: Some(get_vis),
+                    ..
+                }) => get_vis,
+                _ => vis,
+            };
+            let output_ty = match &field.content {
+                FieldContent::Single(SingleField {
+                    get_kind: AccessorKind::TryConvTy(ty),
+                    ..
+                }) => quote! { #ty },
+                FieldContent::Single(SingleField {
+                    get_kind: AccessorKind::TryGetFn { result_ty, .. },
+                    ..
+                }) => quote! { <#result_ty as ::proc_bitfield::__private::IsOk>::Ok },
+                _ => unreachable!(),
+            };
+            quote! {
+                #(#attrs)*
+                #[inline]
+                #vis fn #getter_ident(&self) -> #output_ty {
+                    ::proc_bitfield::__private::IsOk::unwrap_valid(self.0.#getter_ident())
+                }
+            }
+        });
+        impls.push(quote! {
+            #[doc = #valid_doc]
+            #[derive(#(#derives),*)]
+            #[repr(transparent)]
+            #vis struct #valid_ident #generics(#ident #ty_generics) #where_clause;
+
+            impl #impl_generics #valid_ident #ty_generics #where_clause {
+                /// Checks that all of the given bitfield's fields with fallible getters or legal
+                /// ranges contain valid values, returning it back otherwise.
+                #vis fn new(
+                    value: #ident #ty_generics,
+                ) -> ::core::result::Result<Self, #ident #ty_generics> {
+                    #is_valid
+                    if valid {
+                        ::core::result::Result::Ok(Self(value))
+                    } else {
+                        ::core::result::Result::Err(value)
+                    }
+                }
+
+                /// Wraps the given bitfield without checking the validity of its fields.
+                ///
+                /// # Safety
+                ///
+                /// All fields with fallible getters must contain valid values, as code relying on
+                /// this wrapper's invariants might otherwise behave incorrectly.
+                #[inline]
+                #vis const unsafe fn new_unchecked(value: #ident #ty_generics) -> Self {
+                    Self(value)
+                }
+
+                /// Creates a checked bitfield from its raw storage value, returning it back if any
+                /// of the fields with fallible getters or legal ranges contains an invalid value.
+                #vis fn try_from_storage(
+                    storage: #storage_ty,
+                ) -> ::core::result::Result<Self, #storage_ty> {
+                    let value = #ident(#masked_storage #extra_members_init);
+                    #is_valid
+                    if valid {
+                        ::core::result::Result::Ok(Self(value))
+                    } else {
+                        ::core::result::Result::Err(value.0)
+                    }
+                }
+
+                /// Creates a checked bitfield from its raw storage value without checking the
+                /// validity of its fields, for raw values already known to be valid (i.e.
+                /// previously checked ones).
+                ///
+                /// # Safety
+                ///
+                /// All fields with fallible getters must contain valid values, as code relying on
+                /// this wrapper's invariants might otherwise behave incorrectly.
+                #[inline]
+                #vis const unsafe fn from_storage_unchecked(storage: #storage_ty) -> Self {
+                    Self(#ident(#masked_storage #extra_members_init))
+                }
+
+                /// Returns the wrapped bitfield.
+                #[inline]
+                #vis fn into_inner(self) -> #ident #ty_generics {
+                    self.0
+                }
+
+                #(#getters)*
+            }
+
+            impl #impl_generics ::core::ops::Deref for #valid_ident #ty_generics #where_clause {
+                type Target = #ident #ty_generics;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl #impl_generics ::core::convert::TryFrom<#ident #ty_generics>
+                for #valid_ident #ty_generics #where_clause
+            {
+                type Error = #ident #ty_generics;
+
+                #[inline]
+                fn try_from(value: #ident #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    Self::new(value)
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<#valid_ident #ty_generics>
+                for #ident #ty_generics #where_clause
+            {
+                #[inline]
+                fn from(other: #valid_ident #ty_generics) -> Self {
+                    other.0
+                }
+            }
+
+            #debug_impl
+        });
+    }
+
+    // Bitfields hold decoded values, raw values being decoded and encoded only when converted
+    let (decode_storage, encode_storage) = match &options.transform {
+        Some(transform) => {
+            impls.push(quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// Creates a bitfield from a raw value encoded with its transform, decoding it.
+                    #[inline]
+                    #vis fn from_encoded(raw: #storage_ty) -> Self {
+                        let storage = <
+                            #transform as ::proc_bitfield::transform::Transform<#storage_ty>
+                        >::decode(raw);
+                        Self(#masked_storage #extra_members_init)
                     }
-                },
 
-                FieldContent::Nested(NestedField { is_readable, is_writable }) => {
-                    let mut_fn_ident = format_ident!("{}_mut", ident);
-                    let set_fn_ident = format_ident!("set_{}", ident);
-                    let with_fn_ident = format_ident!("with_{}", ident);
+                    /// Returns the bitfield's raw value encoded with its transform.
+                    #[inline]
+                    #vis fn into_encoded(self) -> #storage_ty {
+                        <
+                            #transform as ::proc_bitfield::transform::Transform<#storage_ty>
+                        >::encode(self.0)
+                    }
+                }
+            });
+            (
+                quote! {
+                    let storage = <
+                        #transform as ::proc_bitfield::transform::Transform<#storage_ty>
+                    >::decode(storage);
+                },
+                quote! { value.into_encoded() },
+            )
+        }
+        None => (quote! {}, quote! { value.0 }),
+    };
 
-                    let (start, end) = match bits_span {
-                        BitsSpan::Single(_) => panic!("Nested bitfields can't be single-bit"),
-                        BitsSpan::Range { start, ref end } => {
-                            (start, end)
-                        }
-                        BitsSpan::Full => {
-                            (quote! { 0 }, &storage_ty_bits)
+    if auto_impls.remote {
+        let remote_ident = options
+            .remote_name
+            .clone()
+            .unwrap_or_else(|| format_ident!("{}Remote", ident));
+        let remote_doc = format!(
+            "A handle to a [`{ident}`] stored in a word of a [`StorageBackend`] (i.e. an external \
+             EEPROM or flash), whose accessors read and write the word through the backend.\
+             \n\nAll accessors return `Result`s, propagating the backend's errors; setters read \
+             the word, modify it and write it back.\n\n[`StorageBackend`]: \
+             ::proc_bitfield::remote::StorageBackend",
+        );
+        let accessors = fields
+            .iter()
+            .filter_map(|field| match &field.content {
+                FieldContent::Single(content) => Some((field, content)),
+                FieldContent::Nested(_) => None,
+            })
+            .map(|(field, content)| {
+                let Field {
+                    attrs,
+                    vis,
+                    ty: field_ty,
+                    accessor_name,
+                    getter_ident,
+                    ..
+                } = field;
+                let get_vis = content.get_vis.as_ref().unwrap_or(vis);
+                let set_vis = content.set_vis.as_ref().unwrap_or(vis);
+                if content.trigger.is_some() {
+                    let trigger_fn_ident = format_ident!("trigger_{}", accessor_name);
+                    return quote! {
+                        #(#attrs)*
+                        #[inline]
+                        #set_vis fn #trigger_fn_ident(
+                            &mut self,
+                        ) -> ::core::result::Result<(), Backend::Error> {
+                            self.modify(|value| value.#trigger_fn_ident())
                         }
                     };
+                }
 
-                    let getter = if *is_readable {
-                        let bits_span_asserts = bits_span_asserts();
-                        quote! {
-                            #(#attrs)*
-                            #[inline]
-                            #[allow(clippy::identity_op)]
-                            #vis fn #ident(&self)
-                                -> ::proc_bitfield::nested::NestedRef<Self, #field_ty, #start, #end>
-                            {
-                                #bits_span_asserts
-                                ::proc_bitfield::nested::NestedRef::new(self)
-                            }
+                // Unsafe and cached getters can't be forwarded without their extra parameters and
+                // safety requirements, so they're only available through `read`
+                let get_output_ty = match &content.get_kind {
+                    AccessorKind::Default => Some(quote! { #field_ty }),
+                    AccessorKind::ConvTy(ty)
+                    | AccessorKind::ConvFn { ty, .. }
+                    | AccessorKind::UnwrapConvTy(ty)
+                    | AccessorKind::UnwrapConvFn { ty, .. } => Some(quote! { #ty }),
+                    AccessorKind::UnsafeConvTy {
+                        ty,
+                        has_safe_accessor: true,
+                    }
+                    | AccessorKind::UnsafeConvFn {
+                        ty,
+                        has_safe_accessor: true,
+                        ..
+                    } => Some(quote! { #ty }),
+                    AccessorKind::TryConvTy(ty) => Some(quote! {
+                        ::core::result::Result<
+                            #ty,
+                            <#ty as ::core::convert::TryFrom<#field_ty>>::Error,
+                        >
+                    }),
+                    AccessorKind::TryGetFn { result_ty, .. } => Some(quote! { #result_ty }),
+                    _ => None,
+                };
+                let getter = get_output_ty.map(|output_ty| {
+                    quote! {
+                        #(#attrs)*
+                        #[inline]
+                        #get_vis fn #getter_ident(
+                            &mut self,
+                        ) -> ::core::result::Result<#output_ty, Backend::Error> {
+                            ::core::result::Result::Ok(self.read()?.#getter_ident())
                         }
-                    } else {
-                        quote! {}
-                    };
+                    }
+                });
 
-                    let modifier = if *is_readable && *is_writable {
-                        let bits_span_asserts = bits_span_asserts();
-                        quote! {
-                            #(#attrs)*
-                            #[inline]
-                            #[allow(clippy::identity_op)]
-                            #vis fn #mut_fn_ident(&mut self)
-                                -> ::proc_bitfield::nested::NestedRefMut<
-                                    Self, #field_ty, #start, #end
-                                >
-                            {
-                                #bits_span_asserts
-                                ::proc_bitfield::nested::NestedRefMut::new(self)
-                            }
+                let set_input_ty = match &content.set_kind {
+                    AccessorKind::Default => Some(quote! { #field_ty }),
+                    AccessorKind::ConvTy(ty)
+                    | AccessorKind::ConvFn { ty, .. }
+                    | AccessorKind::TryConvTy(ty)
+                    | AccessorKind::UnwrapConvTy(ty)
+                    | AccessorKind::UnwrapConvFn { ty, .. } => Some(quote! { #ty }),
+                    AccessorKind::UnsafeConvTy {
+                        ty,
+                        has_safe_accessor: true,
+                    }
+                    | AccessorKind::UnsafeConvFn {
+                        ty,
+                        has_safe_accessor: true,
+                        ..
+                    } => Some(quote! { #ty }),
+                    AccessorKind::TrySetFn { input_ty, .. } => Some(quote! { #input_ty }),
+                    _ => None,
+                };
+                let set_output_ty = if content.range.is_some() {
+                    quote! { ::core::result::Result<(), ::proc_bitfield::access::OutOfRange> }
+                } else if let Some(write_guard) = &content.write_guard {
+                    match write_guard.kind {
+                        WriteGuardKind::Once => quote! {
+                            ::core::result::Result<(), ::proc_bitfield::access::AlreadyWritten>
+                        },
+                        WriteGuardKind::LockedBy => quote! {
+                            ::core::result::Result<(), ::proc_bitfield::access::Locked>
+                        },
+                    }
+                } else {
+                    match &content.set_kind {
+                        AccessorKind::TryConvTy(ty) => quote! {
+                            ::core::result::Result<
+                                (),
+                                <#ty as ::core::convert::TryInto<#field_ty>>::Error
+                            >
+                        },
+                        AccessorKind::TrySetFn { result_ty, .. } => quote! {
+                            <#result_ty as ::proc_bitfield::Try>::WithOutput<()>
+                        },
+                        _ => quote! { () },
+                    }
+                };
+                let set_fn_ident = format_ident!("set_{}", accessor_name);
+                let setter = set_input_ty.filter(|_| has_set).map(|input_ty| {
+                    quote! {
+                        #(#attrs)*
+                        #[inline]
+                        #set_vis fn #set_fn_ident(
+                            &mut self,
+                            value: #input_ty,
+                        ) -> ::core::result::Result<#set_output_ty, Backend::Error> {
+                            self.modify(|bitfield| bitfield.#set_fn_ident(value))
                         }
-                    } else {
-                        quote! {}
-                    };
+                    }
+                });
 
-                    let setters = if *is_writable {
-                        let bits_span_asserts_1 = bits_span_asserts();
-                        let bits_span_asserts_2 = bits_span_asserts();
-                        quote! {
-                            #(#attrs)*
-                            #[inline]
-                            #[must_use]
-                            #[allow(clippy::identity_op)]
-                            #vis fn #with_fn_ident(self, value: #field_ty) -> Self {
-                                #bits_span_asserts_1
-                                Self(
-                                    <#storage_ty as ::proc_bitfield::WithBits<
-                                        <#field_ty as ::proc_bitfield::Bitfield>::Storage>
-                                    >::with_bits::<#start, #end>(
-                                        self.0,
-                                        ::proc_bitfield::Bitfield::into_storage(value),
-                                    )
-                                    #type_params_phantom_data
-                                )
-                            }
+                quote! {
+                    #getter
+                    #setter
+                }
+            });
+        impls.push(quote! {
+            #[doc = #remote_doc]
+            #vis struct #remote_ident<Backend> {
+                backend: Backend,
+                address: usize,
+            }
 
-                            #(#attrs)*
-                            #[inline]
-                            #[allow(clippy::identity_op)]
-                            #vis fn #set_fn_ident(&mut self, value: #field_ty) {
-                                #bits_span_asserts_2
-                                <#storage_ty as ::proc_bitfield::SetBits<
-                                    <#field_ty as ::proc_bitfield::Bitfield>::Storage>
-                                >::set_bits::<#start, #end>(
-                                    &mut self.0,
-                                    ::proc_bitfield::Bitfield::into_storage(value),
-                                );
-                            }
-                        }
-                    } else {
-                        quote! {}
-                    };
+            impl<Backend> #remote_ident<Backend> {
+                /// Creates a handle to the bitfield stored at the given address of the backend.
+                #[inline]
+                #vis const fn new(backend: Backend, address: usize) -> Self {
+                    Self { backend, address }
+                }
 
-                    quote! {
-                        #getter
-                        #modifier
-                        #setters
+                /// Returns the address of the word containing the bitfield.
+                #[inline]
+                #vis const fn address(&self) -> usize {
+                    self.address
+                }
+
+                /// Returns a reference to the backend.
+                #[inline]
+                #vis const fn backend(&self) -> &Backend {
+                    &self.backend
+                }
+
+                /// Returns a mutable reference to the backend.
+                #[inline]
+                #vis fn backend_mut(&mut self) -> &mut Backend {
+                    &mut self.backend
+                }
+
+                /// Returns the backend, consuming the handle.
+                #[inline]
+                #vis fn into_backend(self) -> Backend {
+                    self.backend
+                }
+            }
+
+            impl<Backend: ::proc_bitfield::remote::StorageBackend<#storage_ty>>
+                #remote_ident<Backend>
+            {
+                /// Reads the bitfield from the backend.
+                #[inline]
+                #vis fn read(&mut self) -> ::core::result::Result<#ident, Backend::Error> {
+                    let storage = self.backend.read_word(self.address)?;
+                    #decode_storage
+                    ::core::result::Result::Ok(#ident(#masked_storage #extra_members_init))
+                }
+
+                /// Writes the bitfield to the backend.
+                #[inline]
+                #vis fn write(&mut self, value: #ident) -> ::core::result::Result<(), Backend::Error> {
+                    self.backend.write_word(self.address, #encode_storage)
+                }
+
+                /// Reads the bitfield from the backend, modifies it through `f` and writes it
+                /// back, returning the result of `f`.
+                ///
+                /// Nothing is written if reading fails.
+                #vis fn modify<R>(
+                    &mut self,
+                    f: impl ::core::ops::FnOnce(&mut #ident) -> R,
+                ) -> ::core::result::Result<R, Backend::Error> {
+                    let mut value = self.read()?;
+                    let result = f(&mut value);
+                    self.write(value)?;
+                    ::core::result::Result::Ok(result)
+                }
+
+                #(#accessors)*
+            }
+        });
+    }
+
+    let debug_name = options
+        .debug_name
+        .as_ref()
+        .or(options.name.as_ref())
+        .map_or_else(
+            || quote! { ::core::stringify!(#ident) },
+            |debug_name| quote! { #debug_name },
+        );
+    let debug_raw = options
+        .debug_raw
+        .as_ref()
+        .is_none_or(|debug_raw| debug_raw.value);
+
+    if auto_impls.debug {
+        let debug_raw_entry = debug_raw.then(|| quote! { s.field("0", &self.0); });
+        let field_entries = fields
+            .iter()
+            .filter(|field| field.is_readable())
+            .map(|field| {
+                let field_ident = &field.ident;
+                let value = field.fmt_value();
+                let cfg_attrs = field.cfg_attrs();
+                quote! {
+                    #(#cfg_attrs)*
+                    s.field(::core::stringify!(#field_ident), #value);
+                }
+            });
+        impls.push(quote! {
+            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    let mut s = f.debug_struct(#debug_name);
+                    #debug_raw_entry
+                    #(#field_entries)*
+                    s.finish()
+                }
+            }
+        });
+    }
+
+    if auto_impls.display {
+        // Fields can be disabled by `cfg` attributes, so separators are tracked at runtime
+        let field_writes = fields
+            .iter()
+            .filter(|field| field.is_readable())
+            .map(|field| {
+                let name = format!("{}: ", field.ident);
+                let value = field.fmt_value();
+                let unit = field.unit().map(|unit| {
+                    let unit = format!(" {}", unit.value());
+                    quote! { f.write_str(#unit)?; }
+                });
+                let cfg_attrs = field.cfg_attrs();
+                quote! {
+                    #(#cfg_attrs)*
+                    {
+                        f.write_str(separator)?;
+                        f.write_str(#name)?;
+                        ::core::fmt::Debug::fmt(#value, f)?;
+                        #unit
+                        separator = ", ";
                     }
-                },
+                }
+            })
+            .collect::<Vec<_>>();
+        impls.push(quote! {
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    f.write_str(::core::stringify!(#ident))?;
+                    #[allow(unused_mut)]
+                    let mut separator = " { ";
+                    #(#field_writes)*
+                    f.write_str(if separator == " { " { " {}" } else { " }" })
+                }
             }
-        },
-    ).collect::<Vec<_>>();
+        });
+    }
 
-    let mut impls = vec![quote! {
-        impl #impl_generics ::proc_bitfield::Bitfield for #ident #ty_generics #where_clause {
-            type Storage = #storage_ty;
+    if auto_impls.u_debug {
+        let debug_raw_entry = debug_raw.then(|| quote! { s.field("0", &self.0)?; });
+        let field_entries = fields
+            .iter()
+            .filter(|field| field.is_readable())
+            .map(|field| {
+                let field_ident = &field.ident;
+                let value = field.fmt_value();
+                let cfg_attrs = field.cfg_attrs();
+                quote! {
+                    #(#cfg_attrs)*
+                    s.field(::core::stringify!(#field_ident), #value)?;
+                }
+            });
+        impls.push(quote! {
+            impl #impl_generics ::ufmt::uDebug for #ident #ty_generics #where_clause {
+                fn fmt<W>(
+                    &self,
+                    f: &mut ::ufmt::Formatter<'_, W>,
+                ) -> ::core::result::Result<(), W::Error>
+                where
+                    W: ::ufmt::uWrite + ?Sized,
+                {
+                    let mut s = f.debug_struct(#debug_name)?;
+                    #debug_raw_entry
+                    #(#field_entries)*
+                    s.finish()
+                }
+            }
+        });
+    }
 
-            #[inline]
-            fn from_storage(storage: Self::Storage) -> Self {
-                Self(storage #type_params_phantom_data)
+    if auto_impls.u_display {
+        // Fields can be disabled by `cfg` attributes, so separators are tracked at runtime
+        let field_writes = fields
+            .iter()
+            .filter(|field| field.is_readable())
+            .map(|field| {
+                let name = format!("{}: ", field.ident);
+                let value = field.fmt_value();
+                let unit = field.unit().map(|unit| {
+                    let unit = format!(" {}", unit.value());
+                    quote! { f.write_str(#unit)?; }
+                });
+                let cfg_attrs = field.cfg_attrs();
+                quote! {
+                    #(#cfg_attrs)*
+                    {
+                        f.write_str(separator)?;
+                        f.write_str(#name)?;
+                        ::ufmt::uDebug::fmt(#value, f)?;
+                        #unit
+                        separator = ", ";
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        impls.push(quote! {
+            impl #impl_generics ::ufmt::uDisplay for #ident #ty_generics #where_clause {
+                fn fmt<W>(
+                    &self,
+                    f: &mut ::ufmt::Formatter<'_, W>,
+                ) -> ::core::result::Result<(), W::Error>
+                where
+                    W: ::ufmt::uWrite + ?Sized,
+                {
+                    f.write_str(::core::stringify!(#ident))?;
+                    #[allow(unused_mut)]
+                    let mut separator = " { ";
+                    #(#field_writes)*
+                    f.write_str(if separator == " { " { " {}" } else { " }" })
+                }
             }
+        });
+    }
 
-            #[inline]
-            fn into_storage(self) -> Self::Storage {
-                self.0
+    #[cfg(feature = "alloc")]
+    if options.minimal.is_none() {
+        let field_inserts = fields
+            .iter()
+            .filter(|field| field.is_readable())
+            .map(|field| {
+                let field_ident = &field.ident;
+                let getter_ident = &field.getter_ident;
+                let value = if matches!(field.content, FieldContent::Nested(_)) {
+                    quote! { &*self.#getter_ident() }
+                } else if field.has_unsafe_getter() {
+                    quote! { &unsafe { self.#getter_ident() } }
+                } else if field.has_cached_getter() {
+                    quote! { self.#getter_ident(&mut ::proc_bitfield::cache::Cached::new()) }
+                } else {
+                    quote! { &self.#getter_ident() }
+                };
+                let cfg_attrs = field.cfg_attrs();
+                quote! {
+                    #(#cfg_attrs)*
+                    map.insert(
+                        ::core::stringify!(#field_ident),
+                        (&Probe(#value)).__to_field_value(),
+                    );
+                }
+            });
+        impls.push(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Returns the values of all of this bitfield's readable fields, indexed by name.
+                #items_vis fn to_value_map(
+                    &self,
+                ) -> ::proc_bitfield::__private::BTreeMap<
+                    &'static str,
+                    ::proc_bitfield::value::FieldValue,
+                > {
+                    #[allow(unused_imports)]
+                    use ::proc_bitfield::value::__probe::{Probe, ViaOpaque, ViaToFieldValue};
+                    #[allow(unused_mut)]
+                    let mut map = ::proc_bitfield::__private::BTreeMap::new();
+                    #(#field_inserts)*
+                    map
+                }
+            }
+
+            impl #impl_generics ::proc_bitfield::value::ToFieldValue for #ident #ty_generics
+                #where_clause
+            {
+                #[inline]
+                fn to_field_value(&self) -> ::proc_bitfield::value::FieldValue {
+                    ::proc_bitfield::value::FieldValue::Bitfield(self.to_value_map())
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "rand")]
+    if options.minimal.is_none() {
+        let randomized_fields = fields
+            .iter()
+            .zip(&bits_spans)
+            .filter_map(|(field, bits_span)| {
+                let FieldContent::Single(content) = &field.content else {
+                    return None;
+                };
+                if !field.is_writable()
+                    || matches!(
+                        content.get_kind,
+                        AccessorKind::UnsafeConvTy { .. } | AccessorKind::UnsafeConvFn { .. }
+                    )
+                {
+                    return None;
+                }
+                let getter_ident = &field.getter_ident;
+                let field_ty = &field.ty;
+                let variants = match field.conv_ty() {
+                    Some(conv_ty) => quote! {
+                        (&EnumProbe::<#conv_ty>::new())
+                            .__enum_info()
+                            .map(|(_, variants)| variants)
+                    },
+                    None => quote! { ::core::option::Option::None },
+                };
+                let (write, raw_value) = match bits_span {
+                    BitsSpan::Single(bit) => (
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::SetBits<u128>>
+                                ::set_bits::<#bit, { (#bit) + 1 }>(&mut self.0, value);
+                        },
+                        quote! { <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(&self.0) },
+                    ),
+                    BitsSpan::Range { start, end } => (
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::SetBits<u128>>
+                                ::set_bits::<#start, #end>(&mut self.0, value);
+                        },
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                                ::bits::<#start, #end>(&self.0)
+                        },
+                    ),
+                    BitsSpan::Strided { start, step, count } => (
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::SetStridedBits<u128>>
+                                ::set_strided_bits::<#start, #step, #count>(&mut self.0, value);
+                        },
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::StridedBits<#field_ty>>
+                                ::strided_bits::<#start, #step, #count>(&self.0)
+                        },
+                    ),
+                    BitsSpan::Full => (
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::SetBits<u128>>
+                                ::set_bits::<0, #storage_ty_bits>(&mut self.0, value);
+                        },
+                        quote! {
+                            <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                                ::bits::<0, #storage_ty_bits>(&self.0)
+                        },
+                    ),
+                };
+                // Fields whose getters can fail (or panic) are only left with values that can be
+                // read back successfully
+                let is_valid = match &content.get_kind {
+                    AccessorKind::TryConvTy(_) | AccessorKind::TryGetFn { .. } => Some(quote! {
+                        ::proc_bitfield::__private::IsOk::is_ok(&self.#getter_ident())
+                    }),
+                    AccessorKind::UnwrapConvTy(ty) => Some(quote! {
+                        <#ty as ::core::convert::TryFrom<#field_ty>>::try_from(#raw_value).is_ok()
+                    }),
+                    AccessorKind::UnwrapConvFn { fn_, .. } => Some(quote! {
+                        ::proc_bitfield::__private::IsOk::is_ok(&#fn_(#raw_value))
+                    }),
+                    AccessorKind::Default => content.range.as_ref().map(|range| {
+                        quote! { (#range).contains(&self.#getter_ident()) }
+                    }),
+                    _ => None,
+                };
+                let value = quote! { ::proc_bitfield::random::raw_value(rng, variants) };
+                let cfg_attrs = field.cfg_attrs();
+                let randomize = match is_valid {
+                    Some(is_valid) => quote! {
+                        let variants = #variants;
+                        let prev = ::core::clone::Clone::clone(&self.0);
+                        let mut valid = false;
+                        for _ in 0..64 {
+                            let value = #value;
+                            #write
+                            if #is_valid {
+                                valid = true;
+                                break;
+                            }
+                        }
+                        if !valid {
+                            self.0 = prev;
+                        }
+                    },
+                    None => quote! {
+                        let variants = #variants;
+                        let value = #value;
+                        #write
+                    },
+                };
+                Some(quote! {
+                    #(#cfg_attrs)*
+                    { #randomize }
+                })
+            })
+            .collect::<Vec<_>>();
+        // Generic storage types are only required to support random writes when randomizing
+        let randomize_where_clause = if generic_storage {
+            let strided_bound = bits_spans
+                .iter()
+                .any(|bits_span| matches!(bits_span, BitsSpan::Strided { .. }))
+                .then(|| quote! { + ::proc_bitfield::SetStridedBits<u128> });
+            quote! { where #storage_ty: ::proc_bitfield::SetBits<u128> #strided_bound }
+        } else {
+            quote! {}
+        };
+        impls.push(quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Writes random values to all of this bitfield's writable fields, except for
+                /// nested bitfield fields and fields read through unsafe conversions; fields
+                /// converted to enums only receive the values of their variants, and fields with
+                /// fallible getters are left unchanged if no valid value could be found.
+                #[allow(clippy::identity_op, unused_variables)]
+                #items_vis fn randomize<R: ::proc_bitfield::random::RawRng + ?::core::marker::Sized>(
+                    &mut self,
+                    rng: &mut R,
+                ) #randomize_where_clause {
+                    #[allow(unused_imports)]
+                    use ::proc_bitfield::meta::__probe::{EnumProbe, ViaEnumInfo, ViaNotEnum};
+                    #(#randomized_fields)*
+                }
             }
+        });
+    }
 
+    if !generic_storage && options.minimal.is_none() {
+        impls.push(quote! {
+        impl #impl_generics ::proc_bitfield::meta::AnyBitfield for #ident #ty_generics
+            #where_clause
+        {
             #[inline]
-            fn storage(&self) -> &Self::Storage {
-                &self.0
+            fn layout(&self) -> ::proc_bitfield::meta::Layout {
+                Self::LAYOUT
             }
 
             #[inline]
-            fn storage_mut(&mut self) -> &mut Self::Storage {
-                &mut self.0
+            fn raw_u128(&self) -> u128 {
+                <#storage_ty as ::proc_bitfield::Bits<u128>>::bits::<
+                    0,
+                    { if #storage_ty_bits < 128 { #storage_ty_bits } else { 128 } },
+                >(&self.0)
             }
         }
-    }];
+        });
+    }
 
-    if auto_impls.debug {
-        let readable_fields = fields.iter().filter(|field| field.is_readable());
-        let field_idents = readable_fields.clone().map(|field| &field.ident);
-        let field_unsafes = readable_fields.map(|field| {
-            if field.has_unsafe_getter() {
-                quote! { unsafe }
-            } else {
-                quote! {}
+    if auto_impls.merge {
+        let merged_fields = fields
+            .iter()
+            .zip(&bits_spans)
+            .filter_map(|(field, bits_span)| {
+                let FieldContent::Single(content) = &field.content else {
+                    return None;
+                };
+                let merge_kind = content.merge_kind.unwrap_or(match bits_span {
+                    BitsSpan::Single(_) => MergeKind::Or,
+                    _ => MergeKind::Last,
+                });
+                if matches!(merge_kind, MergeKind::Last) {
+                    return None;
+                }
+                let field_ty = &field.ty;
+                let cfg_attrs = field.cfg_attrs();
+                let merge = match bits_span {
+                    BitsSpan::Single(bit) => quote! {
+                        let value = <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(&self.0)
+                            | <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(&other.0);
+                        storage = <#storage_ty as ::proc_bitfield::WithBit>::with_bit::<#bit>(
+                            storage,
+                            value,
+                        );
+                    },
+                    BitsSpan::Range { start, end } => quote! {
+                        let value = <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                            ::bits::<#start, #end>(&self.0)
+                            | <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                                ::bits::<#start, #end>(&other.0);
+                        storage = <#storage_ty as ::proc_bitfield::WithBits<#field_ty>>
+                            ::with_bits::<#start, #end>(storage, value);
+                    },
+                    BitsSpan::Strided { start, step, count } => quote! {
+                        let value = <#storage_ty as ::proc_bitfield::StridedBits<#field_ty>>
+                            ::strided_bits::<#start, #step, #count>(&self.0)
+                            | <#storage_ty as ::proc_bitfield::StridedBits<#field_ty>>
+                                ::strided_bits::<#start, #step, #count>(&other.0);
+                        storage = <#storage_ty as ::proc_bitfield::WithStridedBits<#field_ty>>
+                            ::with_strided_bits::<#start, #step, #count>(storage, value);
+                    },
+                    BitsSpan::Full => quote! {
+                        let value = <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                            ::bits::<0, #storage_ty_bits>(&self.0)
+                            | <#storage_ty as ::proc_bitfield::Bits<#field_ty>>
+                                ::bits::<0, #storage_ty_bits>(&other.0);
+                        storage = <#storage_ty as ::proc_bitfield::WithBits<#field_ty>>
+                            ::with_bits::<0, #storage_ty_bits>(storage, value);
+                    },
+                };
+                Some(quote! {
+                    #(#cfg_attrs)*
+                    { #merge }
+                })
+            });
+        let merged_fields = merged_fields.collect::<Vec<_>>();
+        let storage_decl = if merged_fields.is_empty() {
+            quote! { let storage = other.0; }
+        } else {
+            quote! {
+                let mut storage = ::core::clone::Clone::clone(&other.0);
+                #(#merged_fields)*
+            }
+        };
+        impls.push(quote! {
+            impl #impl_generics ::proc_bitfield::Merge for #ident #ty_generics #where_clause {
+                #[allow(clippy::identity_op)]
+                fn merge(self, other: Self) -> Self {
+                    #storage_decl
+                    Self(storage #extra_members_of_other)
+                }
+            }
+
+            impl #impl_generics ::core::iter::FromIterator<Self> for #ident #ty_generics
+                #where_clause
+            {
+                fn from_iter<I: ::core::iter::IntoIterator<Item = Self>>(iter: I) -> Self {
+                    iter.into_iter().fold(
+                        Self(::core::default::Default::default() #extra_members_init),
+                        ::proc_bitfield::Merge::merge,
+                    )
+                }
             }
         });
+    }
+
+    if auto_impls.flags {
+        let flag_fields = fields
+            .iter()
+            .zip(&bits_spans)
+            .filter_map(
+                |(field, bits_span)| match (&field.content, bits_span, &field.ty) {
+                    (FieldContent::Single(_), BitsSpan::Single(bit), Type::Path(ty))
+                        if ty.qself.is_none() && ty.path.is_ident("bool") =>
+                    {
+                        Some((field, bit))
+                    }
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+        let flag_count = enabled_field_count(flag_fields.iter().map(|(field, _)| *field));
+        let (flag_entries, flag_arms): (Vec<_>, Vec<_>) = flag_fields
+            .iter()
+            .map(|(field, bit)| {
+                let name = LitStr::new(&field.ident.to_string(), field.ident.span());
+                let cfg_attrs = field.cfg_attrs();
+                (
+                    quote! {
+                        #(#cfg_attrs)*
+                        (#name, <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(&self.0)),
+                    },
+                    quote! {
+                        #(#cfg_attrs)*
+                        #name => <#storage_ty as ::proc_bitfield::SetBit>
+                            ::set_bit::<#bit>(&mut storage, true),
+                    },
+                )
+            })
+            .unzip();
         impls.push(quote! {
-            impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
-                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                    f.debug_struct(::core::stringify!(#ident))
-                        .field("0", &self.0)
-                        #(.field(
-                            ::core::stringify!(#field_idents),
-                            #field_unsafes { &self.#field_idents() },
-                        ))*
-                        .finish()
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Returns the names of all of this bitfield's single-bit `bool` fields that are
+                /// set, in declaration order.
+                #vis fn set_flags(&self) -> impl ::core::iter::Iterator<Item = &'static str> {
+                    let flags: [(&'static str, bool); #flag_count] = [#(#flag_entries)*];
+                    flags
+                        .into_iter()
+                        .filter_map(|(name, is_set)| is_set.then_some(name))
+                }
+
+                /// Returns a bitfield with the single-bit `bool` fields with the given names set,
+                /// and all other bits cleared, or the first name that doesn't match any such
+                /// field.
+                #vis fn mask_from_names<'a>(
+                    names: &[&'a str],
+                ) -> ::core::result::Result<Self, &'a str> {
+                    #[allow(unused_mut)]
+                    let mut storage: #storage_ty = ::core::default::Default::default();
+                    for &name in names {
+                        match name {
+                            #(#flag_arms)*
+                            _ => return ::core::result::Result::Err(name),
+                        }
+                    }
+                    ::core::result::Result::Ok(Self(storage #extra_members_init))
                 }
             }
         });
@@ -1155,8 +5834,8 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
             impl #impl_generics ::core::convert::From<#storage_ty> for #ident #ty_generics
                 #where_clause
             {
-                fn from(other: #storage_ty) -> Self {
-                    Self(other #type_params_phantom_data)
+                fn from(storage: #storage_ty) -> Self {
+                    Self(#masked_storage #extra_members_init)
                 }
             }
         });
@@ -1187,24 +5866,208 @@ pub fn bitfield(input: TokenStream) -> TokenStream {
     }
 
     let type_params_phantom_data_field = if has_type_params {
-        let type_params = generics.type_params();
+        let type_params = generics.type_params().map(|param| &param.ident);
         quote! { , #storage_vis ::core::marker::PhantomData<(#(#type_params),*)> }
     } else {
         quote! {}
     };
+    let extra_member_fields = extra_members
+        .iter()
+        .map(|ExtraMember { attrs, vis, ty, .. }| quote! { , #(#attrs)* #vis #ty });
+    // Extra members may have any size, so the struct can only be transparent without them
+    let repr_attr = extra_members
+        .is_empty()
+        .then(|| quote! { #[repr(transparent)] });
+
+    // Register names are surfaced in the struct's documentation as well as its metadata
+    let (register_name, register_name_doc) = match &options.name {
+        Some(name) => {
+            let doc = format!("Register name: `{}`.", name.value());
+            (
+                quote! { ::core::option::Option::Some(#name) },
+                quote! {
+                    #[doc = ""]
+                    #[doc = #doc]
+                },
+            )
+        }
+        None => (quote! { ::core::option::Option::None }, quote! {}),
+    };
+
+    // The layout is also surfaced in the struct's documentation as a table sorted by bit position,
+    // so it can be checked against datasheets; positions that can't be evaluated by the macro (i.e.
+    // depending on user-defined constants) are shown as written
+    let layout_doc = {
+        let describe_bits = |bits_span: &BitsSpan| -> (Option<u128>, String) {
+            let (start, last, step) = match bits_span {
+                BitsSpan::Single(bit) => (bit.clone(), bit.clone(), None),
+                BitsSpan::Range { start, end } => (start.clone(), quote! { (#end) - 1 }, None),
+                BitsSpan::Strided { start, step, count } => (
+                    start.clone(),
+                    quote! { (#start) + (#step) * ((#count) - 1) },
+                    Some(step.clone()),
+                ),
+                BitsSpan::Full => (quote! { 0 }, quote! { (#storage_ty_bits) - 1 }, None),
+            };
+            let (start, last) = (LinearExpr::parse(start), LinearExpr::parse(last));
+            let mut desc = if start == last {
+                start.to_string()
+            } else {
+                format!("{last}:{start}")
+            };
+            if let Some(step) = step {
+                desc += &format!(" (every {} bits)", LinearExpr::parse(step));
+            }
+            (start.value(), desc)
+        };
+        let mut rows = fields
+            .iter()
+            .zip(&bits_spans)
+            .map(|(field, bits_span)| {
+                let (start, bits) = describe_bits(bits_span);
+                let access = match (field.is_readable(), field.is_writable()) {
+                    (true, true) => "R/W",
+                    (true, false) => "R",
+                    (false, true) => "W",
+                    (false, false) => "-",
+                };
+                let ty = field.conv_ty().unwrap_or(&field.ty);
+                let row = format!(
+                    "| {bits} | `{}` | {access} | `{}` |",
+                    field.ident,
+                    tokens_to_doc_string(quote! { #ty }),
+                );
+                let predicates = field
+                    .cfg_attrs()
+                    .into_iter()
+                    .filter_map(|attr| attr.meta.require_list().ok())
+                    .map(|list| list.tokens.clone())
+                    .collect::<Vec<_>>();
+                let doc = if predicates.is_empty() {
+                    quote! { #[doc = #row] }
+                } else {
+                    quote! { #[cfg_attr(all(#(#predicates),*), doc = #row)] }
+                };
+                (start, doc)
+            })
+            .chain(reserved_spans.iter().map(|bits_span| {
+                let (start, bits) = describe_bits(bits_span);
+                let row = format!("| {bits} | *reserved* | - | |");
+                (start, quote! { #[doc = #row] })
+            }))
+            .collect::<Vec<_>>();
+        // Rows whose position couldn't be evaluated are kept in declaration order at the end
+        rows.sort_by_key(|(start, _)| start.map_or((1, 0), |start| (0, start)));
+        let rows = rows.into_iter().map(|(_, doc)| doc);
+        (!fields.is_empty() || !reserved_spans.is_empty()).then(|| {
+            quote! {
+                #[doc = ""]
+                #[doc = "# Layout"]
+                #[doc = ""]
+                #[doc = "| Bits | Name | Access | Type |"]
+                #[doc = "|------|------|--------|------|"]
+                #(#rows)*
+            }
+        })
+    };
+
+    // Minimal bitfields only contain their accessors and constants, without any metadata
+    let metadata = options.minimal.is_none().then(|| {
+        quote! {
+            /// Metadata for all of this bitfield's fields, in declaration order.
+            #items_vis const FIELDS: &'static [::proc_bitfield::meta::FieldInfo] = &[#(#field_infos),*];
+
+            /// Metadata for this bitfield's reserved bit ranges, in declaration order.
+            #items_vis const RESERVED: &'static [::proc_bitfield::meta::FieldInfo] =
+                &[#(#reserved_infos),*];
+
+            /// Returns the metadata for the first field spanning the given bit, if any.
+            #[inline]
+            #items_vis const fn field_at_bit(
+                bit: usize,
+            ) -> ::core::option::Option<&'static ::proc_bitfield::meta::FieldInfo> {
+                ::proc_bitfield::meta::field_at_bit(Self::FIELDS, bit)
+            }
+
+            /// This bitfield's layout, including metadata for all of its fields.
+            #items_vis const LAYOUT: ::proc_bitfield::meta::Layout = ::proc_bitfield::meta::Layout {
+                name: ::core::stringify!(#ident),
+                register_name: #register_name,
+                bits: #storage_ty_bits,
+                fields: Self::FIELDS,
+                field_enum: |i| {
+                    #[allow(unused_imports)]
+                    use ::proc_bitfield::meta::__probe::{EnumProbe, ViaEnumInfo, ViaNotEnum};
+                    match i {
+                        #(#field_enum_arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                },
+            };
+
+            /// A fingerprint of this bitfield's layout, computed from its storage's width and its
+            /// fields' names and bit ranges; see [`layout_hash`](::proc_bitfield::meta::layout_hash).
+            #items_vis const LAYOUT_HASH: u64 =
+                ::proc_bitfield::meta::layout_hash(#storage_ty_bits, Self::FIELDS);
+
+            /// The width of this bitfield's storage type, in bits.
+            #items_vis const BITS: u32 = #storage_ty_bits as u32;
+
+            /// The size of this bitfield's storage type, in bytes.
+            #items_vis const BYTES: usize = ::core::mem::size_of::<#storage_ty>();
+
+            /// The number of bits up to and including the highest one spanned by any field; see
+            /// [`used_bits`](::proc_bitfield::meta::used_bits).
+            #items_vis const USED_BITS: u32 = ::proc_bitfield::meta::used_bits(Self::FIELDS) as u32;
+
+            /// Writes a JSON description of this bitfield's layout; see
+            /// [`Layout::write_json`](::proc_bitfield::meta::Layout::write_json).
+            #items_vis fn write_layout_json(w: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                Self::LAYOUT.write_json(w)
+            }
+        }
+    });
+
+    // Move the accessors past the first `split_impls` ones to their own `impl` blocks
+    let split_accessor_fns = match options.split_impls {
+        Some(len) if accessor_fns.len() > len => {
+            let rest = accessor_fns.split_off(len);
+            rest.chunks(len).map(<[_]>::to_vec).collect()
+        }
+        _ => Vec::new(),
+    };
+
+    let output = quote! {
+        #(#views)*
 
-    (quote! {
         #(#outer_attrs)*
-        #[repr(transparent)]
+        #register_name_doc
+        #layout_doc
+        #auto_derive_attr
+        #repr_attr
         #vis struct #ident #generics(
-            #storage_vis #storage_ty #type_params_phantom_data_field
+            #storage_vis #storage_ty #(#extra_member_fields)* #type_params_phantom_data_field
         ) #where_clause;
 
         impl #impl_generics #ident #ty_generics #where_clause {
-            #(#field_fns)*
+            #metadata
+
+            #(#consts)*
+            #(#accessor_fns)*
         }
 
+        #(
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#split_accessor_fns)*
+            }
+        )*
+
         #(#impls)*
-    })
-    .into()
+
+        #layout_checks
+    };
+    if stats::enabled() {
+        stats::report(&ident, field_count, &output);
+    }
+    output.into()
 }
@@ -4,56 +4,276 @@ use quote::quote;
 use std::borrow::Cow;
 use syn::{
     parse::{Parse, ParseStream, Result},
-    Error, Expr, Lit, Token, Type,
+    parse_quote, Error, Expr, ExprLit, Token, Type,
 };
 
 mod kw {
     syn::custom_keyword!(above);
     syn::custom_keyword!(below);
+    syn::custom_keyword!(step);
+    syn::custom_keyword!(count);
 }
 
 #[derive(Clone)]
 pub enum Bits {
-    Single(Lit),
+    Single(Expr),
     SinglePack {
         above_below_span: proc_macro2::Span,
         above: bool,
     },
     Range {
-        start: Lit,
-        end: Lit,
+        start: Expr,
+        end: Expr,
+    },
+    RangeFrom {
+        start: Expr,
+        span: proc_macro2::Span,
     },
     RangeInclusive {
-        start: Lit,
-        end: Lit,
+        start: Expr,
+        end: Expr,
     },
     OffsetAndLength {
-        start: Lit,
-        length: Lit,
+        start: Expr,
+        length: Expr,
     },
     Pack {
         above_below_span: proc_macro2::Span,
         above: bool,
-        length: Lit,
+        length: Expr,
+    },
+    Strided {
+        start: Expr,
+        step: Expr,
+        count: Expr,
     },
     RangeFull,
 }
 
+/// Parses a bit position as a constant expression, stopping before any top-level `..`, `..=`,
+/// `;`, `,` or `=` token, as they delimit bit positions in *FieldRange*s.
+fn parse_bit_position(input: ParseStream) -> Result<Expr> {
+    let mut tokens = proc_macro2::TokenStream::new();
+    let mut after_joint_punct = false;
+    while !(input.is_empty()
+        || input.peek(Token![..])
+        || input.peek(Token![;])
+        || input.peek(Token![,])
+        || (input.peek(Token![=]) && !input.peek(Token![==]) && !after_joint_punct))
+    {
+        let tt = input.parse::<proc_macro2::TokenTree>()?;
+        after_joint_punct = matches!(
+            &tt,
+            proc_macro2::TokenTree::Punct(punct) if punct.spacing() == proc_macro2::Spacing::Joint
+        );
+        tokens.extend([tt]);
+    }
+    if tokens.is_empty() {
+        return Err(input.error("expected a bit position"));
+    }
+    syn::parse2(tokens)
+}
+
+/// Converts a bit position into tokens usable as a const generic argument, wrapping any
+/// non-literal expressions in braces.
+fn bit_position(expr: Expr) -> proc_macro2::TokenStream {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => quote! { #lit },
+        expr => quote! { {#expr} },
+    }
+}
+
+/// Returns whether the given tokens mention the `msb` placeholder.
+fn mentions_msb(tokens: proc_macro2::TokenStream) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => ident == "msb",
+        proc_macro2::TokenTree::Group(group) => mentions_msb(group.stream()),
+        _ => false,
+    })
+}
+
+/// Replaces the `msb` placeholder in a bit position with the given position of the storage's most
+/// significant bit.
+fn resolve_msb(
+    tokens: proc_macro2::TokenStream,
+    msb: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            proc_macro2::TokenTree::Ident(ident) if ident == "msb" => {
+                proc_macro2::TokenTree::Group(proc_macro2::Group::new(
+                    proc_macro2::Delimiter::Parenthesis,
+                    msb.clone(),
+                ))
+            }
+            proc_macro2::TokenTree::Group(group) => {
+                let mut resolved =
+                    proc_macro2::Group::new(group.delimiter(), resolve_msb(group.stream(), msb));
+                resolved.set_span(group.span());
+                proc_macro2::TokenTree::Group(resolved)
+            }
+            tt => tt,
+        })
+        .collect()
+}
+
 impl Bits {
-    pub fn into_span(self, last: Option<&BitsSpan>) -> Result<BitsSpan> {
+    /// Returns whether any of the bit positions are relative to the most significant bit of the
+    /// storage, through the `msb` placeholder.
+    pub fn mentions_msb(&self) -> bool {
+        let exprs: &[&Expr] = match self {
+            Bits::Single(bit) => &[bit],
+            Bits::Range { start, end } | Bits::RangeInclusive { start, end } => &[start, end],
+            Bits::RangeFrom { start, .. } => &[start],
+            Bits::OffsetAndLength { start, length } => &[start, length],
+            Bits::Pack { length, .. } => &[length],
+            Bits::Strided { start, step, count } => &[start, step, count],
+            Bits::SinglePack { .. } | Bits::RangeFull => &[],
+        };
+        exprs.iter().any(|expr| mentions_msb(quote! { #expr }))
+    }
+
+    /// Moves the bit range of a field of a flattened bitfield, whose storage is `storage_ty_bits`
+    /// wide, up by `offset` bits.
+    ///
+    /// `..`, open-ended ranges and the `msb` placeholder are resolved relative to the flattened
+    /// bitfield's storage, as is packing for its `first` field; other packed fields stay relative
+    /// to the previous one.
+    pub fn flatten(
+        self,
+        offset: &Expr,
+        storage_ty_bits: &proc_macro2::TokenStream,
+        first: bool,
+    ) -> Bits {
+        let msb = quote! { (#storage_ty_bits) - 1 };
+        let shift = |expr: Expr| -> Expr {
+            let expr = resolve_msb(quote! { #expr }, &msb);
+            parse_quote! { (#offset) + (#expr) }
+        };
+        let end: Expr = parse_quote! { (#offset) + (#storage_ty_bits) };
+        match self {
+            Bits::Single(bit) => Bits::Single(shift(bit)),
+            Bits::Range { start, end } => Bits::Range {
+                start: shift(start),
+                end: shift(end),
+            },
+            Bits::RangeInclusive { start, end } => Bits::RangeInclusive {
+                start: shift(start),
+                end: shift(end),
+            },
+            Bits::RangeFrom { start, .. } => Bits::Range {
+                start: shift(start),
+                end,
+            },
+            Bits::OffsetAndLength { start, length } => Bits::OffsetAndLength {
+                start: shift(start),
+                length,
+            },
+            Bits::Strided { start, step, count } => Bits::Strided {
+                start: shift(start),
+                step,
+                count,
+            },
+            Bits::RangeFull => Bits::Range {
+                start: parse_quote! { #offset },
+                end,
+            },
+            Bits::SinglePack { above: true, .. } if first => Bits::Single(parse_quote! { #offset }),
+            Bits::SinglePack { above: false, .. } if first => {
+                Bits::Single(parse_quote! { (#end) - 1 })
+            }
+            Bits::Pack {
+                above: true,
+                length,
+                ..
+            } if first => Bits::OffsetAndLength {
+                start: parse_quote! { #offset },
+                length,
+            },
+            Bits::Pack {
+                above: false,
+                length,
+                ..
+            } if first => Bits::Range {
+                start: parse_quote! { (#end) - (#length) },
+                end,
+            },
+            bits @ (Bits::SinglePack { .. } | Bits::Pack { .. }) => bits,
+        }
+    }
+
+    /// Moves the bit range up by `offset` bits, leaving packed fields relative to the previous one.
+    pub fn shift(self, offset: &Expr) -> Bits {
+        let shift = |expr: Expr| -> Expr {
+            parse_quote! { (#offset) + (#expr) }
+        };
+        match self {
+            Bits::Single(bit) => Bits::Single(shift(bit)),
+            Bits::Range { start, end } => Bits::Range {
+                start: shift(start),
+                end: shift(end),
+            },
+            Bits::RangeInclusive { start, end } => Bits::RangeInclusive {
+                start: shift(start),
+                end: shift(end),
+            },
+            Bits::RangeFrom { start, span } => Bits::RangeFrom {
+                start: shift(start),
+                span,
+            },
+            Bits::OffsetAndLength { start, length } => Bits::OffsetAndLength {
+                start: shift(start),
+                length,
+            },
+            Bits::Strided { start, step, count } => Bits::Strided {
+                start: shift(start),
+                step,
+                count,
+            },
+            bits @ (Bits::SinglePack { .. } | Bits::Pack { .. } | Bits::RangeFull) => bits,
+        }
+    }
+
+    /// Resolves the bit range into a span, placing packed fields relative to the `last` one.
+    ///
+    /// If `storage_ty_bits` is specified, packed fields with no previous field are placed at the
+    /// bottom (for `above`) or top (for `below`) of the storage.
+    pub fn into_span(
+        self,
+        last: Option<&BitsSpan>,
+        storage_ty_bits: Option<&proc_macro2::TokenStream>,
+    ) -> Result<BitsSpan> {
+        // `msb` is only resolved when the storage's width is known, and left as an unresolved
+        // identifier otherwise
+        let msb = storage_ty_bits.map(|storage_ty_bits| quote! { #storage_ty_bits - 1 });
+        let bit_position = |expr: Expr| match &msb {
+            Some(msb) => resolve_msb(bit_position(expr), msb),
+            None => bit_position(expr),
+        };
+        let pack_bounds = |above_below_span| {
+            match (last, storage_ty_bits) {
+                (Some(last), _) => last.to_start_end(),
+                (None, Some(storage_ty_bits)) => Some((
+                    Cow::Owned(storage_ty_bits.clone()),
+                    Cow::Owned(quote! { 0 }),
+                )),
+                (None, None) => None,
+            }
+            .ok_or_else(|| {
+                Error::new(
+                    above_below_span,
+                    "cannot use field packing in this position",
+                )
+            })
+        };
         Ok(match self {
-            Bits::Single(bit) => BitsSpan::Single(quote! { #bit }),
+            Bits::Single(bit) => BitsSpan::Single(bit_position(bit)),
             Bits::SinglePack {
                 above_below_span,
                 above,
             } => {
-                let (last_start, last_end) =
-                    last.and_then(BitsSpan::to_start_end).ok_or_else(|| {
-                        Error::new(
-                            above_below_span,
-                            "cannot use field packing in this position",
-                        )
-                    })?;
+                let (last_start, last_end) = pack_bounds(above_below_span)?;
                 if above {
                     BitsSpan::Single(last_end.into_owned())
                 } else {
@@ -61,18 +281,31 @@ impl Bits {
                 }
             }
             Bits::Range { start, end } => BitsSpan::Range {
-                start: quote! { #start },
-                end: quote! { #end },
+                start: bit_position(start),
+                end: bit_position(end),
             },
-            Bits::RangeInclusive { start, end } => BitsSpan::Range {
-                start: quote! { #start },
-                end: quote! { {#end + 1} },
+            Bits::RangeFrom { start, span } => BitsSpan::Range {
+                start: bit_position(start),
+                end: storage_ty_bits.cloned().ok_or_else(|| {
+                    Error::new(
+                        span,
+                        "open-ended ranges can only be used in bitfield declarations",
+                    )
+                })?,
             },
+            Bits::RangeInclusive { start, end } => {
+                let end = bit_position(end);
+                BitsSpan::Range {
+                    start: bit_position(start),
+                    end: quote! { {(#end) + 1} },
+                }
+            }
             Bits::OffsetAndLength { start, length } => {
-                let end = quote! { {#start + #length} };
+                let start = bit_position(start);
+                let length = bit_position(length);
                 BitsSpan::Range {
-                    start: quote! { #start },
-                    end,
+                    end: quote! { {(#start) + (#length)} },
+                    start,
                 }
             }
             Bits::Pack {
@@ -80,27 +313,27 @@ impl Bits {
                 above,
                 length,
             } => {
-                let (last_start, last_end) =
-                    last.and_then(BitsSpan::to_start_end).ok_or_else(|| {
-                        Error::new(
-                            above_below_span,
-                            "cannot use field packing in this position",
-                        )
-                    })?;
+                let length = bit_position(length);
+                let (last_start, last_end) = pack_bounds(above_below_span)?;
                 if above {
                     let start = last_end.into_owned();
                     BitsSpan::Range {
-                        end: quote! { {(#start) + #length} },
+                        end: quote! { {(#start) + (#length)} },
                         start,
                     }
                 } else {
                     let end = last_start.into_owned();
                     BitsSpan::Range {
-                        start: quote! { {(#end) - #length} },
+                        start: quote! { {(#end) - (#length)} },
                         end,
                     }
                 }
             }
+            Bits::Strided { start, step, count } => BitsSpan::Strided {
+                start: bit_position(start),
+                step: bit_position(step),
+                count: bit_position(count),
+            },
             Bits::RangeFull => BitsSpan::Full,
         })
     }
@@ -108,8 +341,7 @@ impl Bits {
 
 impl Parse for Bits {
     fn parse(input: ParseStream) -> Result<Self> {
-        let lookahead = input.lookahead1();
-        Ok(if lookahead.peek(Token![..]) {
+        Ok(if input.peek(Token![..]) {
             input.parse::<Token![..]>()?;
             Bits::RangeFull
         } else if input.peek(kw::above) || input.peek(kw::below) {
@@ -118,7 +350,7 @@ impl Parse for Bits {
                 .map(|a| (a.span, true))
                 .or_else(|_| input.parse::<kw::below>().map(|b| (b.span, false)))?;
             if input.parse::<Token![;]>().is_ok() {
-                let length = input.parse()?;
+                let length = parse_bit_position(input)?;
                 Bits::Pack {
                     above_below_span,
                     above,
@@ -130,26 +362,35 @@ impl Parse for Bits {
                     above,
                 }
             }
-        } else if input.peek(Lit) {
-            let start = input.parse()?;
-            let lookahead = input.lookahead1();
-            if lookahead.peek(Token![..=]) {
-                input.parse::<Token![..=]>()?;
-                let end = input.parse()?;
+        } else {
+            let start = parse_bit_position(input)?;
+            if input.parse::<Token![..=]>().is_ok() {
+                let end = parse_bit_position(input)?;
                 Bits::RangeInclusive { start, end }
-            } else if lookahead.peek(Token![..]) {
-                input.parse::<Token![..]>()?;
-                let end = input.parse()?;
-                Bits::Range { start, end }
-            } else if lookahead.peek(Token![;]) {
-                input.parse::<Token![;]>()?;
-                let length = input.parse()?;
+            } else if let Ok(dots) = input.parse::<Token![..]>() {
+                if input.is_empty() || input.peek(Token![,]) {
+                    Bits::RangeFrom {
+                        start,
+                        span: dots.spans[0],
+                    }
+                } else {
+                    let end = parse_bit_position(input)?;
+                    Bits::Range { start, end }
+                }
+            } else if input.parse::<Token![;]>().is_ok() {
+                let length = parse_bit_position(input)?;
                 Bits::OffsetAndLength { start, length }
+            } else if input.peek(Token![,]) && input.peek2(kw::step) && !input.peek3(Token![:]) {
+                input.parse::<Token![,]>()?;
+                input.parse::<kw::step>()?;
+                let step = parse_bit_position(input)?;
+                input.parse::<Token![,]>()?;
+                input.parse::<kw::count>()?;
+                let count = parse_bit_position(input)?;
+                Bits::Strided { start, step, count }
             } else {
                 Bits::Single(start)
             }
-        } else {
-            return Err(lookahead.error());
         })
     }
 }
@@ -161,10 +402,30 @@ pub enum BitsSpan {
         start: proc_macro2::TokenStream,
         end: proc_macro2::TokenStream,
     },
+    Strided {
+        start: proc_macro2::TokenStream,
+        step: proc_macro2::TokenStream,
+        count: proc_macro2::TokenStream,
+    },
     Full,
 }
 
 impl BitsSpan {
+    /// Translates a span whose bit positions are counted from the most significant bit of the
+    /// storage into one counted from the least significant bit, returning `None` for strided
+    /// spans, whose bit order would be reversed.
+    pub fn msb0_to_lsb0(self, storage_ty_bits: &proc_macro2::TokenStream) -> Option<BitsSpan> {
+        Some(match self {
+            BitsSpan::Single(bit) => BitsSpan::Single(quote! { {(#storage_ty_bits) - 1 - (#bit)} }),
+            BitsSpan::Range { start, end } => BitsSpan::Range {
+                start: quote! { {(#storage_ty_bits) - (#end)} },
+                end: quote! { {(#storage_ty_bits) - (#start)} },
+            },
+            BitsSpan::Strided { .. } => return None,
+            BitsSpan::Full => BitsSpan::Full,
+        })
+    }
+
     fn to_start_end(
         &'_ self,
     ) -> Option<(
@@ -173,10 +434,10 @@ impl BitsSpan {
     )> {
         match self {
             BitsSpan::Range { start, end } => Some((Cow::Borrowed(start), Cow::Borrowed(end))),
-            BitsSpan::Single(bit) => {
-                let bit = quote! { #bit };
-                Some((Cow::Owned(bit.clone()), Cow::Owned(bit)))
-            }
+            BitsSpan::Single(bit) => Some((
+                Cow::Owned(quote! { #bit }),
+                Cow::Owned(quote! { {(#bit) + 1} }),
+            )),
             _ => None,
         }
     }
@@ -198,7 +459,7 @@ fn ty_bits(ty: &Option<Type>, runtime_value: proc_macro2::TokenStream) -> proc_m
     }
 }
 
-fn asserts(
+pub fn asserts(
     bits_span: &BitsSpan,
     has_storage_ty: bool,
     storage_ty_bits: &proc_macro2::TokenStream,
@@ -220,6 +481,14 @@ fn asserts(
                 #field_ty_assert(#end - #start <= #field_ty_bits);
             }
         }
+        BitsSpan::Strided { start, step, count } => {
+            quote! {
+                ::proc_bitfield::__private::static_assertions::const_assert!(#step > 0);
+                ::proc_bitfield::__private::static_assertions::const_assert!(#count > 0);
+                #storage_ty_assert((#start) + (#step) * ((#count) - 1) < #storage_ty_bits);
+                #field_ty_assert(#count <= #field_ty_bits);
+            }
+        }
         BitsSpan::Full => {
             let assert = maybe_const_assert(has_storage_ty && has_field_ty);
             quote! {
@@ -239,6 +508,13 @@ impl Parse for TyAndAtSign {
     }
 }
 
+fn parse_ty_and_at_sign(input: ParseStream) -> Option<Type> {
+    // Bit positions can be expressions that start like types, so only consume the type if it's
+    // followed by `@`
+    input.fork().parse::<TyAndAtSign>().ok()?;
+    input.parse::<TyAndAtSign>().ok().map(|t| t.0)
+}
+
 pub fn bits(input: TokenStream) -> TokenStream {
     struct Arguments {
         storage_value: Expr,
@@ -252,7 +528,7 @@ pub fn bits(input: TokenStream) -> TokenStream {
             let storage_value = input.parse()?;
             let storage_ty = maybe_ty_from_cast_expr(&storage_value);
             input.parse::<Token![,]>()?;
-            let field_ty = input.parse::<TyAndAtSign>().ok().map(|t| t.0);
+            let field_ty = parse_ty_and_at_sign(input);
             let bits = input.parse()?;
             if !input.is_empty() {
                 return Err(input.error("unexpected extra tokens"));
@@ -275,7 +551,7 @@ pub fn bits(input: TokenStream) -> TokenStream {
 
     let storage_ty_bits = ty_bits(&storage_ty, quote! { storage_value });
     let field_ty_bits = ty_bits(&field_ty, quote! { &result });
-    let bits_span = match bits.into_span(None) {
+    let bits_span = match bits.into_span(None, None) {
         Ok(bits_span) => bits_span,
         Err(err) => return err.to_compile_error().into(),
     };
@@ -287,15 +563,17 @@ pub fn bits(input: TokenStream) -> TokenStream {
         &field_ty_bits,
     );
 
-    let (maybe_uninit_ty, bits_trait) = if let Some(field_ty) = &field_ty {
+    let (maybe_uninit_ty, bits_trait, strided_bits_trait) = if let Some(field_ty) = &field_ty {
         (
             quote! { ::core::mem::MaybeUninit::<#field_ty> },
             quote! { ::proc_bitfield::Bits::<#field_ty> },
+            quote! { ::proc_bitfield::StridedBits::<#field_ty> },
         )
     } else {
         (
             quote! { ::core::mem::MaybeUninit },
             quote! { ::proc_bitfield::Bits },
+            quote! { ::proc_bitfield::StridedBits },
         )
     };
 
@@ -326,6 +604,17 @@ pub fn bits(input: TokenStream) -> TokenStream {
                 unsafe { result.assume_init() }
             }}
         }
+        BitsSpan::Strided { start, step, count } => {
+            quote! {{
+                let storage_value = &(#storage_value);
+                let mut result = #maybe_uninit_ty::uninit();
+                #asserts
+                result = #maybe_uninit_ty::new(
+                    #strided_bits_trait::strided_bits::<#start, #step, #count>(storage_value),
+                );
+                unsafe { result.assume_init() }
+            }}
+        }
         BitsSpan::Full => {
             if storage_ty.is_none() {
                 return Error::new_spanned(
@@ -362,7 +651,7 @@ impl Parse for ModifyBitsArguments {
         let storage_value = input.parse()?;
         let storage_ty = maybe_ty_from_cast_expr(&storage_value);
         input.parse::<Token![,]>()?;
-        let pre_field_ty = input.parse::<TyAndAtSign>().ok().map(|t| t.0);
+        let pre_field_ty = parse_ty_and_at_sign(input);
         let bits = input.parse()?;
         input.parse::<Token![=]>()?;
         let field_value = input.parse()?;
@@ -391,7 +680,7 @@ pub fn with_bits(input: TokenStream) -> TokenStream {
 
     let storage_ty_bits = ty_bits(&storage_ty, quote! { &storage_value });
     let field_ty_bits = ty_bits(&field_ty, quote! { &field_value });
-    let bits_span = match bits.into_span(None) {
+    let bits_span = match bits.into_span(None, None) {
         Ok(bits_span) => bits_span,
         Err(err) => return err.to_compile_error().into(),
     };
@@ -403,10 +692,16 @@ pub fn with_bits(input: TokenStream) -> TokenStream {
         &field_ty_bits,
     );
 
-    let bits_trait = if let Some(field_ty) = &field_ty {
-        quote! { ::proc_bitfield::WithBits::<#field_ty> }
+    let (bits_trait, strided_bits_trait) = if let Some(field_ty) = &field_ty {
+        (
+            quote! { ::proc_bitfield::WithBits::<#field_ty> },
+            quote! { ::proc_bitfield::WithStridedBits::<#field_ty> },
+        )
     } else {
-        quote! { ::proc_bitfield::WithBits }
+        (
+            quote! { ::proc_bitfield::WithBits },
+            quote! { ::proc_bitfield::WithStridedBits },
+        )
     };
 
     match bits_span {
@@ -433,6 +728,17 @@ pub fn with_bits(input: TokenStream) -> TokenStream {
                 #bits_trait::with_bits::<#start, #end>(storage_value, field_value)
             }}
         }
+        BitsSpan::Strided { start, step, count } => {
+            quote! {{
+                let storage_value = #storage_value;
+                let field_value = #field_value;
+                #asserts
+                #strided_bits_trait::with_strided_bits::<#start, #step, #count>(
+                    storage_value,
+                    field_value,
+                )
+            }}
+        }
         BitsSpan::Full => {
             if storage_ty.is_none() {
                 return Error::new_spanned(
@@ -469,7 +775,7 @@ pub fn set_bits(input: TokenStream) -> TokenStream {
 
     let storage_ty_bits = ty_bits(&storage_ty, quote! { storage_value });
     let field_ty_bits = ty_bits(&field_ty, quote! { &field_value });
-    let bits_span = match bits.into_span(None) {
+    let bits_span = match bits.into_span(None, None) {
         Ok(bits_span) => bits_span,
         Err(err) => return err.to_compile_error().into(),
     };
@@ -481,10 +787,16 @@ pub fn set_bits(input: TokenStream) -> TokenStream {
         &field_ty_bits,
     );
 
-    let bits_trait = if let Some(field_ty) = &field_ty {
-        quote! { ::proc_bitfield::SetBits::<#field_ty> }
+    let (bits_trait, strided_bits_trait) = if let Some(field_ty) = &field_ty {
+        (
+            quote! { ::proc_bitfield::SetBits::<#field_ty> },
+            quote! { ::proc_bitfield::SetStridedBits::<#field_ty> },
+        )
     } else {
-        quote! { ::proc_bitfield::SetBits }
+        (
+            quote! { ::proc_bitfield::SetBits },
+            quote! { ::proc_bitfield::SetStridedBits },
+        )
     };
 
     match bits_span {
@@ -511,6 +823,17 @@ pub fn set_bits(input: TokenStream) -> TokenStream {
                 #bits_trait::set_bits::<#start, #end>(storage_value, field_value);
             }}
         }
+        BitsSpan::Strided { start, step, count } => {
+            quote! {{
+                let storage_value = &mut #storage_value;
+                let field_value = #field_value;
+                #asserts
+                #strided_bits_trait::set_strided_bits::<#start, #step, #count>(
+                    storage_value,
+                    field_value,
+                );
+            }}
+        }
         BitsSpan::Full => {
             if storage_ty.is_none() {
                 return Error::new_spanned(
@@ -0,0 +1,88 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    spanned::Spanned,
+    Error, Expr, ExprCall, Path, Token,
+};
+
+struct FieldRef {
+    ty: Path,
+    field_name: String,
+    value: Expr,
+}
+
+impl Parse for FieldRef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let call = input.parse::<ExprCall>()?;
+        let err = || {
+            Error::new(
+                call.span(),
+                "Expected a field reference in the form `Type::field(value)`",
+            )
+        };
+        let Expr::Path(func) = &*call.func else {
+            return Err(err());
+        };
+        if func.qself.is_some() || func.path.segments.len() < 2 || call.args.len() != 1 {
+            return Err(err());
+        }
+        let mut ty = func.path.clone();
+        let field = ty.segments.pop().unwrap().into_value();
+        if !field.arguments.is_empty() {
+            return Err(err());
+        }
+        ty.segments.pop_punct();
+        Ok(FieldRef {
+            ty,
+            field_name: field.ident.to_string(),
+            value: call.args[0].clone(),
+        })
+    }
+}
+
+struct CopyBits {
+    src: FieldRef,
+    dst: FieldRef,
+}
+
+impl Parse for CopyBits {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let src = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let dst = input.parse()?;
+        Ok(CopyBits { src, dst })
+    }
+}
+
+fn field_info(field: &FieldRef) -> proc_macro2::TokenStream {
+    let FieldRef { ty, field_name, .. } = field;
+    let err = format!(
+        "`{}` has no non-strided field named `{}`",
+        quote! { #ty },
+        field_name,
+    );
+    quote! {
+        match ::proc_bitfield::meta::field_by_name(<#ty>::FIELDS, #field_name) {
+            ::core::option::Option::Some(field) if field.stride == 1 => field,
+            _ => ::core::panic!(#err),
+        }
+    }
+}
+
+pub fn copy_bits(input: TokenStream) -> TokenStream {
+    let CopyBits { src, dst } = syn::parse_macro_input!(input);
+    let src_info = field_info(&src);
+    let dst_info = field_info(&dst);
+    let src_value = &src.value;
+    let dst_value = &dst.value;
+    quote! {{
+        const SRC: &::proc_bitfield::meta::FieldInfo = #src_info;
+        const DST: &::proc_bitfield::meta::FieldInfo = #dst_info;
+        ::proc_bitfield::ops::copy_bits::<{ SRC.start }, { SRC.end }, { DST.start }, { DST.end }>(
+            ::proc_bitfield::Bitfield::storage(#src_value),
+            ::proc_bitfield::Bitfield::storage_mut(#dst_value),
+        )
+    }}
+    .into()
+}
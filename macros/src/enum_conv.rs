@@ -1,70 +1,182 @@
-use crate::utils::for_all_int_types;
-use proc_macro::{Span, TokenStream};
-use quote::quote;
+use crate::utils::{for_all_int_types, Casing};
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, LitInt, UnOp,
-    Variant,
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Expr, ExprLit, ExprUnary,
+    Fields, Lit, LitInt, LitStr, UnOp, Variant,
 };
 
+/// Parses an integer literal, negated if `negative` is set, as an `i128`.
+fn parse_int_lit(lit_int: &LitInt, negative: bool, kind: &str) -> syn::Result<i128> {
+    let magnitude = lit_int.base10_parse::<u128>()?;
+    if negative {
+        0_i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            lit_int,
+            format!("{kind} must be in the range of i128 to be converted"),
+        )
+    })
+}
+
+/// Parses the enum's discriminants, returning them along with unsuffixed literals with the same
+/// value, which can be used as patterns and expressions for any integer type that contains them.
 fn parse_discrs<'a>(
     variants: impl Iterator<Item = &'a Variant> + 'a,
-) -> impl Iterator<Item = (&'a Variant, Expr, isize)> {
-    let mut next_discr_int = 0_isize;
-    variants.map(move |variant| {
-        if let Some((_, discr)) = &variant.discriminant {
-            if let Some(discr_int) = match discr {
-                Expr::Lit(ExprLit {
-                    lit: Lit::Int(lit_int),
-                    ..
-                }) => Some(
-                    lit_int
-                        .base10_parse::<isize>()
-                        .expect("Couldn't parse discriminant"),
-                ),
-                Expr::Unary(ExprUnary {
-                    op: UnOp::Neg(_),
-                    expr,
-                    ..
-                }) => {
-                    if let Expr::Lit(ExprLit {
+) -> syn::Result<Vec<(&'a Variant, proc_macro2::Literal, i128)>> {
+    let mut next_discr_int = Some(0_i128);
+    variants
+        .map(move |variant| {
+            let discr_int = if let Some((_, discr)) = &variant.discriminant {
+                match discr {
+                    Expr::Lit(ExprLit {
                         lit: Lit::Int(lit_int),
                         ..
-                    }) = &**expr
-                    {
-                        Some(
-                            -lit_int
-                                .base10_parse::<isize>()
-                                .expect("Couldn't parse discriminant"),
-                        )
-                    } else {
-                        None
+                    }) => parse_int_lit(lit_int, false, "discriminants")?,
+                    Expr::Unary(ExprUnary {
+                        op: UnOp::Neg(_),
+                        expr,
+                        ..
+                    }) => {
+                        if let Expr::Lit(ExprLit {
+                            lit: Lit::Int(lit_int),
+                            ..
+                        }) = &**expr
+                        {
+                            parse_int_lit(lit_int, true, "discriminants")?
+                        } else {
+                            return Err(syn::Error::new_spanned(
+                                discr,
+                                "Non-literal discriminants are unsupported",
+                            ));
+                        }
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            discr,
+                            "Non-literal discriminants are unsupported",
+                        ))
                     }
                 }
-                _ => None,
-            } {
-                next_discr_int = discr_int + 1;
-                (variant, discr.clone(), discr_int)
             } else {
-                unimplemented!("Non-literal discriminants are unsupported");
-            }
-        } else {
-            let discr_int = next_discr_int;
-            next_discr_int += 1;
-            (
+                next_discr_int.ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        variant,
+                        "implicit discriminant overflows the range of i128",
+                    )
+                })?
+            };
+            next_discr_int = discr_int.checked_add(1);
+            Ok((
                 variant,
-                Expr::Lit(ExprLit {
-                    lit: Lit::Int(LitInt::new(
-                        &format!("{}", discr_int),
-                        Span::call_site().into(),
-                    )),
-                    attrs: Vec::new(),
-                }),
+                proc_macro2::Literal::i128_unsuffixed(discr_int),
                 discr_int,
-            )
+            ))
+        })
+        .collect()
+}
+
+/// A bit pattern matching a family of raw values, with don't-care bits cleared in its mask.
+struct Pattern {
+    mask: u128,
+    value: u128,
+    width: usize,
+    span: proc_macro2::Span,
+}
+
+/// Parses a pattern of `0`, `1` and `x` (don't-care) digits, starting from the most significant
+/// bit; underscores are ignored.
+fn parse_pattern(lit: &LitStr) -> syn::Result<Pattern> {
+    let (mut mask, mut value, mut width) = (0_u128, 0_u128, 0);
+    for digit in lit.value().chars().filter(|&digit| digit != '_') {
+        if width == 128 {
+            return Err(syn::Error::new(
+                lit.span(),
+                "patterns can be at most 128 bits wide",
+            ));
         }
+        let (mask_bit, value_bit) = match digit {
+            '0' => (1, 0),
+            '1' => (1, 1),
+            'x' | 'X' => (0, 0),
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "patterns can only contain `0`, `1`, `x` and `_`",
+                ))
+            }
+        };
+        mask = mask << 1 | mask_bit;
+        value = value << 1 | value_bit;
+        width += 1;
+    }
+    if width == 0 {
+        return Err(syn::Error::new(lit.span(), "patterns can't be empty"));
+    }
+    Ok(Pattern {
+        mask,
+        value,
+        width,
+        span: lit.span(),
     })
 }
 
+/// The options specified in a variant's `#[conv_raw(...)]` attributes.
+struct VariantOptions {
+    /// The additional raw values the variant is converted from, along with their spans.
+    aliases: Vec<(i128, proc_macro2::Span)>,
+    pattern: Option<Pattern>,
+}
+
+/// Parses the `#[conv_raw(alias = ..., pattern = "...")]` attributes of a variant.
+fn parse_variant_options(variant: &Variant) -> syn::Result<VariantOptions> {
+    let mut aliases = Vec::new();
+    let mut pattern = None;
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("conv_raw") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("alias") {
+                let value = meta.value()?.parse::<Expr>()?;
+                let (negative, lit) = match &value {
+                    Expr::Unary(ExprUnary {
+                        op: UnOp::Neg(_),
+                        expr,
+                        ..
+                    }) => (true, &**expr),
+                    expr => (false, expr),
+                };
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit_int),
+                    ..
+                }) = lit
+                else {
+                    return Err(syn::Error::new_spanned(
+                        &value,
+                        "aliases must be integer literals",
+                    ));
+                };
+                let alias = parse_int_lit(lit_int, negative, "aliases")?;
+                aliases.push((alias, value.span()));
+                Ok(())
+            } else if meta.path.is_ident("pattern") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                if pattern.replace(parse_pattern(&lit)?).is_some() {
+                    return Err(meta.error("duplicate pattern option"));
+                }
+                Ok(())
+            } else {
+                Err(meta.error("unknown conv_raw variant option"))
+            }
+        })?;
+    }
+    Ok(VariantOptions { aliases, pattern })
+}
+
 fn signed_bounds(discr_bits: u8) -> (i128, i128) {
     (
         -1_i128 << (discr_bits - 1),
@@ -76,52 +188,187 @@ fn unsigned_bound(discr_bits: u8) -> u128 {
     (1_u128 << (discr_bits - 1) << 1).wrapping_sub(1)
 }
 
+/// The maximum number of entries in the lookup tables generated with `#[conv_raw(lut)]`.
+const MAX_LUT_LEN: u128 = 4096;
+
+struct Options {
+    lut: bool,
+}
+
+fn parse_options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut options = Options { lut: false };
+    for attr in attrs {
+        if !attr.path().is_ident("conv_raw") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("lut") {
+                if options.lut {
+                    return Err(meta.error("duplicate lut option"));
+                }
+                options.lut = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown conv_raw option"))
+            }
+        })?;
+    }
+    Ok(options)
+}
+
 pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
     let type_name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let options = match parse_options(&input.attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let is_non_exhaustive = input
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"));
 
     match &input.data {
         Data::Enum(data) => {
             if data.variants.is_empty() {
-                unimplemented!("#[derive(ConvRaw)] requires a populated enum");
+                return syn::Error::new_spanned(
+                    type_name,
+                    "#[derive(ConvRaw)] requires a populated enum",
+                )
+                .to_compile_error()
+                .into();
             }
 
-            if data
+            if let Some(variant) = data
                 .variants
                 .iter()
-                .any(|variant| !matches!(variant.fields, Fields::Unit))
+                .find(|variant| !matches!(variant.fields, Fields::Unit))
             {
-                unimplemented!("#[derive(ConvRaw)] requires a fieldless enum");
+                return syn::Error::new_spanned(
+                    &variant.fields,
+                    "#[derive(ConvRaw)] requires a fieldless enum",
+                )
+                .to_compile_error()
+                .into();
             }
 
-            let discr_data = parse_discrs(data.variants.iter()).collect::<Vec<_>>();
-            let (min_discr, max_discr) =
-                discr_data.iter().fold((0, 0), |(min, max), (_, _, discr)| {
+            let discr_data = match parse_discrs(data.variants.iter()) {
+                Ok(discr_data) => discr_data,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            let (min_discr, max_discr) = discr_data
+                .iter()
+                .fold((i128::MAX, i128::MIN), |(min, max), (_, _, discr)| {
                     (min.min(*discr), max.max(*discr))
                 });
 
+            // Raw values are converted to variants from both their discriminants and their
+            // aliases, while variants are only converted back to their discriminants
+            let mut raw_data = discr_data.clone();
+            let mut patterns = Vec::new();
+            for variant in &data.variants {
+                let VariantOptions { aliases, pattern } = match parse_variant_options(variant) {
+                    Ok(options) => options,
+                    Err(err) => return err.to_compile_error().into(),
+                };
+                patterns.extend(pattern.map(|pattern| (variant, pattern)));
+                for (alias, span) in aliases {
+                    if raw_data.iter().any(|(_, _, raw)| *raw == alias) {
+                        return syn::Error::new(
+                            span,
+                            format!("the raw value {alias} already corresponds to a variant"),
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                    raw_data.push((variant, proc_macro2::Literal::i128_unsuffixed(alias), alias));
+                }
+            }
+            let (min_raw, max_raw) = raw_data
+                .iter()
+                .fold((i128::MAX, i128::MIN), |(min, max), (_, _, raw)| {
+                    (min.min(*raw), max.max(*raw))
+                });
+
+            if options.lut && max_raw.abs_diff(min_raw) >= MAX_LUT_LEN {
+                return syn::Error::new_spanned(
+                    type_name,
+                    format!(
+                        "#[conv_raw(lut)] requires the discriminants and aliases to span at most \
+                         {} values",
+                        MAX_LUT_LEN
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+
             let mut impls = Vec::new();
 
             // Implement TryFrom/UnsafeFrom<u/i8..=u/i128>
             for_all_int_types(|discr_bits, signed, discr_ty| {
-                let from_raw_variants = discr_data
+                let fitting_discrs = raw_data
                     .iter()
                     .filter(|(_, _, discr)| {
                         if signed {
                             let (min, max) = signed_bounds(discr_bits);
-                            *discr as i128 >= min && *discr as i128 <= max
+                            *discr >= min && *discr <= max
                         } else {
                             *discr >= 0 && *discr as u128 <= unsigned_bound(discr_bits)
                         }
                     })
-                    .map(|(variant, discr_lit, _)| {
+                    .collect::<Vec<_>>();
+                let from_raw_variants = fitting_discrs.iter().map(|(variant, discr_lit, _)| {
+                    let variant_name = &variant.ident;
+                    quote! {
+                        #discr_lit => #type_name::#variant_name,
+                    }
+                });
+                let from_raw_variants_ = from_raw_variants.clone();
+                let try_from_body = if fitting_discrs.is_empty() {
+                    quote! {
+                        let _ = other;
+                        Err(())
+                    }
+                } else if options.lut {
+                    let min = fitting_discrs
+                        .iter()
+                        .map(|(_, _, discr)| *discr)
+                        .min()
+                        .unwrap();
+                    let max = fitting_discrs
+                        .iter()
+                        .map(|(_, _, discr)| *discr)
+                        .max()
+                        .unwrap();
+                    let len = (max - min + 1) as usize;
+                    let mut entries = vec![quote! { ::core::option::Option::None }; len];
+                    for (variant, _, discr) in &fitting_discrs {
                         let variant_name = &variant.ident;
-                        quote! {
-                            #discr_lit => #type_name::#variant_name,
+                        entries[(*discr - min) as usize] = quote! {
+                            ::core::option::Option::Some(#type_name::#variant_name)
+                        };
+                    }
+                    let min_lit = proc_macro2::Literal::i128_unsuffixed(min);
+                    let max_lit = proc_macro2::Literal::i128_unsuffixed(max);
+                    quote! {
+                        const LUT: [::core::option::Option<#type_name>; #len] = [
+                            #(#entries),*
+                        ];
+                        if !(#min_lit..=#max_lit).contains(&other) {
+                            return Err(());
                         }
-                    });
-                let from_raw_variants_ = from_raw_variants.clone();
+                        LUT[(other as i128 - #min_lit) as usize].ok_or(())
+                    }
+                } else {
+                    quote! {
+                        Ok(match other {
+                            #(#from_raw_variants)*
+                            _ => return Err(()),
+                        })
+                    }
+                };
                 let from_raw_impls = quote! {
                     impl #impl_generics ::core::convert::TryFrom<#discr_ty>
                         for #type_name #ty_generics
@@ -130,10 +377,7 @@ pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
                         type Error = ();
 
                         fn try_from(other: #discr_ty) -> Result<#type_name #ty_generics, ()> {
-                            Ok(match other {
-                                #(#from_raw_variants)*
-                                _ => return Err(()),
-                            })
+                            #try_from_body
                         }
                     }
 
@@ -153,11 +397,49 @@ pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
                 impls.push(from_raw_impls);
             });
 
+            // Implement TryInto<u/i8..=u/i128> for non-exhaustive enums, as variants added in the
+            // future might not fit in the integer types that can contain all current discriminants
+            if is_non_exhaustive {
+                for_all_int_types(|discr_bits, signed, discr_ty| {
+                    let into_raw_variants = discr_data.iter().map(|(variant, discr_lit, discr)| {
+                        let variant_name = &variant.ident;
+                        let fits = if signed {
+                            let (min, max) = signed_bounds(discr_bits);
+                            *discr >= min && *discr <= max
+                        } else {
+                            *discr >= 0 && *discr as u128 <= unsigned_bound(discr_bits)
+                        };
+                        if fits {
+                            quote! { #type_name::#variant_name => Ok(#discr_lit), }
+                        } else {
+                            quote! { #type_name::#variant_name => Err(()), }
+                        }
+                    });
+                    impls.push(quote! {
+                        impl #impl_generics ::core::convert::TryFrom<#type_name #ty_generics>
+                            for #discr_ty
+                            #where_clause
+                        {
+                            type Error = ();
+
+                            fn try_from(other: #type_name #ty_generics) -> Result<#discr_ty, ()> {
+                                match other {
+                                    #(#into_raw_variants)*
+                                }
+                            }
+                        }
+                    });
+                });
+            }
+
             // Implement Into<u/i<min_discr_bits>..=u/i128>
             for_all_int_types(|discr_bits, signed, discr_ty| {
+                if is_non_exhaustive {
+                    return;
+                }
                 let fits_discr_range = if signed {
                     let (min, max) = signed_bounds(discr_bits);
-                    min_discr as i128 >= min && max_discr as i128 <= max
+                    min_discr >= min && max_discr <= max
                 } else {
                     min_discr >= 0 && max_discr as u128 <= unsigned_bound(discr_bits)
                 };
@@ -191,6 +473,7 @@ pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
             if let [(v_false, _, 0), (v_true, _, 1)] | [(v_true, _, 1), (v_false, _, 0)] =
                 discr_data.as_slice()
             {
+                let (v_false, v_true) = (&v_false.ident, &v_true.ident);
                 let impl_from_bool = quote! {
                     impl #impl_generics ::core::convert::From<bool> for #type_name #ty_generics
                         #where_clause
@@ -218,14 +501,139 @@ pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
                     }
                 };
 
+                let impl_into_bool = if is_non_exhaustive {
+                    quote! {
+                        impl #impl_generics ::core::convert::TryFrom<#type_name #ty_generics>
+                            for bool
+                            #where_clause
+                        {
+                            type Error = ();
+
+                            fn try_from(other: #type_name #ty_generics) -> Result<bool, ()> {
+                                match other {
+                                    #type_name::#v_false => Ok(false),
+                                    #type_name::#v_true => Ok(true),
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    impl_into_bool
+                };
+
                 impls.push(impl_into_bool);
                 impls.push(impl_from_bool);
             }
 
+            // Generate the pattern constants and matching function
+            if let Some((_, first)) = patterns.first() {
+                let width = first.width;
+                if let Some((_, pattern)) = patterns.iter().find(|(_, p)| p.width != width) {
+                    return syn::Error::new(
+                        pattern.span,
+                        "all patterns of an enum must have the same width",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                let raw_ty = format_ident!("u{}", width.next_power_of_two().max(8));
+                let vis = &input.vis;
+                let (pattern_consts, pattern_idents): (Vec<_>, Vec<_>) = patterns
+                    .iter()
+                    .map(|(variant, Pattern { mask, value, .. })| {
+                        let variant_name = &variant.ident;
+                        let const_ident = format_ident!(
+                            "{}_PATTERN",
+                            Casing::ScreamingSnake.apply(&variant_name.to_string())
+                        );
+                        let doc = format!(
+                            "The mask and value of the raw values matched by [`{variant_name}`]\
+                             (Self::{variant_name})'s pattern."
+                        );
+                        let mask = proc_macro2::Literal::u128_unsuffixed(*mask);
+                        let value = proc_macro2::Literal::u128_unsuffixed(*value);
+                        (
+                            quote! {
+                                #[doc = #doc]
+                                #vis const #const_ident: (#raw_ty, #raw_ty) = (#mask, #value);
+                            },
+                            (const_ident, variant_name),
+                        )
+                    })
+                    .unzip();
+                let pattern_checks = pattern_idents.iter().map(|(const_ident, variant_name)| {
+                    quote! {
+                        if raw & Self::#const_ident.0 == Self::#const_ident.1 {
+                            return ::core::option::Option::Some(#type_name::#variant_name);
+                        }
+                    }
+                });
+                impls.push(quote! {
+                    impl #impl_generics #type_name #ty_generics #where_clause {
+                        #(#pattern_consts)*
+
+                        /// Returns the first variant in declaration order whose pattern matches the
+                        /// given raw value, if any.
+                        #[inline]
+                        #vis const fn match_pattern(raw: #raw_ty) -> ::core::option::Option<Self> {
+                            #(#pattern_checks)*
+                            ::core::option::Option::None
+                        }
+                    }
+                });
+            }
+
+            // Implement EnumInfo
+            let variant_infos = discr_data.iter().map(|(variant, discr, _)| {
+                let variant_name = &variant.ident;
+                quote! {
+                    ::proc_bitfield::meta::VariantInfo {
+                        name: ::core::stringify!(#variant_name),
+                        value: #discr,
+                    }
+                }
+            });
+            impls.push(quote! {
+                impl #impl_generics ::proc_bitfield::meta::EnumInfo for #type_name #ty_generics
+                    #where_clause
+                {
+                    const NAME: &'static str = ::core::stringify!(#type_name);
+                    const VARIANTS: &'static [::proc_bitfield::meta::VariantInfo] =
+                        &[#(#variant_infos),*];
+                }
+            });
+
+            // Implement ToFieldValue
+            #[cfg(feature = "alloc")]
+            {
+                let variant_names = discr_data.iter().map(|(variant, _, _)| &variant.ident);
+                let variant_names_ = variant_names.clone();
+                impls.push(quote! {
+                    impl #impl_generics ::proc_bitfield::value::ToFieldValue
+                        for #type_name #ty_generics
+                        #where_clause
+                    {
+                        fn to_field_value(&self) -> ::proc_bitfield::value::FieldValue {
+                            ::proc_bitfield::value::FieldValue::Enum(match self {
+                                #(#type_name::#variant_names => ::core::stringify!(#variant_names_),)*
+                            })
+                        }
+                    }
+                });
+            }
+
             quote! { #(#impls)* }.into()
         }
 
-        Data::Struct(_) => unimplemented!("Can't derive ConvRaw on structs"),
-        Data::Union(_) => unimplemented!("Can't derive ConvRaw on unions"),
+        Data::Struct(data) => {
+            syn::Error::new_spanned(data.struct_token, "Can't derive ConvRaw on structs")
+                .to_compile_error()
+                .into()
+        }
+        Data::Union(data) => {
+            syn::Error::new_spanned(data.union_token, "Can't derive ConvRaw on unions")
+                .to_compile_error()
+                .into()
+        }
     }
 }
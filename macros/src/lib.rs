@@ -1,6 +1,10 @@
 mod bitfield;
 mod bits;
+mod copy_bits;
 mod enum_conv;
+mod match_layout;
+mod stats;
+mod template;
 #[cfg(feature = "nightly")]
 mod unwrap_bits;
 mod utils;
@@ -27,7 +31,22 @@ pub fn set_bits(input: TokenStream) -> TokenStream {
     bits::set_bits(input)
 }
 
-#[proc_macro_derive(ConvRaw)]
+#[proc_macro]
+pub fn copy_bits(input: TokenStream) -> TokenStream {
+    copy_bits::copy_bits(input)
+}
+
+#[proc_macro]
+pub fn bitfield_template(input: TokenStream) -> TokenStream {
+    template::bitfield_template(input)
+}
+
+#[proc_macro]
+pub fn match_layout(input: TokenStream) -> TokenStream {
+    match_layout::match_layout(input)
+}
+
+#[proc_macro_derive(ConvRaw, attributes(conv_raw))]
 pub fn derive_conv_raw(item: TokenStream) -> TokenStream {
     enum_conv::derive_conv_raw(item)
 }
@@ -0,0 +1,197 @@
+use crate::bits::{asserts, Bits, BitsSpan};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    token, Attribute, Ident, Pat, Token, Type, Visibility,
+};
+
+struct Variant {
+    attrs: Vec<Attribute>,
+    pat: Pat,
+    ident: Ident,
+    ty: Type,
+}
+
+impl Parse for Variant {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        input.parse::<Token![=>]>()?;
+        let ident = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let ty = content.parse()?;
+        Ok(Variant {
+            attrs,
+            pat,
+            ident,
+            ty,
+        })
+    }
+}
+
+struct Layout {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    storage_ty: Type,
+    tag_ident: Ident,
+    tag_ty: Type,
+    tag_bits: Bits,
+    variants: Punctuated<Variant, Token![,]>,
+}
+
+impl Parse for Layout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let ident = input.parse()?;
+        let storage_content;
+        parenthesized!(storage_content in input);
+        let storage_ty = storage_content.parse()?;
+        input.parse::<Token![match]>()?;
+        let tag_ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let tag_ty = input.parse()?;
+        input.parse::<Token![@]>()?;
+        // Bit positions can be arbitrary expressions, so delimit the tag's range at the variants'
+        // braces before parsing it
+        let mut tag_bits_tokens = proc_macro2::TokenStream::new();
+        while !input.is_empty() && !input.peek(token::Brace) {
+            tag_bits_tokens.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        let tag_bits = syn::parse2(tag_bits_tokens)?;
+        let variants_content;
+        braced!(variants_content in input);
+        let variants = variants_content.parse_terminated(Variant::parse, Token![,])?;
+        if !input.is_empty() {
+            return Err(input.error("unexpected extra tokens"));
+        }
+        Ok(Layout {
+            attrs,
+            vis,
+            ident,
+            storage_ty,
+            tag_ident,
+            tag_ty,
+            tag_bits,
+            variants,
+        })
+    }
+}
+
+pub fn match_layout(input: TokenStream) -> TokenStream {
+    let Layout {
+        attrs,
+        vis,
+        ident,
+        storage_ty,
+        tag_ident,
+        tag_ty,
+        tag_bits,
+        variants,
+    } = syn::parse_macro_input!(input);
+
+    let storage_ty_bits = quote! { {::core::mem::size_of::<#storage_ty>() << 3} };
+    let tag_ty_bits = quote! { {::core::mem::size_of::<#tag_ty>() << 3} };
+    let tag_span = match tag_bits.into_span(None, None) {
+        Ok(tag_span) => tag_span,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let tag_asserts = asserts(&tag_span, true, &storage_ty_bits, true, &tag_ty_bits);
+    let read_tag = match &tag_span {
+        BitsSpan::Single(bit) => quote! {
+            <#storage_ty as ::proc_bitfield::Bit>::bit::<#bit>(raw)
+        },
+        BitsSpan::Range { start, end } => quote! {
+            <#storage_ty as ::proc_bitfield::Bits<#tag_ty>>::bits::<#start, #end>(raw)
+        },
+        BitsSpan::Strided { start, step, count } => quote! {
+            <#storage_ty as ::proc_bitfield::StridedBits<#tag_ty>>
+                ::strided_bits::<#start, #step, #count>(raw)
+        },
+        BitsSpan::Full => quote! {
+            <#storage_ty as ::proc_bitfield::Bits<#tag_ty>>::bits::<0, #storage_ty_bits>(raw)
+        },
+    };
+
+    let variant_attrs = variants.iter().map(|variant| &variant.attrs);
+    let variant_idents = variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let variant_tys = variants
+        .iter()
+        .map(|variant| &variant.ty)
+        .collect::<Vec<_>>();
+    let variant_pats = variants.iter().map(|variant| &variant.pat);
+
+    quote! {
+        #(#attrs)*
+        #vis enum #ident {
+            #(
+                #(#variant_attrs)*
+                #variant_idents(#variant_tys),
+            )*
+        }
+
+        impl #ident {
+            /// Decodes a raw value into the layout selected by its tag, returning `None` if it
+            /// doesn't match any of the variants.
+            #[allow(clippy::identity_op)]
+            #vis fn decode(raw: #storage_ty) -> ::core::option::Option<Self> {
+                #tag_asserts
+                let tag = {
+                    let raw = &raw;
+                    #read_tag
+                };
+                #[allow(unreachable_patterns)]
+                match tag {
+                    #(
+                        #variant_pats => ::core::option::Option::Some(
+                            Self::#variant_idents(
+                                <#variant_tys as ::proc_bitfield::Bitfield>::from_storage(raw),
+                            ),
+                        ),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            /// Returns the tag selecting the current layout.
+            #[inline]
+            #[allow(clippy::identity_op)]
+            #vis fn #tag_ident(&self) -> #tag_ty {
+                let raw = self.storage();
+                #read_tag
+            }
+
+            /// Returns a reference to the storage of the current layout.
+            #[inline]
+            #vis fn storage(&self) -> &#storage_ty {
+                match self {
+                    #(
+                        Self::#variant_idents(value) => ::proc_bitfield::Bitfield::storage(value),
+                    )*
+                }
+            }
+
+            /// Returns the storage of the current layout, including its tag.
+            #[inline]
+            #vis fn into_storage(self) -> #storage_ty {
+                match self {
+                    #(
+                        Self::#variant_idents(value) => {
+                            ::proc_bitfield::Bitfield::into_storage(value)
+                        }
+                    )*
+                }
+            }
+        }
+    }
+    .into()
+}
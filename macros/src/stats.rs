@@ -0,0 +1,67 @@
+//! Opt-in expansion statistics for `bitfield!`, printed to stderr at compile time when the
+//! `PROC_BITFIELD_STATS` environment variable is set (to anything other than an empty string or
+//! `0`), to find which declarations dominate a crate's compile times.
+
+use proc_macro2::{TokenStream, TokenTree};
+use std::{collections::HashMap, sync::Mutex};
+use syn::Ident;
+
+/// Running totals of structs, fields, functions and tokens, for a single crate.
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    structs: usize,
+    fields: usize,
+    fns: usize,
+    tokens: usize,
+}
+
+/// The running totals for each crate expanding `bitfield!` in the current process, as the
+/// proc-macro library can stay loaded across crates (i.e. in rust-analyzer).
+static TOTALS: Mutex<Option<HashMap<String, Totals>>> = Mutex::new(None);
+
+pub fn enabled() -> bool {
+    std::env::var_os("PROC_BITFIELD_STATS").is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Counts the token trees in `tokens` (recursing into groups), and the `fn` items among them.
+fn count(tokens: TokenStream, fns: &mut usize, total: &mut usize) {
+    for tree in tokens {
+        *total += 1;
+        match tree {
+            TokenTree::Group(group) => count(group.stream(), fns, total),
+            TokenTree::Ident(ident) if ident == "fn" => *fns += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Prints the statistics for a single `bitfield!` expansion, followed by the running totals for
+/// the current crate.
+pub fn report(ident: &Ident, fields: usize, output: &TokenStream) {
+    let (mut fns, mut tokens) = (0, 0);
+    count(output.clone(), &mut fns, &mut tokens);
+
+    let krate = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    let mut all_totals = TOTALS.lock().unwrap_or_else(|err| err.into_inner());
+    let totals = all_totals
+        .get_or_insert_with(HashMap::new)
+        .entry(krate.clone())
+        .or_default();
+    totals.structs += 1;
+    totals.fields += fields;
+    totals.fns += fns;
+    totals.tokens += tokens;
+    let Totals {
+        structs: total_structs,
+        fields: total_fields,
+        fns: total_fns,
+        tokens: total_tokens,
+    } = *totals;
+    drop(all_totals);
+
+    eprintln!(
+        "proc-bitfield stats [{krate}]: `{ident}`: {fields} fields, {fns} functions, {tokens} \
+         tokens (totals: {total_structs} structs, {total_fields} fields, {total_fns} functions, \
+         {total_tokens} tokens)"
+    );
+}
@@ -0,0 +1,165 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Group, Punct, Spacing, Span, TokenTree};
+use quote::quote;
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    Attribute, Error, Ident, Token,
+};
+
+mod kw {
+    syn::custom_keyword!(template);
+}
+
+struct Template {
+    attrs: Vec<Attribute>,
+    macro_ident: Ident,
+    params: Punctuated<Ident, Token![,]>,
+    body: proc_macro2::TokenStream,
+}
+
+impl Parse for Template {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        input.parse::<kw::template>()?;
+        let macro_ident = input.parse()?;
+        let params_content;
+        parenthesized!(params_content in input);
+        let params = params_content.parse_terminated(Ident::parse, Token![,])?;
+        let body_content;
+        braced!(body_content in input);
+        let body = body_content.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("unexpected extra tokens"));
+        }
+        Ok(Template {
+            attrs,
+            macro_ident,
+            params,
+            body,
+        })
+    }
+}
+
+fn dollar() -> TokenTree {
+    Punct::new('$', Spacing::Alone).into()
+}
+
+/// Replaces all occurrences of the template's parameters with parenthesized `macro_rules!`
+/// metavariables of the same name, recursing into groups.
+fn substitute_params(
+    tokens: proc_macro2::TokenStream,
+    params: &Punctuated<Ident, Token![,]>,
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if params.iter().any(|param| *param == ident) => {
+                let mut group = Group::new(
+                    Delimiter::Parenthesis,
+                    [dollar(), TokenTree::Ident(ident.clone())]
+                        .into_iter()
+                        .collect(),
+                );
+                group.set_span(ident.span());
+                TokenTree::Group(group)
+            }
+            TokenTree::Group(group) => {
+                let mut new_group =
+                    Group::new(group.delimiter(), substitute_params(group.stream(), params));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            tt => tt,
+        })
+        .collect()
+}
+
+/// Replaces the `_` placeholder in `struct _` with the `$name` metavariable.
+fn substitute_name(body: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    let mut tokens = body.into_iter().collect::<Vec<_>>();
+    let struct_pos = tokens
+        .iter()
+        .position(|tt| matches!(tt, TokenTree::Ident(ident) if ident == "struct"))
+        .ok_or_else(|| Error::new(Span::call_site(), "expected a bitfield struct declaration"))?;
+    match tokens.get(struct_pos + 1) {
+        Some(TokenTree::Ident(ident)) if ident == "_" => {}
+        Some(tt) => return Err(Error::new(tt.span(), "expected `_` as the struct's name")),
+        None => return Err(Error::new(tokens[struct_pos].span(), "expected `_`")),
+    }
+    tokens.splice(
+        struct_pos + 1..struct_pos + 2,
+        [dollar(), Ident::new("name", Span::call_site()).into()],
+    );
+    Ok(tokens.into_iter().collect())
+}
+
+/// Returns the storage declaration and body of a template's bitfield struct, to be spliced into
+/// other bitfields flattening it.
+fn storage_and_fields(body: &proc_macro2::TokenStream) -> Result<(Group, Group)> {
+    let mut groups = body.clone().into_iter().filter_map(|tt| match tt {
+        TokenTree::Group(group) => Some(group),
+        _ => None,
+    });
+    let storage = groups
+        .find(|group| group.delimiter() == Delimiter::Parenthesis)
+        .ok_or_else(|| Error::new(Span::call_site(), "expected the struct's storage"))?;
+    let fields = groups
+        .filter(|group| group.delimiter() == Delimiter::Brace)
+        .last()
+        .ok_or_else(|| Error::new(Span::call_site(), "expected the struct's fields"))?;
+    Ok((storage, fields))
+}
+
+pub fn bitfield_template(input: TokenStream) -> TokenStream {
+    let Template {
+        attrs,
+        macro_ident,
+        params,
+        body,
+    } = syn::parse_macro_input!(input);
+
+    let (body, storage, fields) = match substitute_name(substitute_params(body, &params))
+        .and_then(|body| Ok((storage_and_fields(&body)?, body)))
+    {
+        Ok(((storage, fields), body)) => (body, storage, fields),
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let fields = fields.stream();
+    let params = params.iter();
+    let param_metavars = params.clone();
+    let flatten_params = params.clone();
+    let flatten_param_metavars = params.clone();
+
+    quote! {
+        #(#attrs)*
+        macro_rules! #macro_ident {
+            (
+                @flatten [$($header:tt)*] { $($before:tt)* } { $($after:tt)* } ($offset:expr)
+                #(, #flatten_params = $#flatten_param_metavars:expr)*
+                $(,)?
+            ) => {
+                ::proc_bitfield::bitfield! {
+                    $($header)* {
+                        $($before)*
+                        flatten ($offset) #storage { #fields },
+                        $($after)*
+                    }
+                }
+            };
+            (
+                $(#[$meta:meta])*
+                $name:ident
+                #(, #params = $#param_metavars:expr)*
+                $(,)?
+            ) => {
+                ::proc_bitfield::bitfield! {
+                    $(#[$meta])*
+                    #body
+                }
+            };
+        }
+    }
+    .into()
+}
@@ -49,8 +49,7 @@ pub fn derive(item: TokenStream) -> TokenStream {
                     self,
                     value: #type_name #ty_generics
                 ) -> Self {
-                    let written_bits = END - START;
-                    let mask = ((1 as #int_ty) << (written_bits - 1) << 1).wrapping_sub(1) << START;
+                    let mask = ::proc_bitfield::ops::field_mask::<#int_ty>(START, END);
                     (self & !mask)
                         | (<#type_name #ty_generics as ::core::convert::Into::<#int_ty>>::into(
                             value,
@@ -3,7 +3,7 @@ use quote::quote;
 use syn::{
     parenthesized,
     parse::{ParseBuffer, ParseStream},
-    Ident, Result,
+    BinOp, Expr, GenericArgument, Generics, Ident, Lit, PathArguments, Result, Stmt, Type,
 };
 
 pub fn for_all_int_types(mut f: impl FnMut(u8, bool, Ident)) {
@@ -29,3 +29,381 @@ pub fn maybe_const_assert(is_const: bool) -> proc_macro2::TokenStream {
         quote! { ::core::assert! }
     }
 }
+
+/// Returns the width and underlying builtin type of the `arbitrary-int` unsigned integer type
+/// named by the given type's path (`arbitrary_int::u1` to `arbitrary_int::u127`, optionally with a
+/// leading `::`, excluding builtin widths).
+///
+/// Only fully qualified paths are recognized, so that enabling the feature anywhere in a
+/// dependency graph doesn't change the meaning of other crates' types with the same names.
+#[cfg(feature = "arbitrary-int")]
+pub fn arbitrary_uint(ty: &Type) -> Option<(u8, Type)> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => {
+            let mut segments = ty.path.segments.iter();
+            let (Some(krate), Some(segment), None) =
+                (segments.next(), segments.next(), segments.next())
+            else {
+                return None;
+            };
+            if krate.ident != "arbitrary_int"
+                || !krate.arguments.is_none()
+                || !segment.arguments.is_none()
+            {
+                return None;
+            }
+            let name = segment.ident.to_string();
+            let bits = name.strip_prefix('u')?.parse::<u8>().ok()?;
+            if name != format!("u{bits}")
+                || !(1..128).contains(&bits)
+                || [8, 16, 32, 64].contains(&bits)
+            {
+                return None;
+            }
+            let underlying = format_ident!("u{}", bits.next_power_of_two().max(8));
+            Some((bits, syn::parse_quote! { #underlying }))
+        }
+        Type::Paren(ty) => arbitrary_uint(&ty.elem),
+        Type::Group(ty) => arbitrary_uint(&ty.elem),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "arbitrary-int"))]
+pub fn arbitrary_uint(_ty: &Type) -> Option<(u8, Type)> {
+    None
+}
+
+/// Returns whether the given type is a builtin integer type or an array of them.
+pub fn is_int_storage(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.get_ident().is_some_and(|ident| {
+            [
+                "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+                "isize",
+            ]
+            .iter()
+            .any(|int_ty| ident == int_ty)
+        }),
+        Type::Array(ty) => is_int_storage(&ty.elem),
+        Type::Paren(ty) => is_int_storage(&ty.elem),
+        Type::Group(ty) => is_int_storage(&ty.elem),
+        _ => false,
+    }
+}
+
+/// Returns the size in bytes of a builtin fixed-width integer type or an array of them.
+fn int_storage_size(ty: &Type) -> Option<i128> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => {
+            let name = ty.path.get_ident()?.to_string();
+            let bits = name.strip_prefix(['u', 'i'])?.parse::<i128>().ok()?;
+            [8, 16, 32, 64, 128].contains(&bits).then_some(bits / 8)
+        }
+        Type::Array(ty) => {
+            int_storage_size(&ty.elem)?.checked_mul(LinearExpr::from_expr(&ty.len).value_i128()?)
+        }
+        Type::Paren(ty) => int_storage_size(&ty.elem),
+        Type::Group(ty) => int_storage_size(&ty.elem),
+        _ => None,
+    }
+}
+
+/// A constant integer expression (as generated for bit positions) in linear form: a constant
+/// plus a sum of opaque terms (i.e. user-defined constants) multiplied by constant coefficients.
+#[derive(Clone, PartialEq, Eq)]
+pub struct LinearExpr {
+    constant: i128,
+    terms: Vec<(String, i128)>,
+}
+
+impl LinearExpr {
+    fn constant(constant: i128) -> Self {
+        LinearExpr {
+            constant,
+            terms: Vec::new(),
+        }
+    }
+
+    fn opaque(expr: &Expr) -> Self {
+        LinearExpr {
+            constant: 0,
+            terms: vec![(tokens_to_doc_string(quote! { #expr }), 1)],
+        }
+    }
+
+    /// Parses and simplifies an expression, treating any subexpression that isn't made of
+    /// literals, arithmetic and bitwise operators and `size_of` calls on builtin integer types as
+    /// an opaque term.
+    pub fn parse(tokens: proc_macro2::TokenStream) -> Self {
+        match syn::parse2::<Expr>(tokens.clone()) {
+            Ok(expr) => Self::from_expr(&expr),
+            Err(_) => LinearExpr {
+                constant: 0,
+                terms: vec![(tokens_to_doc_string(tokens), 1)],
+            },
+        }
+    }
+
+    fn from_expr(expr: &Expr) -> Self {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Int(lit) => lit
+                    .base10_parse()
+                    .map_or_else(|_| Self::opaque(expr), Self::constant),
+                _ => Self::opaque(expr),
+            },
+            Expr::Paren(inner) => Self::from_expr(&inner.expr),
+            Expr::Group(inner) => Self::from_expr(&inner.expr),
+            Expr::Cast(inner) => Self::from_expr(&inner.expr),
+            Expr::Block(block) => match block.block.stmts.as_slice() {
+                [Stmt::Expr(inner, None)] => Self::from_expr(inner),
+                _ => Self::opaque(expr),
+            },
+            Expr::Binary(binary) => {
+                let left = Self::from_expr(&binary.left);
+                let right = Self::from_expr(&binary.right);
+                let result = match (binary.op, left.value_i128(), right.value_i128()) {
+                    (BinOp::Add(_), ..) => Some(left.add(right, 1)),
+                    (BinOp::Sub(_), ..) => Some(left.add(right, -1)),
+                    (BinOp::Mul(_), _, Some(factor)) => Some(left.scale(factor)),
+                    (BinOp::Mul(_), Some(factor), _) => Some(right.scale(factor)),
+                    (op, Some(left), Some(right)) => match op {
+                        BinOp::Div(_) => left.checked_div(right),
+                        BinOp::Rem(_) => left.checked_rem(right),
+                        BinOp::Shl(_) => u32::try_from(right)
+                            .ok()
+                            .and_then(|right| left.checked_shl(right)),
+                        BinOp::Shr(_) => u32::try_from(right)
+                            .ok()
+                            .and_then(|right| left.checked_shr(right)),
+                        BinOp::BitAnd(_) => Some(left & right),
+                        BinOp::BitOr(_) => Some(left | right),
+                        BinOp::BitXor(_) => Some(left ^ right),
+                        _ => None,
+                    }
+                    .map(Self::constant),
+                    _ => None,
+                };
+                result.unwrap_or_else(|| Self::opaque(expr))
+            }
+            Expr::Call(call) if call.args.is_empty() => {
+                let size = match &*call.func {
+                    Expr::Path(func) => func.path.segments.last().and_then(|segment| {
+                        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                            return None;
+                        };
+                        match args.args.iter().collect::<Vec<_>>().as_slice() {
+                            [GenericArgument::Type(ty)] if segment.ident == "size_of" => {
+                                int_storage_size(ty)
+                            }
+                            _ => None,
+                        }
+                    }),
+                    _ => None,
+                };
+                size.map_or_else(|| Self::opaque(expr), Self::constant)
+            }
+            _ => Self::opaque(expr),
+        }
+    }
+
+    fn add(mut self, other: LinearExpr, sign: i128) -> Self {
+        self.constant = self
+            .constant
+            .wrapping_add(sign.wrapping_mul(other.constant));
+        for (term, coeff) in other.terms {
+            match self
+                .terms
+                .iter_mut()
+                .find(|(other_term, _)| *other_term == term)
+            {
+                Some((_, other_coeff)) => *other_coeff = other_coeff.wrapping_add(sign * coeff),
+                None => self.terms.push((term, sign * coeff)),
+            }
+        }
+        self.terms.retain(|(_, coeff)| *coeff != 0);
+        self
+    }
+
+    fn scale(mut self, factor: i128) -> Self {
+        self.constant = self.constant.wrapping_mul(factor);
+        for (_, coeff) in &mut self.terms {
+            *coeff = coeff.wrapping_mul(factor);
+        }
+        self.terms.retain(|(_, coeff)| *coeff != 0);
+        self
+    }
+
+    fn value_i128(&self) -> Option<i128> {
+        self.terms.is_empty().then_some(self.constant)
+    }
+
+    /// Returns the expression's value, if it doesn't contain any opaque terms.
+    pub fn value(&self) -> Option<u128> {
+        self.value_i128()?.try_into().ok()
+    }
+}
+
+impl std::fmt::Display for LinearExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (term, coeff)) in self.terms.iter().enumerate() {
+            let sign = match (i, *coeff < 0) {
+                (0, false) => "",
+                (0, true) => "-",
+                (_, false) => " + ",
+                (_, true) => " - ",
+            };
+            match coeff.unsigned_abs() {
+                1 => write!(f, "{sign}{term}")?,
+                coeff => write!(f, "{sign}{coeff} * {term}")?,
+            }
+        }
+        match (self.terms.is_empty(), self.constant) {
+            (true, constant) => write!(f, "{constant}"),
+            (false, 0) => Ok(()),
+            (false, constant) if constant < 0 => write!(f, " - {}", constant.unsigned_abs()),
+            (false, constant) => write!(f, " + {constant}"),
+        }
+    }
+}
+
+/// Formats tokens as compact source text for documentation, without the spaces `quote` inserts
+/// around punctuation (i.e. `Option<NonZeroU8>` instead of `Option < NonZeroU8 >`).
+pub fn tokens_to_doc_string(tokens: proc_macro2::TokenStream) -> String {
+    let mut text = tokens.to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        text = text.replace(from, to);
+    }
+    text
+}
+
+/// Returns whether the given tokens mention any of the given type parameters, i.e. to check
+/// whether a type depends on them.
+pub fn mentions_type_params(tokens: proc_macro2::TokenStream, generics: &Generics) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => {
+            generics.type_params().any(|param| param.ident == ident)
+        }
+        proc_macro2::TokenTree::Group(group) => mentions_type_params(group.stream(), generics),
+        _ => false,
+    })
+}
+
+/// Splits an identifier into words at underscores and case changes, keeping acronyms together
+/// (i.e. `IRQEnable` is split into `IRQ` and `Enable`, and `rx_fifo` into `rx` and `fifo`).
+pub fn split_words(name: &str) -> Vec<&str> {
+    let chars = name.char_indices().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if start < pos {
+                words.push(&name[start..pos]);
+            }
+            start = pos + 1;
+        } else if c.is_uppercase() && start < pos {
+            let prev = chars[i - 1].1;
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|(_, c)| c.is_lowercase());
+            if !prev.is_uppercase() || next_is_lowercase {
+                words.push(&name[start..pos]);
+                start = pos;
+            }
+        }
+    }
+    if start < name.len() {
+        words.push(&name[start..]);
+    }
+    words
+}
+
+/// Converts a type name in upper camel case to snake case, i.e. `UartCtrl` to `uart_ctrl`, keeping
+/// acronyms together (`DMACtrl` to `dma_ctrl`).
+pub fn to_snake_case(name: &str) -> String {
+    Casing::Snake.apply(name)
+}
+
+/// A casing convention for generated names, as specified by the `rename_all` option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Casing {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl Casing {
+    /// Parses a casing convention from its name, as used by `serde`'s `rename_all` attribute.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "lowercase" => Casing::Lower,
+            "UPPERCASE" => Casing::Upper,
+            "PascalCase" => Casing::Pascal,
+            "camelCase" => Casing::Camel,
+            "snake_case" => Casing::Snake,
+            "SCREAMING_SNAKE_CASE" => Casing::ScreamingSnake,
+            _ => return None,
+        })
+    }
+
+    /// Converts the given identifier to this casing convention.
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect::<String>()
+        };
+        match self {
+            Casing::Lower => words.concat().to_lowercase(),
+            Casing::Upper => words.concat().to_uppercase(),
+            Casing::Pascal => words.into_iter().map(capitalize).collect(),
+            Casing::Camel => words
+                .into_iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Casing::Snake => words.join("_").to_lowercase(),
+            Casing::ScreamingSnake => words.join("_").to_uppercase(),
+        }
+    }
+}
+
+/// Converts a field name in snake case to upper camel case, i.e. `rx_fifo` to `RxFifo`.
+pub fn to_upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
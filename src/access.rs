@@ -0,0 +1,36 @@
+//! Errors returned by the setters of fields with restricted write access or legal value ranges.
+
+use core::fmt;
+
+/// The error returned when writing to a write-once field (declared with the `write_once` field
+/// option) that has already been written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AlreadyWritten;
+
+impl fmt::Display for AlreadyWritten {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("field has already been written")
+    }
+}
+
+/// The error returned when writing to a lockable field (declared with the `locked_by` field
+/// option) while its lock bit is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Locked;
+
+impl fmt::Display for Locked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("field is locked")
+    }
+}
+
+/// The error returned when writing a value outside of a field's legal range (declared with the
+/// `range` field option).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRange;
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is out of range")
+    }
+}
@@ -0,0 +1,204 @@
+//! Reference bitfields for flag words of common binary formats (ELF and PE/COFF), for binary
+//! analysis tooling.
+//!
+//! Field names follow the specifications' constant names, without their prefixes and in snake
+//! case (i.e. `SHF_EXECINSTR` is `ElfSectionFlags::execinstr`).
+
+use crate::{bitfield, ConvRaw};
+
+bitfield! {
+    /// The flags of an ELF section header (`sh_flags`).
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ElfSectionFlags(pub u64): Debug, Flags {
+        /// The section contains data that should be writable during process execution.
+        pub write: bool @ 0,
+        /// The section occupies memory during process execution.
+        pub alloc: bool @ 1,
+        /// The section contains executable machine instructions.
+        pub execinstr: bool @ 2,
+        /// The section's data may be merged to eliminate duplication.
+        pub merge: bool @ 4,
+        /// The section consists of null-terminated character strings.
+        pub strings: bool @ 5,
+        /// The section header's `sh_info` field holds a section header table index.
+        pub info_link: bool @ 6,
+        /// The section has special ordering requirements with respect to its linked section.
+        pub link_order: bool @ 7,
+        /// The section requires OS-specific processing beyond the standard linking rules.
+        pub os_nonconforming: bool @ 8,
+        /// The section is a member of a section group.
+        pub group: bool @ 9,
+        /// The section holds thread-local storage.
+        pub tls: bool @ 10,
+        /// The section contains compressed data.
+        pub compressed: bool @ 11,
+        /// Bits reserved for OS-specific semantics (`SHF_MASKOS`).
+        pub mask_os: u8 @ 20..28,
+        /// Bits reserved for processor-specific semantics (`SHF_MASKPROC`).
+        pub mask_proc: u8 @ 28..32,
+    }
+}
+
+bitfield! {
+    /// The flags of an ELF program header (`p_flags`).
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ElfSegmentFlags(pub u32): Debug, Flags {
+        /// The segment is executable.
+        pub x: bool @ 0,
+        /// The segment is writable.
+        pub w: bool @ 1,
+        /// The segment is readable.
+        pub r: bool @ 2,
+        /// Bits reserved for OS-specific semantics (`PF_MASKOS`).
+        pub mask_os: u8 @ 20..28,
+        /// Bits reserved for processor-specific semantics (`PF_MASKPROC`).
+        pub mask_proc: u8 @ 28..32,
+    }
+}
+
+/// An ELF symbol's binding (`STB_*`).
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElfSymbolBinding {
+    Local = 0,
+    Global = 1,
+    Weak = 2,
+    /// `STB_GNU_UNIQUE`, the only OS-specific binding in common use.
+    GnuUnique = 10,
+}
+
+/// An ELF symbol's type (`STT_*`).
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElfSymbolType {
+    NoType = 0,
+    Object = 1,
+    Func = 2,
+    Section = 3,
+    File = 4,
+    Common = 5,
+    Tls = 6,
+    /// `STT_GNU_IFUNC`, the only OS-specific type in common use.
+    GnuIfunc = 10,
+}
+
+bitfield! {
+    /// The binding and type of an ELF symbol table entry (`st_info`).
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ElfSymbolInfo(pub u8): Debug {
+        /// The symbol's type; values in the OS- and processor-specific ranges other than the
+        /// known ones are returned as errors.
+        pub ty: u8 [try_get ElfSymbolType, set ElfSymbolType] @ 0..4,
+        /// The symbol's binding; values in the OS- and processor-specific ranges other than the
+        /// known ones are returned as errors.
+        pub binding: u8 [try_get ElfSymbolBinding, set ElfSymbolBinding] @ 4..8,
+    }
+}
+
+bitfield! {
+    /// The characteristics of a PE/COFF image file (`Characteristics` in the COFF file header).
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct PeFileCharacteristics(pub u16): Debug, Flags {
+        /// The file doesn't contain base relocations and must be loaded at its preferred base
+        /// address.
+        pub relocs_stripped: bool @ 0,
+        /// The file is a valid image that can be run.
+        pub executable_image: bool @ 1,
+        /// COFF line numbers have been removed (deprecated).
+        pub line_nums_stripped: bool @ 2,
+        /// COFF symbol table entries for local symbols have been removed (deprecated).
+        pub local_syms_stripped: bool @ 3,
+        /// Aggressively trim the working set (obsolete).
+        pub aggressive_ws_trim: bool @ 4,
+        /// The application can handle addresses larger than 2 GiB.
+        pub large_address_aware: bool @ 5,
+        /// The file's bytes are reversed, little endian (deprecated).
+        pub bytes_reversed_lo: bool @ 7,
+        /// The machine is based on a 32-bit-word architecture.
+        pub machine_32bit: bool @ 8,
+        /// Debugging information has been removed from the image file.
+        pub debug_stripped: bool @ 9,
+        /// If the image is on removable media, fully load it and copy it to the swap file.
+        pub removable_run_from_swap: bool @ 10,
+        /// If the image is on network media, fully load it and copy it to the swap file.
+        pub net_run_from_swap: bool @ 11,
+        /// The image file is a system file, not a user program.
+        pub system: bool @ 12,
+        /// The image file is a dynamic-link library.
+        pub dll: bool @ 13,
+        /// The file should be run only on a uniprocessor machine.
+        pub up_system_only: bool @ 14,
+        /// The file's bytes are reversed, big endian (deprecated).
+        pub bytes_reversed_hi: bool @ 15,
+    }
+}
+
+/// The alignment of a PE/COFF section's data in object files (`IMAGE_SCN_ALIGN_*`).
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PeSectionAlign {
+    /// No alignment was specified, and the default of 16 bytes is used.
+    Default,
+    Align1,
+    Align2,
+    Align4,
+    Align8,
+    Align16,
+    Align32,
+    Align64,
+    Align128,
+    Align256,
+    Align512,
+    Align1024,
+    Align2048,
+    Align4096,
+    Align8192,
+}
+
+impl PeSectionAlign {
+    /// Returns the alignment in bytes.
+    pub const fn bytes(self) -> u16 {
+        match self {
+            PeSectionAlign::Default => 16,
+            align => 1 << (align as u16 - 1),
+        }
+    }
+}
+
+bitfield! {
+    /// The characteristics of a PE/COFF section header (`Characteristics`).
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct PeSectionCharacteristics(pub u32): Debug, Flags {
+        /// The section should not be padded to the next boundary (obsolete).
+        pub type_no_pad: bool @ 3,
+        /// The section contains executable code.
+        pub cnt_code: bool @ 5,
+        /// The section contains initialized data.
+        pub cnt_initialized_data: bool @ 6,
+        /// The section contains uninitialized data.
+        pub cnt_uninitialized_data: bool @ 7,
+        /// The section contains comments or other information (object files only).
+        pub lnk_info: bool @ 9,
+        /// The section will not become part of the image (object files only).
+        pub lnk_remove: bool @ 11,
+        /// The section contains COMDAT data (object files only).
+        pub lnk_comdat: bool @ 12,
+        /// The section contains data referenced through the global pointer.
+        pub gprel: bool @ 15,
+        /// The alignment of the section's data (object files only); the value 15 is invalid.
+        pub align: u8 [try_get PeSectionAlign, set PeSectionAlign] @ 20..24,
+        /// The section contains extended relocations.
+        pub lnk_nreloc_ovfl: bool @ 24,
+        /// The section can be discarded as needed.
+        pub mem_discardable: bool @ 25,
+        /// The section can't be cached.
+        pub mem_not_cached: bool @ 26,
+        /// The section isn't pageable.
+        pub mem_not_paged: bool @ 27,
+        /// The section can be shared in memory.
+        pub mem_shared: bool @ 28,
+        /// The section can be executed as code.
+        pub mem_execute: bool @ 29,
+        /// The section can be read.
+        pub mem_read: bool @ 30,
+        /// The section can be written to.
+        pub mem_write: bool @ 31,
+    }
+}
@@ -0,0 +1,54 @@
+//! Caching for field getters that convert to types that are expensive to construct, declared with
+//! the `get_cached` field option.
+
+/// A cache holding the last value converted from a field's raw value, alongside the raw value it
+/// was converted from.
+///
+/// The value is only converted again when the field's raw value changes, so writes to the field
+/// (through any means, including direct modification of the storage) invalidate it.
+#[derive(Clone, Debug)]
+pub struct Cached<R, T> {
+    entry: Option<(R, T)>,
+}
+
+impl<R, T> Cached<R, T> {
+    /// Creates an empty cache.
+    #[inline]
+    pub const fn new() -> Self {
+        Cached { entry: None }
+    }
+
+    /// Returns the cached value, if any.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.entry.as_ref().map(|(_, value)| value)
+    }
+
+    /// Clears the cache, so that the next access will convert the raw value again.
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.entry = None;
+    }
+
+    /// Returns the cached value if it was converted from `raw`, or converts `raw` using `f`,
+    /// caches the result and returns it otherwise.
+    pub fn get_or_insert_with(&mut self, raw: R, f: impl FnOnce(R) -> T) -> &T
+    where
+        R: PartialEq + Clone,
+    {
+        if !matches!(&self.entry, Some((cached_raw, _)) if *cached_raw == raw) {
+            self.entry = Some((raw.clone(), f(raw)));
+        }
+        match &self.entry {
+            Some((_, value)) => value,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<R, T> Default for Cached<R, T> {
+    #[inline]
+    fn default() -> Self {
+        Cached::new()
+    }
+}
@@ -0,0 +1,92 @@
+//! Adapters to access bitfields whose storage is wrapped in a [`Cell`] or [`RefCell`] through the
+//! generated accessors.
+
+use crate::Bitfield;
+use core::cell::{Cell, RefCell};
+
+/// A cell containing a bitfield's storage, allowing the bitfield to be read and updated in place
+/// through shared references.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, cell::StorageCell};
+/// # use core::cell::Cell;
+/// bitfield! {
+///     pub struct Ctrl(pub u32) {
+///         pub irq_en: bool @ 0,
+///         pub mode: u8 @ 4..8,
+///     }
+/// }
+///
+/// let ctrl = Cell::new(0_u32);
+/// ctrl.update_bitfield(|ctrl: &mut Ctrl| ctrl.set_mode(3));
+/// assert_eq!(ctrl.get_bitfield::<Ctrl>().mode(), 3);
+/// assert_eq!(ctrl.get(), 0x30);
+/// ```
+pub trait StorageCell {
+    /// The storage type contained in the cell.
+    type Storage;
+
+    /// Returns a bitfield containing a copy of the cell's storage.
+    fn get_bitfield<B: Bitfield<Storage = Self::Storage>>(&self) -> B;
+
+    /// Replaces the cell's storage with the bitfield's.
+    fn set_bitfield<B: Bitfield<Storage = Self::Storage>>(&self, value: B);
+
+    /// Calls `f` with a bitfield containing a copy of the cell's storage, then writes its storage
+    /// back to the cell.
+    fn update_bitfield<B: Bitfield<Storage = Self::Storage>, R>(
+        &self,
+        f: impl FnOnce(&mut B) -> R,
+    ) -> R;
+}
+
+impl<S: Copy> StorageCell for Cell<S> {
+    type Storage = S;
+
+    #[inline]
+    fn get_bitfield<B: Bitfield<Storage = S>>(&self) -> B {
+        B::from_storage(self.get())
+    }
+
+    #[inline]
+    fn set_bitfield<B: Bitfield<Storage = S>>(&self, value: B) {
+        self.set(value.into_storage());
+    }
+
+    #[inline]
+    fn update_bitfield<B: Bitfield<Storage = S>, R>(&self, f: impl FnOnce(&mut B) -> R) -> R {
+        let mut value = B::from_storage(self.get());
+        let result = f(&mut value);
+        self.set(value.into_storage());
+        result
+    }
+}
+
+/// # Panics
+/// All methods panic if the cell is currently mutably borrowed, and [`set_bitfield`] and
+/// [`update_bitfield`] also panic if it's currently borrowed at all.
+///
+/// [`set_bitfield`]: StorageCell::set_bitfield
+/// [`update_bitfield`]: StorageCell::update_bitfield
+impl<S: Clone> StorageCell for RefCell<S> {
+    type Storage = S;
+
+    #[inline]
+    fn get_bitfield<B: Bitfield<Storage = S>>(&self) -> B {
+        B::from_storage(self.borrow().clone())
+    }
+
+    #[inline]
+    fn set_bitfield<B: Bitfield<Storage = S>>(&self, value: B) {
+        *self.borrow_mut() = value.into_storage();
+    }
+
+    #[inline]
+    fn update_bitfield<B: Bitfield<Storage = S>, R>(&self, f: impl FnOnce(&mut B) -> R) -> R {
+        let mut storage = self.borrow_mut();
+        let mut value = B::from_storage(storage.clone());
+        let result = f(&mut value);
+        *storage = value.into_storage();
+        result
+    }
+}
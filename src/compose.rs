@@ -0,0 +1,123 @@
+//! Helpers to compose a bitfield from two half-width bitfields, and to split it back.
+
+use crate::Bitfield;
+
+/// Concatenate two half-width values into a value of twice their width, and split it back.
+///
+/// `self` holds the low half and `Hi` the high half. This is only implemented for pairs of
+/// integer types whose combined width is exactly the width of `Output`, so compatible widths are
+/// checked at compile time.
+pub trait ConcatStorage<Hi>: Sized {
+    /// The type containing both halves.
+    type Output;
+
+    /// Returns a value with `self` in its low half and `hi` in its high half.
+    fn concat(self, hi: Hi) -> Self::Output;
+
+    /// Splits a value into its low and high halves.
+    fn split(value: Self::Output) -> (Self, Hi);
+}
+
+macro_rules! impl_concat_storage {
+    ($($half: ty, $u_half: ty => $full: ty, $u_full: ty);* $(;)?) => {
+        $(
+            impl ConcatStorage<$half> for $half {
+                type Output = $full;
+
+                #[inline]
+                fn concat(self, hi: $half) -> $full {
+                    (self as $u_half as $u_full | (hi as $u_half as $u_full) << <$half>::BITS)
+                        as $full
+                }
+
+                #[inline]
+                fn split(value: $full) -> ($half, $half) {
+                    (value as $half, (value as $u_full >> <$half>::BITS) as $half)
+                }
+            }
+        )*
+    };
+}
+
+impl_concat_storage!(
+    u8, u8 => u16, u16;
+    u16, u16 => u32, u32;
+    u32, u32 => u64, u64;
+    u64, u64 => u128, u128;
+    i8, u8 => i16, u16;
+    i16, u16 => i32, u32;
+    i32, u32 => i64, u64;
+    i64, u64 => i128, u128;
+);
+
+/// Concatenates two bitfields into a bitfield of twice their width, with `lo` in the low half of
+/// its storage and `hi` in the high half.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, compose::concat};
+/// bitfield! {
+///     #[derive(Clone, Copy, PartialEq, Eq)]
+///     pub struct Lo(pub u32): FromStorage, IntoStorage {
+///         pub count: u32 @ ..,
+///     }
+/// }
+///
+/// bitfield! {
+///     #[derive(Clone, Copy, PartialEq, Eq)]
+///     pub struct Hi(pub u32): FromStorage, IntoStorage {
+///         pub overflow: bool @ 31,
+///     }
+/// }
+///
+/// bitfield! {
+///     #[derive(Clone, Copy, PartialEq, Eq)]
+///     pub struct Counter(pub u64): FromStorage, IntoStorage {
+///         pub count: u32 @ 0..32,
+///         pub overflow: bool @ 63,
+///     }
+/// }
+///
+/// let counter: Counter = concat(Lo(0x1234), Hi(0x8000_0000));
+/// assert_eq!(counter.0, 0x8000_0000_0000_1234);
+/// assert!(counter.overflow());
+/// ```
+#[inline]
+pub fn concat<A, B, C>(lo: A, hi: B) -> C
+where
+    A: Bitfield,
+    B: Bitfield,
+    C: Bitfield,
+    A::Storage: ConcatStorage<B::Storage, Output = C::Storage>,
+{
+    C::from_storage(lo.into_storage().concat(hi.into_storage()))
+}
+
+/// Splits a bitfield into two bitfields of half its width, containing the low and high halves of
+/// its storage respectively.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, compose::split};
+/// # bitfield! {
+/// #     pub struct Lo(pub u32) { pub count: u32 @ .. }
+/// # }
+/// # bitfield! {
+/// #     pub struct Hi(pub u32) { pub overflow: bool @ 31 }
+/// # }
+/// # bitfield! {
+/// #     pub struct Counter(pub u64) {}
+/// # }
+/// let (lo, hi): (Lo, Hi) = split(Counter(0x8000_0000_0000_1234));
+/// assert_eq!(lo.count(), 0x1234);
+/// assert!(hi.overflow());
+/// ```
+#[inline]
+pub fn split<A, B, C>(value: C) -> (A, B)
+where
+    A: Bitfield,
+    B: Bitfield,
+    C: Bitfield,
+    A::Storage: ConcatStorage<B::Storage, Output = C::Storage>,
+{
+    let (lo, hi) = A::Storage::split(value.into_storage());
+    (A::from_storage(lo), B::from_storage(hi))
+}
@@ -0,0 +1,223 @@
+//! Reference bitfields for CPU status registers (the AArch32 `CPSR` and the RISC-V `mstatus`
+//! CSR), for emulators and as examples of the [`bitfield!`](crate::bitfield!) macro's features.
+//!
+//! Field names follow the architecture manuals' field names in snake case (i.e. `mstatus.MPIE`
+//! is `Mstatus::mpie`). Reserved bits are declared as reserved ranges instead of fields, so that
+//! the layouts are checked to cover every bit, and they're only accessible through the raw value;
+//! setters preserve them, and `RESERVED_MASK` constants are provided to clear them when emulating
+//! writes from software.
+
+use crate::{bitfield, ConvRaw};
+
+/// An AArch32 processor mode (`CPSR.M`).
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArmMode {
+    User = 0x10,
+    Fiq = 0x11,
+    Irq = 0x12,
+    Supervisor = 0x13,
+    Monitor = 0x16,
+    Abort = 0x17,
+    Hyp = 0x1A,
+    Undefined = 0x1B,
+    System = 0x1F,
+}
+
+impl ArmMode {
+    /// Returns whether the mode is privileged, i.e. any mode other than [`ArmMode::User`].
+    #[inline]
+    pub const fn is_privileged(self) -> bool {
+        !matches!(self, ArmMode::User)
+    }
+}
+
+/// The instruction set an AArch32 processor is executing, selected by `CPSR.J` and `CPSR.T`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArmInstrSet {
+    Arm,
+    Thumb,
+    Jazelle,
+    ThumbEe,
+}
+
+bitfield! {
+    /// The AArch32 Current Program Status Register (`CPSR`), also used as the layout of the
+    /// Saved Program Status Registers (`SPSR_<mode>`).
+    ///
+    /// Bits 20-23 are reserved.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Cpsr(pub u32): Debug, Valid, options(deny_gaps) {
+        /// The current processor mode; reserved encodings are returned as errors.
+        pub mode: u8 [try_get ArmMode, set ArmMode] @ 0..5,
+        /// The Thumb execution state bit.
+        pub t: bool @ 5,
+        /// FIQ interrupts are masked.
+        pub f: bool @ 6,
+        /// IRQ interrupts are masked.
+        pub i: bool @ 7,
+        /// Asynchronous aborts are masked.
+        pub a: bool @ 8,
+        /// Data accesses are big-endian.
+        pub e: bool @ 9,
+        it_hi: u8 @ 10..16,
+        /// The Greater than or Equal flags set by the parallel addition and subtraction
+        /// instructions, one per byte lane.
+        pub ge: u8 @ 16..20,
+        reserved @ 20..24,
+        /// The Jazelle execution state bit.
+        pub j: bool @ 24,
+        it_lo: u8 @ 25..27,
+        /// The current instruction set, as selected by the `J` and `T` bits.
+        pub calc instr_set -> ArmInstrSet = match (self.j(), self.t()) {
+            (false, false) => ArmInstrSet::Arm,
+            (false, true) => ArmInstrSet::Thumb,
+            (true, false) => ArmInstrSet::Jazelle,
+            (true, true) => ArmInstrSet::ThumbEe,
+        },
+        /// The cumulative saturation flag.
+        pub q: bool @ 27,
+        /// The overflow condition flag.
+        pub v: bool @ 28,
+        /// The carry condition flag.
+        pub c: bool @ 29,
+        /// The zero condition flag.
+        pub z: bool @ 30,
+        /// The negative condition flag.
+        pub n: bool @ 31,
+        /// The condition flags (`NZCV`) as a 4-bit value, with `N` in the highest bit.
+        pub nzcv: u8 [read_only] @ 28..32,
+        /// The If-Then execution state, reassembled from its two parts (`IT[1:0]` in bits 25-26
+        /// and `IT[7:2]` in bits 10-15).
+        pub calc it -> u8 = self.it_hi() << 2 | self.it_lo(),
+
+        /// The state on reset: Supervisor mode, ARM state, with all interrupts masked.
+        pub const RESET = { mode: 0x13, a: true, i: true, f: true };
+    }
+}
+
+impl Cpsr {
+    /// The mask of the reserved bits.
+    pub const RESERVED_MASK: u32 = 0x00F0_0000;
+
+    /// Returns the value with the If-Then execution state set to the given value.
+    #[inline]
+    #[must_use]
+    pub fn with_it(self, value: u8) -> Self {
+        self.with_it_hi(value >> 2).with_it_lo(value & 3)
+    }
+
+    /// Sets the If-Then execution state to the given value.
+    #[inline]
+    pub fn set_it(&mut self, value: u8) {
+        *self = self.with_it(value);
+    }
+}
+
+/// A RISC-V privilege mode, as encoded in `mstatus.MPP`.
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiscvPrivilege {
+    User = 0,
+    Supervisor = 1,
+    Machine = 3,
+}
+
+/// The state of a RISC-V extension's context, as encoded in `mstatus.FS`, `mstatus.VS` and
+/// `mstatus.XS`.
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiscvExtState {
+    Off = 0,
+    Initial = 1,
+    Clean = 2,
+    Dirty = 3,
+}
+
+/// The effective XLEN of a less privileged mode, as encoded in `mstatus.UXL` and `mstatus.SXL`.
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RiscvXlen {
+    Rv32 = 1,
+    Rv64 = 2,
+    Rv128 = 3,
+}
+
+bitfield! {
+    /// The RV64 machine status register (`mstatus`).
+    ///
+    /// Bits 0, 2, 4, 23-31 and 38-62 are reserved (`WPRI`). The `XS`, `UXL`, `SXL` and `SD`
+    /// fields are read-only, as they're updated by the hart itself; `SD` can be recomputed with
+    /// [`Mstatus::update_sd`] after writing the extension states.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Mstatus(pub u64): Debug, Valid, options(deny_gaps) {
+        reserved @ 0,
+        /// Supervisor-mode interrupts are enabled.
+        pub sie: bool @ 1,
+        reserved @ 2,
+        /// Machine-mode interrupts are enabled.
+        pub mie: bool @ 3,
+        reserved @ 4,
+        /// The value of `SIE` before the last trap into supervisor mode.
+        pub spie: bool @ 5,
+        /// User-mode data accesses are big-endian.
+        pub ube: bool @ 6,
+        /// The value of `MIE` before the last trap into machine mode.
+        pub mpie: bool @ 7,
+        /// The privilege mode before the last trap into supervisor mode was supervisor mode
+        /// (user mode otherwise).
+        pub spp: bool @ 8,
+        /// The vector extension's state.
+        pub vs: u8 [unwrap_get RiscvExtState, set RiscvExtState] @ 9..11,
+        /// The privilege mode before the last trap into machine mode; the reserved encoding is
+        /// returned as an error.
+        pub mpp: u8 [try_get RiscvPrivilege, set RiscvPrivilege] @ 11..13,
+        /// The floating-point extension's state.
+        pub fs: u8 [unwrap_get RiscvExtState, set RiscvExtState] @ 13..15,
+        /// The summarized state of any additional user-mode extensions.
+        pub xs: u8 [unwrap_get RiscvExtState, read_only] @ 15..17,
+        /// Loads and stores use the privilege mode in `MPP` for address translation and
+        /// protection.
+        pub mprv: bool @ 17,
+        /// Supervisor-mode accesses to user-mode pages are permitted.
+        pub sum: bool @ 18,
+        /// Loads from executable pages are permitted.
+        pub mxr: bool @ 19,
+        /// Supervisor-mode virtual memory management operations trap.
+        pub tvm: bool @ 20,
+        /// `WFI` instructions in lower privilege modes trap.
+        pub tw: bool @ 21,
+        /// `SRET` instructions in supervisor mode trap.
+        pub tsr: bool @ 22,
+        reserved @ 23..32,
+        /// User mode's XLEN; reserved encodings are returned as errors.
+        pub uxl: u8 [try_get RiscvXlen, read_only] @ 32..34,
+        /// Supervisor mode's XLEN; reserved encodings are returned as errors.
+        pub sxl: u8 [try_get RiscvXlen, read_only] @ 34..36,
+        /// Supervisor-mode data accesses are big-endian.
+        pub sbe: bool @ 36,
+        /// Machine-mode data accesses are big-endian.
+        pub mbe: bool @ 37,
+        reserved @ 38..63,
+        /// Any of the `FS`, `VS` or `XS` fields is dirty.
+        pub sd: bool [read_only] @ 63,
+
+        /// The state on reset of an RV64 hart whose lower privilege modes are also 64-bit.
+        pub const RESET = { mpp: 3, uxl: 2, sxl: 2 };
+    }
+}
+
+impl Mstatus {
+    /// The mask of the reserved bits.
+    pub const RESERVED_MASK: u64 = 0x7FFF_FFC0_FF80_0015;
+
+    /// Returns the value with `SD` set according to the current values of `FS`, `VS` and `XS`.
+    #[inline]
+    #[must_use]
+    pub fn with_sd_updated(self) -> Self {
+        let dirty = [self.fs(), self.vs(), self.xs()].contains(&RiscvExtState::Dirty);
+        Mstatus(self.0 & !(1 << 63) | (dirty as u64) << 63)
+    }
+
+    /// Sets `SD` according to the current values of `FS`, `VS` and `XS`.
+    #[inline]
+    pub fn update_sd(&mut self) {
+        *self = self.with_sd_updated();
+    }
+}
@@ -0,0 +1,143 @@
+//! Reference bitfields for common network protocol headers, as ready-to-use types for packet
+//! tooling.
+//!
+//! All headers are stored as byte arrays in wire order, so they can be copied directly from and to
+//! packet buffers. As array storage numbers bits starting from the least significant bit of its
+//! first byte, multi-byte fields (which are big-endian on the wire) are converted from and to
+//! native integers by swapping their bytes, and bits are numbered from the least significant one
+//! of each byte (i.e. the IPv4 version, in the high nibble of the first byte, spans bits `4..8`).
+
+use crate::bitfield;
+use core::net::Ipv4Addr;
+
+/// Computes the internet checksum (the ones' complement of the ones' complement sum of all
+/// big-endian 16-bit words) of `bytes`, padding it with a zero byte if its length is odd.
+///
+/// ```rust
+/// # use proc_bitfield::headers::internet_checksum;
+/// assert_eq!(internet_checksum(&[0x45, 0x00, 0x00, 0x1C]), !0x451C);
+/// ```
+pub fn internet_checksum(bytes: &[u8]) -> u16 {
+    let mut sum = bytes
+        .chunks(2)
+        .map(|word| u32::from(word[0]) << 8 | u32::from(word.get(1).copied().unwrap_or(0)))
+        .sum::<u32>();
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+bitfield! {
+    /// An Ethernet II frame header.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct EthernetHeader(pub [u8; 14]): Debug {
+        /// The destination MAC address.
+        pub dst: [u8; 6] @ 0..48,
+        /// The source MAC address.
+        pub src: [u8; 6] @ 48..96,
+        /// The type of the payload's protocol, i.e. `0x0800` for IPv4.
+        pub ether_type: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 96..112,
+    }
+}
+
+bitfield! {
+    /// An IPv4 header, without options.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Ipv4Header(pub [u8; 20]): Debug {
+        /// The header's length in 32-bit words, including options.
+        pub ihl: u8 @ 0..4,
+        /// The IP version, always 4.
+        pub version: u8 @ 4..8,
+        /// The explicit congestion notification.
+        pub ecn: u8 @ 8..10,
+        /// The differentiated services code point.
+        pub dscp: u8 @ 10..16,
+        /// The whole packet's length in bytes, including the header.
+        pub total_len: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 16..32,
+        pub identification: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 32..48,
+        flags_fragment_offset: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 48..64,
+        pub more_fragments: bool @ 53,
+        pub dont_fragment: bool @ 54,
+        /// The offset of the fragment's data in the original packet, in units of 8 bytes.
+        pub calc fragment_offset -> u16 = self.flags_fragment_offset() & 0x1FFF,
+        pub ttl: u8 @ 64..72,
+        /// The payload's protocol number, i.e. 6 for TCP and 17 for UDP.
+        pub protocol: u8 @ 72..80,
+        pub checksum: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 80..96,
+        pub src: [u8; 4] [
+            get Ipv4Addr,
+            set_fn (|addr: Ipv4Addr| addr.octets()) (Ipv4Addr),
+        ] @ 96..128,
+        pub dst: [u8; 4] [
+            get Ipv4Addr,
+            set_fn (|addr: Ipv4Addr| addr.octets()) (Ipv4Addr),
+        ] @ 128..160,
+    }
+}
+
+impl Ipv4Header {
+    /// Returns the header with the fragment offset set to the given value, truncated to 13 bits.
+    #[inline]
+    #[must_use]
+    pub fn with_fragment_offset(self, value: u16) -> Self {
+        let flags = self.flags_fragment_offset() & !0x1FFF;
+        self.with_flags_fragment_offset(flags | (value & 0x1FFF))
+    }
+
+    /// Sets the fragment offset to the given value, truncated to 13 bits.
+    #[inline]
+    pub fn set_fragment_offset(&mut self, value: u16) {
+        *self = self.with_fragment_offset(value);
+    }
+
+    /// Computes the header's checksum, treating the checksum field as zero.
+    pub fn compute_checksum(&self) -> u16 {
+        internet_checksum(&self.with_checksum(0).0)
+    }
+
+    /// Returns `true` if the header's checksum field is correct.
+    #[inline]
+    pub fn is_checksum_valid(&self) -> bool {
+        internet_checksum(&self.0) == 0
+    }
+}
+
+bitfield! {
+    /// A UDP header.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct UdpHeader(pub [u8; 8]): Debug {
+        pub src_port: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 0..16,
+        pub dst_port: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 16..32,
+        /// The datagram's length in bytes, including the header.
+        pub length: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 32..48,
+        pub checksum: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 48..64,
+    }
+}
+
+bitfield! {
+    /// A TCP header, without options.
+    ///
+    /// The control bits can be listed by name through the `Flags` automatic implementation.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct TcpHeader(pub [u8; 20]): Debug, Flags {
+        pub src_port: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 0..16,
+        pub dst_port: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 16..32,
+        pub seq_num: u32 [get_fn u32::swap_bytes, set_fn u32::swap_bytes] @ 32..64,
+        pub ack_num: u32 [get_fn u32::swap_bytes, set_fn u32::swap_bytes] @ 64..96,
+        pub ns: bool @ 96,
+        /// The header's length in 32-bit words, including options.
+        pub data_offset: u8 @ 100..104,
+        pub fin: bool @ 104,
+        pub syn: bool @ 105,
+        pub rst: bool @ 106,
+        pub psh: bool @ 107,
+        pub ack: bool @ 108,
+        pub urg: bool @ 109,
+        pub ece: bool @ 110,
+        pub cwr: bool @ 111,
+        pub window: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 112..128,
+        pub checksum: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 128..144,
+        pub urgent_ptr: u16 [get_fn u16::swap_bytes, set_fn u16::swap_bytes] @ 144..160,
+    }
+}
@@ -4,34 +4,115 @@
 #![cfg_attr(all(any(doc, test), feature = "nightly"), feature(trivial_bounds))]
 #![warn(clippy::all)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[doc(hidden)]
 pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::collections::BTreeMap;
     pub use static_assertions;
+
+    /// Checks whether the result of a fallible getter is successful, and extracts its value.
+    pub trait IsOk {
+        type Ok;
+
+        fn is_ok(&self) -> bool;
+
+        fn unwrap_valid(self) -> Self::Ok;
+    }
+
+    impl<T, E> IsOk for Result<T, E> {
+        type Ok = T;
+
+        #[inline]
+        fn is_ok(&self) -> bool {
+            self.is_ok()
+        }
+
+        #[inline]
+        fn unwrap_valid(self) -> T {
+            match self {
+                Ok(value) => value,
+                Err(_) => unreachable!("validated field contains an invalid value"),
+            }
+        }
+    }
+
+    impl<T> IsOk for Option<T> {
+        type Ok = T;
+
+        #[inline]
+        fn is_ok(&self) -> bool {
+            self.is_some()
+        }
+
+        #[inline]
+        fn unwrap_valid(self) -> T {
+            match self {
+                Some(value) => value,
+                None => unreachable!("validated field contains an invalid value"),
+            }
+        }
+    }
 }
 
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
 /// The main focus of the crate. Defines a bitfield struct.
 #[doc = include_str!("../usage_examples/bitfield.md")]
 pub use macros::bitfield;
 
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
+/// Defines a parameterized bitfield template, which can be instantiated multiple times with
+/// different names and parameters.
+#[doc = include_str!("../usage_examples/bitfield_template.md")]
+pub use macros::bitfield_template;
+
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
 /// Reads a single field from an anonymous bitfield, without creating a bitfield struct.
 #[doc = include_str!("../usage_examples/bits.md")]
 pub use macros::bits;
 
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
 /// Returns an anonymous bitfield with a single field modified, without creating a bitfield struct.
 #[doc = include_str!("../usage_examples/with_bits.md")]
 pub use macros::with_bits;
 
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
 /// Modifies a single field in an anonymous bitfield, without creating a bitfield struct.
 #[doc = include_str!("../usage_examples/set_bits.md")]
 pub use macros::set_bits;
 
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
+/// Copies the bits of a field of a bitfield into a field of the same width in another bitfield.
+#[doc = include_str!("../usage_examples/copy_bits.md")]
+pub use macros::copy_bits;
+
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
+/// Defines a tagged union of bitfield layouts sharing the same storage, selected by the value of a
+/// discriminant field.
+#[doc = include_str!("../usage_examples/match_layout.md")]
+pub use macros::match_layout;
+
+#[cfg(feature = "macros")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "macros")))]
 /// A derive macro to implement any applicable conversion traits between an enum and the builtin
 /// integer and boolean types corresponding to variant discriminants.
 #[doc = include_str!("../usage_examples/conv_raw.md")]
 pub use macros::ConvRaw;
 
-#[cfg(feature = "nightly")]
-#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "nightly")))]
+#[cfg(all(feature = "macros", feature = "nightly"))]
+#[cfg_attr(
+    all(doc, feature = "nightly"),
+    doc(cfg(all(feature = "macros", feature = "nightly")))
+)]
 /// A derive macro to implement `Bits<T> for U` and the related traits for a type `T` and all
 /// integer bitfield storage types `U`, by unwrapping the conversion results.
 #[doc = include_str!("../usage_examples/unwrap_bits.md")]
@@ -41,11 +122,38 @@ mod conv;
 pub use conv::*;
 mod traits;
 pub use traits::*;
+pub mod access;
+#[cfg(feature = "binfmt")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "binfmt")))]
+pub mod binfmt;
+pub mod cache;
+pub mod cell;
+pub mod compose;
+#[cfg(feature = "cpu")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "cpu")))]
+pub mod cpu;
+#[cfg(feature = "headers")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "headers")))]
+pub mod headers;
+pub mod meta;
 pub mod nested;
+pub mod ops;
+#[cfg(feature = "rand")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "rand")))]
+pub mod random;
+pub mod remote;
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "alloc")))]
+pub mod shared;
+pub mod transform;
+#[cfg(feature = "alloc")]
+#[cfg_attr(all(doc, feature = "nightly"), doc(cfg(feature = "alloc")))]
+pub mod value;
+pub mod versioned;
 
-#[cfg(any(test, doc))]
+#[cfg(any(test, doc, feature = "headers", feature = "binfmt", feature = "cpu"))]
 extern crate self as proc_bitfield;
 
-#[cfg(doc)]
+#[cfg(all(doc, feature = "macros"))]
 /// Sample bitfields to showcase the crate's features
 pub mod example;
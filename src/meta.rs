@@ -0,0 +1,565 @@
+//! Runtime metadata describing the fields of bitfield structs declared with
+//! [`bitfield!`](crate::bitfield).
+
+use core::fmt;
+
+/// Describes a single field of a bitfield struct.
+///
+/// Every bitfield struct exposes a `FIELDS` associated constant listing the `FieldInfo` for each
+/// of its fields, in declaration order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The field's name, as declared.
+    pub name: &'static str,
+    /// The first bit spanned by the field.
+    pub start: usize,
+    /// The bit after the last one spanned by the field.
+    pub end: usize,
+    /// The distance between consecutive bits spanned by the field; this is 1 for all fields
+    /// except strided ones.
+    pub stride: usize,
+    /// Whether the field has a getter.
+    pub readable: bool,
+    /// Whether the field has setters.
+    pub writable: bool,
+    /// The field's raw type, as declared.
+    pub ty: &'static str,
+    /// The field's unit, if specified with the `unit` field option.
+    pub unit: Option<&'static str>,
+    /// Whether the field contains a nested bitfield.
+    pub nested: bool,
+}
+
+impl FieldInfo {
+    /// Returns the number of bits spanned by the field.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        (self.end - self.start).div_ceil(self.stride)
+    }
+
+    /// Returns `true` if the field spans no bits; this can't happen for fields declared with
+    /// `bitfield!`.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.end == self.start
+    }
+
+    /// Returns `true` if `bit` is spanned by the field.
+    #[inline]
+    // `is_multiple_of` would require Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub const fn contains_bit(&self, bit: usize) -> bool {
+        self.start <= bit && bit < self.end && (bit - self.start) % self.stride == 0
+    }
+}
+
+/// Returns the first field in `fields` that spans `bit`, if any.
+///
+/// This is what the generated `field_at_bit` functions call with the bitfield's `FIELDS`.
+#[inline]
+pub const fn field_at_bit(fields: &'static [FieldInfo], bit: usize) -> Option<&'static FieldInfo> {
+    let mut i = 0;
+    while i < fields.len() {
+        if fields[i].contains_bit(bit) {
+            return Some(&fields[i]);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Returns the number of bits up to and including the highest one spanned by any field in
+/// `fields`, or 0 if there are none.
+///
+/// This is what the generated `USED_BITS` constants contain.
+#[inline]
+pub const fn used_bits(fields: &[FieldInfo]) -> usize {
+    let mut used_bits = 0;
+    let mut i = 0;
+    while i < fields.len() {
+        if fields[i].end > used_bits {
+            used_bits = fields[i].end;
+        }
+        i += 1;
+    }
+    used_bits
+}
+
+/// Returns the lowest of the first `bits` bits that isn't spanned by any of the given fields or
+/// reserved ranges, if any.
+///
+/// This is what the `deny_gaps` option uses to check that a bitfield's storage is fully covered.
+#[inline]
+pub const fn first_uncovered_bit(
+    bits: usize,
+    fields: &[FieldInfo],
+    reserved: &[FieldInfo],
+) -> Option<usize> {
+    let mut bit = 0;
+    'bits: while bit < bits {
+        let mut i = 0;
+        while i < fields.len() {
+            if fields[i].contains_bit(bit) {
+                bit += 1;
+                continue 'bits;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        while i < reserved.len() {
+            if reserved[i].contains_bit(bit) {
+                bit += 1;
+                continue 'bits;
+            }
+            i += 1;
+        }
+        return Some(bit);
+    }
+    None
+}
+
+/// Returns the field in `fields` named `name`, if any.
+#[inline]
+pub const fn field_by_name(fields: &'static [FieldInfo], name: &str) -> Option<&'static FieldInfo> {
+    let mut i = 0;
+    'fields: while i < fields.len() {
+        let field_name = fields[i].name.as_bytes();
+        i += 1;
+        if field_name.len() != name.len() {
+            continue;
+        }
+        let mut j = 0;
+        while j < field_name.len() {
+            if field_name[j] != name.as_bytes()[j] {
+                continue 'fields;
+            }
+            j += 1;
+        }
+        return Some(&fields[i - 1]);
+    }
+    None
+}
+
+/// Describes a single variant of an enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VariantInfo {
+    /// The variant's name.
+    pub name: &'static str,
+    /// The variant's discriminant.
+    pub value: i128,
+}
+
+/// Metadata for fieldless enums used as converted field types, implemented automatically by the
+/// [`ConvRaw`](crate::ConvRaw) derive.
+pub trait EnumInfo {
+    /// The enum's name.
+    const NAME: &'static str;
+    /// All of the enum's variants, in declaration order.
+    const VARIANTS: &'static [VariantInfo];
+}
+
+struct JsonStr<'a>(&'a str);
+
+impl fmt::Display for JsonStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                '\0'..='\x1F' => write!(f, "\\u{:04X}", c as u32)?,
+                c => fmt::Write::write_char(f, c)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+struct JsonOptStr(Option<&'static str>);
+
+impl fmt::Display for JsonOptStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(s) => JsonStr(s).fmt(f),
+            None => f.write_str("null"),
+        }
+    }
+}
+
+/// The enum metadata returned by [`Layout::field_enum`].
+pub type FieldEnum = Option<(&'static str, &'static [VariantInfo])>;
+
+/// Describes a bitfield struct's layout.
+///
+/// Every bitfield struct exposes a `LAYOUT` associated constant of this type.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    /// The bitfield struct's name.
+    pub name: &'static str,
+    /// The name of the register described by the bitfield (i.e. as spelled in its datasheet), if
+    /// specified through the `name` option.
+    pub register_name: Option<&'static str>,
+    /// The width of the bitfield's storage type in bits.
+    pub bits: usize,
+    /// Metadata for all of the bitfield's fields, in declaration order.
+    pub fields: &'static [FieldInfo],
+    /// Returns the metadata of the enum the field at the given index in `fields` is converted
+    /// to, if it derives [`ConvRaw`](crate::ConvRaw).
+    pub field_enum: fn(usize) -> FieldEnum,
+}
+
+// The FNV-1a offset basis and prime for 64-bit hashes
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+#[inline]
+const fn fnv_bytes(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u64).wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+#[inline]
+const fn fnv_usize(hash: u64, value: usize) -> u64 {
+    fnv_bytes(hash, &(value as u64).to_le_bytes())
+}
+
+/// Computes a fingerprint of a bitfield's layout from its storage's width in bits and its fields'
+/// names and bit ranges, in declaration order.
+///
+/// This is what the generated `LAYOUT_HASH` constants contain. The fingerprint is a 64-bit FNV-1a
+/// hash, and is stable across crate versions and targets, so it can be compared between separately
+/// compiled programs; it doesn't depend on the bitfield's name, nor on its fields' types,
+/// conversions or access restrictions.
+pub const fn layout_hash(bits: usize, fields: &[FieldInfo]) -> u64 {
+    let mut hash = fnv_usize(FNV_OFFSET_BASIS, bits);
+    hash = fnv_usize(hash, fields.len());
+    let mut i = 0;
+    while i < fields.len() {
+        let field = &fields[i];
+        hash = fnv_usize(hash, field.name.len());
+        hash = fnv_bytes(hash, field.name.as_bytes());
+        hash = fnv_usize(hash, field.start);
+        hash = fnv_usize(hash, field.end);
+        hash = fnv_usize(hash, field.stride);
+        i += 1;
+    }
+    hash
+}
+
+impl Layout {
+    /// Returns the name of the register described by the bitfield if specified, or the bitfield
+    /// struct's name otherwise.
+    #[inline]
+    pub const fn display_name(&self) -> &'static str {
+        match self.register_name {
+            Some(name) => name,
+            None => self.name,
+        }
+    }
+
+    /// Writes a JSON description of the layout, containing the bitfield's name (and register name,
+    /// if specified), its storage's width in bits and all of its fields' metadata, including the
+    /// variants of their enum types.
+    pub fn write_json(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        write!(w, "{{\"name\":{},", JsonStr(self.name))?;
+        if let Some(register_name) = self.register_name {
+            write!(w, "\"register_name\":{},", JsonStr(register_name))?;
+        }
+        write!(w, "\"bits\":{},\"fields\":[", self.bits)?;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i != 0 {
+                w.write_char(',')?;
+            }
+            write!(
+                w,
+                "{{\"name\":{},\"start\":{},\"end\":{},\"stride\":{},\"readable\":{},\
+                 \"writable\":{},\"type\":{},\"unit\":{},\"nested\":{}",
+                JsonStr(field.name),
+                field.start,
+                field.end,
+                field.stride,
+                field.readable,
+                field.writable,
+                JsonStr(field.ty),
+                JsonOptStr(field.unit),
+                field.nested,
+            )?;
+            if let Some((enum_name, variants)) = (self.field_enum)(i) {
+                write!(
+                    w,
+                    ",\"enum\":{{\"name\":{},\"variants\":[",
+                    JsonStr(enum_name)
+                )?;
+                for (j, variant) in variants.iter().enumerate() {
+                    if j != 0 {
+                        w.write_char(',')?;
+                    }
+                    write!(
+                        w,
+                        "{{\"name\":{},\"value\":{}}}",
+                        JsonStr(variant.name),
+                        variant.value
+                    )?;
+                }
+                w.write_str("]}")?;
+            }
+            w.write_char('}')?;
+        }
+        w.write_str("]}")
+    }
+}
+
+/// Object-safe access to a bitfield's layout and raw value, implemented for all bitfield structs,
+/// to handle bitfields of different types uniformly (i.e. through `&[&dyn AnyBitfield]` in
+/// register inspectors).
+///
+/// Raw values are exchanged as `u128`s, so only the low 128 bits of wider storage types are
+/// accessible through this trait.
+pub trait AnyBitfield {
+    /// Returns the bitfield's layout.
+    fn layout(&self) -> Layout;
+
+    /// Returns the low 128 bits of the bitfield's raw storage value.
+    fn raw_u128(&self) -> u128;
+
+    /// Returns the raw bits spanned by the given field, which should be one of the bitfield's
+    /// own fields, packed together starting from bit 0 and without sign extension; bits above
+    /// the low 128 ones are read as 0.
+    fn field_raw(&self, field: &FieldInfo) -> u128 {
+        let raw = self.raw_u128();
+        let mut value = 0;
+        let mut bit = field.start;
+        let mut i = 0;
+        while bit < field.end && bit < 128 {
+            value |= (raw >> bit & 1) << i;
+            bit += field.stride;
+            i += 1;
+        }
+        value
+    }
+
+    /// Writes the bitfield's register name (or its name, if unspecified) and the raw values of its
+    /// readable fields (or the names of the matching variants, for fields converted to enums
+    /// deriving [`ConvRaw`](crate::ConvRaw)), in the form `Name { field: 0x1, mode: Fast }`;
+    /// fields spanning bits above the low 128 ones are written as `<wide>`, as their values aren't
+    /// accessible through this trait.
+    fn write_dump(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let layout = self.layout();
+        w.write_str(layout.display_name())?;
+        let mut is_first = true;
+        for (i, field) in layout.fields.iter().enumerate() {
+            if !field.readable {
+                continue;
+            }
+            w.write_str(if is_first { " { " } else { ", " })?;
+            is_first = false;
+            if field.start + (field.len() - 1) * field.stride >= 128 {
+                write!(w, "{}: <wide>", field.name)?;
+                continue;
+            }
+            let value = self.field_raw(field);
+            let mask = u128::MAX >> (128 - field.len());
+            let variant = (layout.field_enum)(i).and_then(|(_, variants)| {
+                variants
+                    .iter()
+                    .find(|variant| variant.value as u128 & mask == value)
+            });
+            match variant {
+                Some(variant) => write!(w, "{}: {}", field.name, variant.name)?,
+                None => write!(w, "{}: {:#x}", field.name, value)?,
+            }
+        }
+        w.write_str(if is_first { " {}" } else { " }" })
+    }
+
+    /// Returns the bitfield as a trait object.
+    #[inline]
+    fn as_dyn(&self) -> &dyn AnyBitfield
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
+impl fmt::Debug for dyn AnyBitfield + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_dump(f)
+    }
+}
+
+const PRETTY_PRINTERS_PRELUDE: &str =
+    "# Pretty-printers for bitfield structs, generated by proc-bitfield.
+#
+# Load with `source <file>` in GDB, or `command script import <file>` in LLDB.
+
+";
+
+const PRETTY_PRINTERS_BODY: &str = r#"
+
+def _format(name, raw):
+    fields = _LAYOUTS[name][1]
+    parts = []
+    for field_name, start, end, stride, ty, nested, variants in fields:
+        width = (end - start + stride - 1) // stride
+        value = 0
+        for i in range(width):
+            value |= ((raw >> (start + i * stride)) & 1) << i
+        if nested and ty in _LAYOUTS:
+            text = _format(ty, value)
+        elif ty == "bool":
+            text = "true" if value else "false"
+        else:
+            if ty[:1] == "i" and value >> (width - 1):
+                value -= 1 << width
+            if variants is not None and value in variants:
+                text = variants[value]
+            else:
+                text = str(value)
+        parts.append("%s: %s" % (field_name, text))
+    return "%s { %s }" % (name, ", ".join(parts))
+
+
+def _layout_name(type_name):
+    name = type_name.split("<", 1)[0].rsplit("::", 1)[-1]
+    return name if name in _LAYOUTS else None
+
+
+try:
+    import gdb
+except ImportError:
+    gdb = None
+
+if gdb is not None:
+
+    def _gdb_raw(val):
+        ty = val.type.strip_typedefs()
+        if ty.code == gdb.TYPE_CODE_ARRAY:
+            low, high = ty.range()
+            elem_bits = ty.target().sizeof * 8
+            raw = 0
+            for i in range(low, high + 1):
+                raw |= (int(val[i]) & ((1 << elem_bits) - 1)) << (elem_bits * (i - low))
+            return raw
+        return int(val) & ((1 << (ty.sizeof * 8)) - 1)
+
+    class _Printer:
+        def __init__(self, name, val):
+            self.name = name
+            self.val = val
+
+        def to_string(self):
+            return _format(self.name, _gdb_raw(self.val["__0"]))
+
+    def _lookup(val):
+        name = _layout_name(str(val.type.strip_typedefs()))
+        return _Printer(name, val) if name is not None else None
+
+    gdb.pretty_printers.append(_lookup)
+
+
+def _lldb_summary(valobj, internal_dict):
+    name = _layout_name(valobj.GetType().GetCanonicalType().GetName())
+    data = valobj.GetChildAtIndex(0).GetData()
+    return _format(name, int.from_bytes(bytes(data.uint8s), "little"))
+
+
+def __lldb_init_module(debugger, internal_dict):
+    for name in _LAYOUTS:
+        debugger.HandleCommand(
+            'type summary add -x "^(.*::)?%s(<.+>)?$" -F %s._lldb_summary' % (name, __name__)
+        )
+"#;
+
+/// Writes a Python script containing GDB pretty-printers and LLDB summary providers that decode
+/// the given bitfields' readable fields, analogously to their automatic `Debug` implementations.
+///
+/// This is meant to be called from a test or a small helper binary, to generate a script once
+/// for all bitfields in a crate that can then be loaded into the debugger.
+pub fn write_pretty_printers(w: &mut dyn fmt::Write, layouts: &[Layout]) -> fmt::Result {
+    w.write_str(PRETTY_PRINTERS_PRELUDE)?;
+    w.write_str("_LAYOUTS = {\n")?;
+    for layout in layouts {
+        writeln!(w, "    {}: ({}, [", JsonStr(layout.name), layout.bits)?;
+        for (i, field) in layout.fields.iter().enumerate() {
+            if !field.readable {
+                continue;
+            }
+            write!(
+                w,
+                "        ({}, {}, {}, {}, {}, {}, ",
+                JsonStr(field.name),
+                field.start,
+                field.end,
+                field.stride,
+                JsonStr(field.ty),
+                if field.nested { "True" } else { "False" },
+            )?;
+            match (layout.field_enum)(i) {
+                Some((_, variants)) => {
+                    w.write_char('{')?;
+                    for (j, variant) in variants.iter().enumerate() {
+                        if j != 0 {
+                            w.write_str(", ")?;
+                        }
+                        write!(w, "{}: {}", variant.value, JsonStr(variant.name))?;
+                    }
+                    w.write_char('}')?;
+                }
+                None => w.write_str("None")?,
+            }
+            w.write_str("),\n")?;
+        }
+        w.write_str("    ]),\n")?;
+    }
+    w.write_char('}')?;
+    w.write_str(PRETTY_PRINTERS_BODY)
+}
+
+#[doc(hidden)]
+pub mod __probe {
+    //! Autoref-based dispatch used by the generated code to retrieve [`EnumInfo`] metadata only
+    //! for types that implement it.
+
+    use super::{EnumInfo, VariantInfo};
+    use core::marker::PhantomData;
+
+    pub struct EnumProbe<T: ?Sized>(PhantomData<T>);
+
+    impl<T: ?Sized> EnumProbe<T> {
+        #[allow(clippy::new_without_default)]
+        #[inline]
+        pub const fn new() -> Self {
+            EnumProbe(PhantomData)
+        }
+    }
+
+    pub trait ViaEnumInfo {
+        fn __enum_info(&self) -> Option<(&'static str, &'static [VariantInfo])>;
+    }
+
+    impl<T: EnumInfo> ViaEnumInfo for EnumProbe<T> {
+        #[inline]
+        fn __enum_info(&self) -> Option<(&'static str, &'static [VariantInfo])> {
+            Some((T::NAME, T::VARIANTS))
+        }
+    }
+
+    pub trait ViaNotEnum {
+        fn __enum_info(&self) -> Option<(&'static str, &'static [VariantInfo])>;
+    }
+
+    impl<T: ?Sized> ViaNotEnum for &EnumProbe<T> {
+        #[inline]
+        fn __enum_info(&self) -> Option<(&'static str, &'static [VariantInfo])> {
+            None
+        }
+    }
+}
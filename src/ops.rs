@@ -0,0 +1,291 @@
+//! Utility operations on raw field values, usable on their own or as field conversion functions
+//! through `get_fn` and `set_fn`, and on bit ranges of different bitfields.
+
+use crate::{Bits, SetBits};
+use core::time::Duration;
+
+/// Spreads the low 32 bits of `value` so that they occupy the even bits of the result.
+#[inline]
+const fn spread_2d(value: u32) -> u64 {
+    let mut value = value as u64;
+    value = (value | value << 16) & 0x0000_FFFF_0000_FFFF;
+    value = (value | value << 8) & 0x00FF_00FF_00FF_00FF;
+    value = (value | value << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | value << 2) & 0x3333_3333_3333_3333;
+    (value | value << 1) & 0x5555_5555_5555_5555
+}
+
+/// Gathers the even bits of `value` into the low 32 bits of the result.
+#[inline]
+const fn compact_2d(value: u64) -> u32 {
+    let mut value = value & 0x5555_5555_5555_5555;
+    value = (value | value >> 1) & 0x3333_3333_3333_3333;
+    value = (value | value >> 2) & 0x0F0F_0F0F_0F0F_0F0F;
+    value = (value | value >> 4) & 0x00FF_00FF_00FF_00FF;
+    value = (value | value >> 8) & 0x0000_FFFF_0000_FFFF;
+    (value | value >> 16) as u32
+}
+
+/// Spreads the low 21 bits of `value` so that they occupy every third bit of the result, starting
+/// from bit 0.
+#[inline]
+const fn spread_3d(value: u32) -> u64 {
+    let mut value = value as u64 & 0x1F_FFFF;
+    value = (value | value << 32) & 0x001F_0000_0000_FFFF;
+    value = (value | value << 16) & 0x001F_0000_FF00_00FF;
+    value = (value | value << 8) & 0x100F_00F0_0F00_F00F;
+    value = (value | value << 4) & 0x10C3_0C30_C30C_30C3;
+    (value | value << 2) & 0x1249_2492_4924_9249
+}
+
+/// Gathers every third bit of `value`, starting from bit 0, into the low 21 bits of the result.
+#[inline]
+const fn compact_3d(value: u64) -> u32 {
+    let mut value = value & 0x1249_2492_4924_9249;
+    value = (value | value >> 2) & 0x10C3_0C30_C30C_30C3;
+    value = (value | value >> 4) & 0x100F_00F0_0F00_F00F;
+    value = (value | value >> 8) & 0x001F_0000_FF00_00FF;
+    value = (value | value >> 16) & 0x001F_0000_0000_FFFF;
+    (value | value >> 32) as u32
+}
+
+/// Encodes 2D coordinates as a Morton (Z-order) code, interleaving their bits so that `x`
+/// occupies the even bits of the result and `y` the odd ones.
+///
+/// ```rust
+/// # use proc_bitfield::ops::{morton_decode_2d, morton_encode_2d};
+/// assert_eq!(morton_encode_2d(0b11, 0b01), 0b0111);
+/// assert_eq!(morton_decode_2d(0b0111), (0b11, 0b01));
+/// ```
+#[inline]
+pub const fn morton_encode_2d(x: u32, y: u32) -> u64 {
+    spread_2d(x) | spread_2d(y) << 1
+}
+
+/// Decodes a Morton (Z-order) code into the 2D coordinates it was encoded from with
+/// [`morton_encode_2d`].
+#[inline]
+pub const fn morton_decode_2d(code: u64) -> (u32, u32) {
+    (compact_2d(code), compact_2d(code >> 1))
+}
+
+/// Returns the `x` coordinate of a 2D Morton (Z-order) code, as encoded by [`morton_encode_2d`].
+#[inline]
+pub const fn morton_x_2d(code: u64) -> u32 {
+    compact_2d(code)
+}
+
+/// Returns the `y` coordinate of a 2D Morton (Z-order) code, as encoded by [`morton_encode_2d`].
+#[inline]
+pub const fn morton_y_2d(code: u64) -> u32 {
+    compact_2d(code >> 1)
+}
+
+/// Encodes 3D coordinates as a Morton (Z-order) code, interleaving the low 21 bits of each so
+/// that `x` occupies bits 0, 3, 6, ... of the result, `y` bits 1, 4, 7, ... and `z` bits 2, 5, 8,
+/// ...; higher bits of the coordinates are ignored.
+///
+/// ```rust
+/// # use proc_bitfield::ops::{morton_decode_3d, morton_encode_3d};
+/// assert_eq!(morton_encode_3d(0b11, 0b01, 0b10), 0b101_011);
+/// assert_eq!(morton_decode_3d(0b101_011), (0b11, 0b01, 0b10));
+/// ```
+#[inline]
+pub const fn morton_encode_3d(x: u32, y: u32, z: u32) -> u64 {
+    spread_3d(x) | spread_3d(y) << 1 | spread_3d(z) << 2
+}
+
+/// Decodes a Morton (Z-order) code into the 3D coordinates it was encoded from with
+/// [`morton_encode_3d`].
+#[inline]
+pub const fn morton_decode_3d(code: u64) -> (u32, u32, u32) {
+    (
+        compact_3d(code),
+        compact_3d(code >> 1),
+        compact_3d(code >> 2),
+    )
+}
+
+/// Copies the `SRC_START..SRC_END` bit range of `src` into the `DST_START..DST_END` bit range of
+/// `dst` (with the end bits excluded), i.e. to synchronize a field between a register and its
+/// shadow copy.
+///
+/// Both ranges must have the same width, of at most 128 bits; this is checked at compile time.
+/// The [`copy_bits!`](crate::copy_bits!) macro can be used to copy bitfield fields by name instead.
+///
+/// ```rust
+/// # use proc_bitfield::ops::copy_bits;
+/// let src = 0x1234_u16;
+/// let mut dst = 0_u32;
+/// copy_bits::<4, 12, 16, 24>(&src, &mut dst);
+/// assert_eq!(dst, 0x0023_0000);
+/// ```
+#[inline]
+pub fn copy_bits<
+    const SRC_START: usize,
+    const SRC_END: usize,
+    const DST_START: usize,
+    const DST_END: usize,
+>(
+    src: &impl Bits<u128>,
+    dst: &mut impl SetBits<u128>,
+) {
+    // Checked when the function is instantiated, as the ranges are generic parameters
+    struct Ranges<
+        const SRC_START: usize,
+        const SRC_END: usize,
+        const DST_START: usize,
+        const DST_END: usize,
+    >;
+
+    impl<
+            const SRC_START: usize,
+            const SRC_END: usize,
+            const DST_START: usize,
+            const DST_END: usize,
+        > Ranges<SRC_START, SRC_END, DST_START, DST_END>
+    {
+        const VALID: () = {
+            assert!(
+                SRC_END > SRC_START && SRC_END - SRC_START <= 128,
+                "source bit range must be non-empty and at most 128 bits wide",
+            );
+            assert!(
+                DST_END >= DST_START && DST_END - DST_START == SRC_END - SRC_START,
+                "source and destination bit ranges must have the same width",
+            );
+        };
+    }
+
+    let () = Ranges::<SRC_START, SRC_END, DST_START, DST_END>::VALID;
+    dst.set_bits::<DST_START, DST_END>(src.bits::<SRC_START, SRC_END>());
+}
+
+/// Integer types [`field_mask`] can compute masks for.
+pub trait FieldMask: Sized {
+    /// Returns a value with the `start..end` bit range set (with the end bit excluded); the range
+    /// must be non-empty and end at or before `Self::BITS`.
+    fn field_mask(start: usize, end: usize) -> Self;
+}
+
+macro_rules! impl_field_mask {
+    ($($ty: ty),*) => {$(
+        impl FieldMask for $ty {
+            #[inline]
+            fn field_mask(start: usize, end: usize) -> Self {
+                // The shift is split in two so that full-width masks don't overflow
+                ((1 as $ty) << (end - start - 1) << 1).wrapping_sub(1) << start
+            }
+        }
+    )*};
+}
+
+impl_field_mask!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Returns a mask of type `T` with the `start..end` bit range set (with the end bit excluded), as
+/// used to write fields; the range must be non-empty and end at or before the width of `T`.
+///
+/// ```rust
+/// # use proc_bitfield::ops::field_mask;
+/// assert_eq!(field_mask::<u16>(4, 12), 0xFF0);
+/// assert_eq!(field_mask::<i8>(0, 8), -1);
+/// assert_eq!(field_mask::<u128>(0, 128), u128::MAX);
+/// ```
+#[inline]
+pub fn field_mask<T: FieldMask>(start: usize, end: usize) -> T {
+    T::field_mask(start, end)
+}
+
+/// Reverses the order of the bytes in the low `bits` bits of `value`, clearing the bits above
+/// them; `bits` must be a multiple of 8 between 8 and 128.
+///
+/// ```rust
+/// # use proc_bitfield::ops::swap_bytes;
+/// assert_eq!(swap_bytes(0x12_3456, 24), 0x56_3412);
+/// assert_eq!(swap_bytes(0xFF12_3456, 16), 0x5634);
+/// ```
+#[inline]
+pub const fn swap_bytes(value: u128, bits: u32) -> u128 {
+    value.swap_bytes() >> (128 - bits)
+}
+
+/// Swaps the high and low nibbles of each byte in the low `bits` bits of `value`, clearing the
+/// bits above them; `bits` must be a multiple of 8 between 8 and 128.
+///
+/// ```rust
+/// # use proc_bitfield::ops::swap_nibbles;
+/// assert_eq!(swap_nibbles(0x12_3456, 24), 0x21_4365);
+/// assert_eq!(swap_nibbles(0xFF12_3456, 16), 0x4365);
+/// ```
+#[inline]
+pub const fn swap_nibbles(value: u128, bits: u32) -> u128 {
+    let value = value << (128 - bits) >> (128 - bits);
+    (value & 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F) << 4
+        | (value >> 4) & 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F
+}
+
+/// The rounding policy used by [`ticks_to_duration`] and [`duration_to_ticks`] when a value can't
+/// be represented exactly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round towards zero.
+    Floor,
+    /// Round away from zero.
+    Ceil,
+    /// Round to the nearest value, with ties rounded away from zero.
+    Nearest,
+}
+
+impl Rounding {
+    #[inline]
+    const fn div(self, dividend: u128, divisor: u128) -> u128 {
+        match self {
+            Rounding::Floor => dividend / divisor,
+            Rounding::Ceil => dividend.div_ceil(divisor),
+            Rounding::Nearest => dividend.saturating_add(divisor / 2) / divisor,
+        }
+    }
+}
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Converts a number of ticks of a clock running at `hz` Hz into the time they take, rounded to
+/// nanoseconds according to `rounding`.
+///
+/// ```rust
+/// # use core::time::Duration;
+/// # use proc_bitfield::ops::{ticks_to_duration, Rounding};
+/// assert_eq!(ticks_to_duration(32_768, 32_768, Rounding::Floor), Duration::from_secs(1));
+/// assert_eq!(ticks_to_duration(1, 3, Rounding::Floor), Duration::from_nanos(333_333_333));
+/// assert_eq!(ticks_to_duration(1, 3, Rounding::Ceil), Duration::from_nanos(333_333_334));
+/// ```
+#[inline]
+pub const fn ticks_to_duration(ticks: u64, hz: u64, rounding: Rounding) -> Duration {
+    let nanos = rounding.div(ticks as u128 * NANOS_PER_SEC, hz as u128);
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+/// Converts a duration into the number of ticks of a clock running at `hz` Hz it corresponds to,
+/// rounded according to `rounding` and saturated to `u64::MAX`.
+///
+/// ```rust
+/// # use core::time::Duration;
+/// # use proc_bitfield::ops::{duration_to_ticks, Rounding};
+/// assert_eq!(duration_to_ticks(Duration::from_millis(1), 32_768, Rounding::Floor), 32);
+/// assert_eq!(duration_to_ticks(Duration::from_millis(1), 32_768, Rounding::Nearest), 33);
+/// ```
+#[inline]
+pub const fn duration_to_ticks(duration: Duration, hz: u64, rounding: Rounding) -> u64 {
+    let ticks = rounding.div(
+        duration.as_nanos().saturating_mul(hz as u128),
+        NANOS_PER_SEC,
+    );
+    if ticks > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        ticks as u64
+    }
+}
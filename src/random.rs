@@ -0,0 +1,74 @@
+//! Random number sources for the `randomize` functions generated for bitfields when the `rand`
+//! feature is enabled, used to initialize registers to arbitrary (but valid) states, i.e. to test
+//! drivers against unpredictable power-on values.
+//!
+//! No specific random number generator is required: any type implementing [`RawRng`] can be
+//! used, including closures returning `u64`s (so a [`rand`](https://docs.rs/rand) generator can be
+//! plugged in with `&mut || rng.next_u64()`), and [`SplitMix64`] is provided as a small seedable
+//! generator for reproducible tests.
+
+use crate::meta::VariantInfo;
+
+/// A source of uniformly distributed random `u64`s.
+pub trait RawRng {
+    /// Returns the next random value.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns the next random value, built from two `u64`s.
+    #[inline]
+    fn next_u128(&mut self) -> u128 {
+        let low = self.next_u64() as u128;
+        (self.next_u64() as u128) << 64 | low
+    }
+}
+
+impl<F: FnMut() -> u64> RawRng for F {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// The SplitMix64 generator, a fast seedable generator with a 64-bit state.
+///
+/// It isn't cryptographically secure, and is only meant to produce reproducible sequences of
+/// values from a seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    /// Creates a generator with the given seed.
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+}
+
+impl RawRng for SplitMix64 {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Returns a random raw value for a field: the discriminant of one of the given enum variants, if
+/// any, or random bits otherwise.
+///
+/// The value is meant to be truncated to the field's width when written; discriminants are
+/// sign-extended, so negative ones are written correctly to fields with signed raw types.
+#[inline]
+pub fn raw_value<R: RawRng + ?Sized>(
+    rng: &mut R,
+    variants: Option<&'static [VariantInfo]>,
+) -> u128 {
+    match variants {
+        Some(variants) if !variants.is_empty() => {
+            variants[(rng.next_u64() % variants.len() as u64) as usize].value as u128
+        }
+        _ => rng.next_u128(),
+    }
+}
@@ -0,0 +1,93 @@
+//! Storage backends for bitfields stored outside of memory, i.e. configuration words in an
+//! external EEPROM or flash, accessed through the handles generated by the `Remote` automatic
+//! implementation.
+//!
+//! Any type implementing [`StorageBackend`] can be used, i.e. a driver for an I2C EEPROM reading
+//! and writing words at given addresses; slices and arrays of words are also backends, addressed
+//! by index, to test code using remote bitfields without hardware.
+
+use core::fmt;
+
+/// A word-addressed storage whose accesses can fail.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, remote::StorageBackend};
+/// bitfield! {
+///     pub struct Config(pub u16): Remote {
+///         pub baud_div: u8 @ 0..8,
+///         pub parity: bool @ 8,
+///     }
+/// }
+///
+/// let mut words = [0_u16; 4];
+/// let mut config = ConfigRemote::new(&mut words[..], 2);
+/// config.set_baud_div(26).unwrap();
+/// assert_eq!(config.baud_div(), Ok(26));
+/// assert_eq!(words, [0, 0, 26, 0]);
+///
+/// let mut out_of_bounds = ConfigRemote::new(&mut words[..], 4);
+/// assert!(out_of_bounds.set_parity(true).is_err());
+/// ```
+pub trait StorageBackend<S> {
+    /// The error returned when an access fails.
+    type Error;
+
+    /// Reads the word at the given address.
+    fn read_word(&mut self, address: usize) -> Result<S, Self::Error>;
+
+    /// Writes the word at the given address.
+    fn write_word(&mut self, address: usize, value: S) -> Result<(), Self::Error>;
+}
+
+impl<S, B: StorageBackend<S> + ?Sized> StorageBackend<S> for &mut B {
+    type Error = B::Error;
+
+    #[inline]
+    fn read_word(&mut self, address: usize) -> Result<S, Self::Error> {
+        (**self).read_word(address)
+    }
+
+    #[inline]
+    fn write_word(&mut self, address: usize, value: S) -> Result<(), Self::Error> {
+        (**self).write_word(address, value)
+    }
+}
+
+/// The error returned when accessing a word past the end of a slice or array backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfBounds;
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("address is out of bounds")
+    }
+}
+
+impl<S: Copy> StorageBackend<S> for [S] {
+    type Error = OutOfBounds;
+
+    #[inline]
+    fn read_word(&mut self, address: usize) -> Result<S, OutOfBounds> {
+        self.get(address).copied().ok_or(OutOfBounds)
+    }
+
+    #[inline]
+    fn write_word(&mut self, address: usize, value: S) -> Result<(), OutOfBounds> {
+        *self.get_mut(address).ok_or(OutOfBounds)? = value;
+        Ok(())
+    }
+}
+
+impl<S: Copy, const N: usize> StorageBackend<S> for [S; N] {
+    type Error = OutOfBounds;
+
+    #[inline]
+    fn read_word(&mut self, address: usize) -> Result<S, OutOfBounds> {
+        self[..].read_word(address)
+    }
+
+    #[inline]
+    fn write_word(&mut self, address: usize, value: S) -> Result<(), OutOfBounds> {
+        self[..].write_word(address, value)
+    }
+}
@@ -0,0 +1,320 @@
+//! Shared register handles, for device models where multiple subsystems hold handles to the same
+//! register.
+//!
+//! [`SharedReg`] stores the register in an [`Arc`]-shared atomic integer and can be sent across
+//! threads, while [`LocalSharedReg`] stores it in an [`Rc`]-shared [`Cell`] and is meant for
+//! single-threaded use.
+
+use crate::{cell::StorageCell, Bitfield};
+use alloc::rc::Rc;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use core::{cell::Cell, fmt, marker::PhantomData, sync::atomic::Ordering};
+
+/// Integer storage types with a corresponding atomic type.
+pub trait AtomicStorage: Copy {
+    /// The atomic type corresponding to the storage type.
+    type Atomic;
+
+    /// Creates a new atomic value initialized to `value`.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Loads the atomic's value with the given memory ordering.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores `value` into the atomic with the given memory ordering.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Repeatedly replaces the atomic's value with the one returned by `f` until no concurrent
+    /// modification happens in between, as the atomic types' `fetch_update`; returns the previous
+    /// value if `f` returned `Some`, or `Err` with the last value read otherwise.
+    fn fetch_update(
+        atomic: &Self::Atomic,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: impl FnMut(Self) -> Option<Self>,
+    ) -> Result<Self, Self>;
+
+    /// Stores `new` into the atomic if its value is `current`, as the atomic types'
+    /// `compare_exchange_weak`; may fail spuriously even if the values match. Returns the previous
+    /// value, wrapped in `Ok` if `new` was stored and in `Err` otherwise.
+    fn compare_exchange_weak(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! impl_atomic_storage {
+    ($($width: literal => $($t: ty, $atomic: ident);*);* $(;)?) => {
+        $($(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicStorage for $t {
+                type Atomic = core::sync::atomic::$atomic;
+
+                #[inline]
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    core::sync::atomic::$atomic::new(value)
+                }
+
+                #[inline]
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+
+                #[inline]
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order);
+                }
+
+                #[inline]
+                fn fetch_update(
+                    atomic: &Self::Atomic,
+                    set_order: Ordering,
+                    fetch_order: Ordering,
+                    f: impl FnMut(Self) -> Option<Self>,
+                ) -> Result<Self, Self> {
+                    atomic.fetch_update(set_order, fetch_order, f)
+                }
+
+                #[inline]
+                fn compare_exchange_weak(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange_weak(current, new, success, failure)
+                }
+            }
+        )*)*
+    };
+}
+
+impl_atomic_storage!(
+    "8" => u8, AtomicU8; i8, AtomicI8;
+    "16" => u16, AtomicU16; i16, AtomicI16;
+    "32" => u32, AtomicU32; i32, AtomicI32;
+    "64" => u64, AtomicU64; i64, AtomicI64;
+    "ptr" => usize, AtomicUsize; isize, AtomicIsize;
+);
+
+/// A thread-safe shared handle to a bitfield register, stored in an [`Arc`]-shared atomic integer.
+///
+/// Cloning the handle returns a new handle to the same register. All accesses are sequentially
+/// consistent.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, shared::SharedReg};
+/// bitfield! {
+///     pub struct IrqStatus(pub u32) {
+///         pub timer: bool @ 0,
+///         pub dma: bool @ 1,
+///     }
+/// }
+///
+/// let status = SharedReg::new(IrqStatus(0));
+/// let dma_handle = status.clone();
+/// dma_handle.update(|status| status.set_dma(true));
+/// assert!(status.get().dma());
+/// ```
+#[cfg(target_has_atomic = "ptr")]
+pub struct SharedReg<B: Bitfield>
+where
+    B::Storage: AtomicStorage,
+{
+    inner: Arc<<B::Storage as AtomicStorage>::Atomic>,
+    _bitfield: PhantomData<fn() -> B>,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<B: Bitfield> SharedReg<B>
+where
+    B::Storage: AtomicStorage,
+{
+    /// Creates a handle to a new register containing the given value.
+    #[inline]
+    pub fn new(value: B) -> Self {
+        SharedReg {
+            inner: Arc::new(B::Storage::new_atomic(value.into_storage())),
+            _bitfield: PhantomData,
+        }
+    }
+
+    /// Returns the register's current value.
+    #[inline]
+    pub fn get(&self) -> B {
+        B::from_storage(B::Storage::load(&self.inner, Ordering::SeqCst))
+    }
+
+    /// Replaces the register's value.
+    #[inline]
+    pub fn set(&self, value: B) {
+        B::Storage::store(&self.inner, value.into_storage(), Ordering::SeqCst);
+    }
+
+    /// Atomically modifies the register's value in place through `f`, returning the new value.
+    ///
+    /// `f` may be called multiple times if the register is concurrently modified through other
+    /// handles, and is called once more on the value it was last applied to in order to compute the
+    /// returned value, so it should be deterministic.
+    pub fn update(&self, mut f: impl FnMut(&mut B)) -> B {
+        let (Ok(prev) | Err(prev)) =
+            B::Storage::fetch_update(&self.inner, Ordering::SeqCst, Ordering::SeqCst, |storage| {
+                let mut value = B::from_storage(storage);
+                f(&mut value);
+                Some(value.into_storage())
+            });
+        let mut value = B::from_storage(prev);
+        f(&mut value);
+        value
+    }
+
+    /// Atomically replaces the register's value with the one returned by `f`, starting from
+    /// `current` (usually the last value read from the register) and retrying with the actual
+    /// value as long as the register was concurrently modified, so that fields left unchanged by
+    /// `f` keep the values written through other handles.
+    ///
+    /// Returns the new value if `f` returned `Some`, or the value it rejected otherwise.
+    ///
+    /// ```rust
+    /// # use proc_bitfield::{bitfield, shared::SharedReg};
+    /// bitfield! {
+    ///     #[derive(Clone, Copy, PartialEq)]
+    ///     pub struct Flags(pub u32): Debug {
+    ///         pub ready: bool @ 0,
+    ///         pub owner: u8 @ 8..16,
+    ///     }
+    /// }
+    ///
+    /// let flags = SharedReg::new(Flags(0));
+    /// let current = flags.get();
+    /// flags.update(|flags| flags.set_ready(true));
+    /// // Only claim the register if it isn't owned yet
+    /// let claim = |flags: Flags| (flags.owner() == 0).then(|| flags.with_owner(3));
+    /// assert_eq!(flags.compare_exchange_fields(current, claim), Ok(Flags(0x301)));
+    /// assert!(flags.compare_exchange_fields(current, claim).is_err());
+    /// ```
+    pub fn compare_exchange_fields(
+        &self,
+        current: B,
+        mut f: impl FnMut(B) -> Option<B>,
+    ) -> Result<B, B> {
+        let mut current = current.into_storage();
+        loop {
+            let new = match f(B::from_storage(current)) {
+                Some(new) => new.into_storage(),
+                None => return Err(B::from_storage(current)),
+            };
+            match B::Storage::compare_exchange_weak(
+                &self.inner,
+                current,
+                new,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Ok(B::from_storage(new)),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Returns `true` if both handles refer to the same register.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<B: Bitfield> Clone for SharedReg<B>
+where
+    B::Storage: AtomicStorage,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        SharedReg {
+            inner: Arc::clone(&self.inner),
+            _bitfield: PhantomData,
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<B: Bitfield + fmt::Debug> fmt::Debug for SharedReg<B>
+where
+    B::Storage: AtomicStorage,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedReg").field(&self.get()).finish()
+    }
+}
+
+/// A single-threaded shared handle to a bitfield register, stored in an [`Rc`]-shared [`Cell`].
+///
+/// Cloning the handle returns a new handle to the same register.
+pub struct LocalSharedReg<B: Bitfield> {
+    inner: Rc<Cell<B::Storage>>,
+    _bitfield: PhantomData<fn() -> B>,
+}
+
+impl<B: Bitfield> LocalSharedReg<B>
+where
+    B::Storage: Copy,
+{
+    /// Creates a handle to a new register containing the given value.
+    #[inline]
+    pub fn new(value: B) -> Self {
+        LocalSharedReg {
+            inner: Rc::new(Cell::new(value.into_storage())),
+            _bitfield: PhantomData,
+        }
+    }
+
+    /// Returns the register's current value.
+    #[inline]
+    pub fn get(&self) -> B {
+        self.inner.get_bitfield()
+    }
+
+    /// Replaces the register's value.
+    #[inline]
+    pub fn set(&self, value: B) {
+        self.inner.set_bitfield(value);
+    }
+
+    /// Modifies the register's value in place through `f`, returning the new value.
+    #[inline]
+    pub fn update(&self, f: impl FnOnce(&mut B)) -> B {
+        self.inner.update_bitfield(f);
+        self.get()
+    }
+
+    /// Returns `true` if both handles refer to the same register.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<B: Bitfield> Clone for LocalSharedReg<B> {
+    #[inline]
+    fn clone(&self) -> Self {
+        LocalSharedReg {
+            inner: Rc::clone(&self.inner),
+            _bitfield: PhantomData,
+        }
+    }
+}
+
+impl<B: Bitfield + fmt::Debug> fmt::Debug for LocalSharedReg<B>
+where
+    B::Storage: Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalSharedReg").field(&self.get()).finish()
+    }
+}
@@ -1,5 +1,6 @@
 mod arr_impls;
 mod int_impls;
+mod strided_impls;
 
 pub trait Bitfield {
     type Storage;
@@ -11,6 +12,62 @@ pub trait Bitfield {
     fn storage_mut(&mut self) -> &mut Self::Storage;
 }
 
+/// Zero-cost conversion between a type and its raw representation, to write generic code over
+/// all types backed by a given storage type.
+///
+/// This is implemented for all [`Bitfield`]s, using their storage type as the raw representation.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, RawRepr};
+/// bitfield! {
+///     pub struct Ctrl(pub u32) {
+///         pub en: bool @ 0,
+///     }
+/// }
+///
+/// fn write_reg<R: RawRepr<Raw = u32>>(regs: &mut [u32], index: usize, value: R) {
+///     regs[index] = value.into_raw();
+/// }
+///
+/// let mut regs = [0; 4];
+/// write_reg(&mut regs, 2, Ctrl(0).with_en(true));
+/// assert_eq!(regs, [0, 0, 1, 0]);
+/// assert!(Ctrl::from_raw(regs[2]).en());
+/// ```
+pub trait RawRepr: Sized {
+    /// The raw representation's type.
+    type Raw;
+
+    /// Converts `self` into its raw representation.
+    fn into_raw(self) -> Self::Raw;
+
+    /// Converts a raw representation into a value of this type.
+    fn from_raw(raw: Self::Raw) -> Self;
+}
+
+impl<T: Bitfield> RawRepr for T {
+    type Raw = T::Storage;
+
+    #[inline]
+    fn into_raw(self) -> Self::Raw {
+        self.into_storage()
+    }
+
+    #[inline]
+    fn from_raw(raw: Self::Raw) -> Self {
+        T::from_storage(raw)
+    }
+}
+
+/// Merge two values into one, i.e. to accumulate flags from multiple sources.
+///
+/// This is implemented for bitfield structs by the `Merge` automatic implementation.
+pub trait Merge {
+    /// Returns the result of merging `other` into `self`.
+    #[must_use]
+    fn merge(self, other: Self) -> Self;
+}
+
 /// Read a range of bits inside a value.
 pub trait Bits<T> {
     /// Read `self`'s `START..END` bit range (with `END` excluded) as a value of type `T`.
@@ -50,3 +107,31 @@ pub trait SetBit {
     /// Sets `self`'s specified bit to 1 if `value` is `true`, and 0 otherwise.
     fn set_bit<const BIT: usize>(&mut self, value: bool);
 }
+
+/// Read bits spaced at a fixed stride inside a value.
+pub trait StridedBits<T> {
+    /// Read `COUNT` of `self`'s bits, starting from bit `START` and spaced `STEP` bits apart, as
+    /// the low bits of a value of type `T` (with the bit at `START` being the least significant).
+    fn strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(&self) -> T;
+}
+
+/// Return a value with bits spaced at a fixed stride modified.
+pub trait WithStridedBits<T> {
+    /// Returns `self` with `COUNT` bits, starting from bit `START` and spaced `STEP` bits apart,
+    /// set to the low bits of the given value of type `T`.
+    #[must_use]
+    fn with_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+        self,
+        value: T,
+    ) -> Self;
+}
+
+/// Modify bits spaced at a fixed stride inside a value in place.
+pub trait SetStridedBits<T> {
+    /// Sets `COUNT` of `self`'s bits, starting from bit `START` and spaced `STEP` bits apart, to
+    /// the low bits of the given value of type `T`.
+    fn set_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+        &mut self,
+        value: T,
+    );
+}
@@ -1,4 +1,5 @@
 use super::{Bit, Bits, SetBit, SetBits, WithBit, WithBits};
+use crate::ops::field_mask;
 
 macro_rules! impl_bits_for_int_type {
     ($storage: ident ($storage_u: ident), $value: ident ($value_u: ident)) => {
@@ -45,8 +46,8 @@ macro_rules! impl_bits_for_int_type {
                     if START >> S_SHIFT == (END - 1) >> S_SHIFT {
                         let i = START >> S_SHIFT;
                         let written_bits = END - START;
-                        let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                            << (START & S_MASK);
+                        let mask =
+                            field_mask::<$storage>(START & S_MASK, (START & S_MASK) + written_bits);
                         self[i] =
                             (self[i] & !mask) | ((value as $storage) << (START & S_MASK) & mask);
                     } else {
@@ -54,8 +55,10 @@ macro_rules! impl_bits_for_int_type {
                             let start = START.max(i << S_SHIFT);
                             let end = END.min((i + 1) << S_SHIFT);
                             let written_bits = end - start;
-                            let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                                << (start & S_MASK);
+                            let mask = field_mask::<$storage>(
+                                start & S_MASK,
+                                (start & S_MASK) + written_bits,
+                            );
                             self[i] = (self[i] & !mask)
                                 | (((value >> (start - START)) as $storage) << (start & S_MASK)
                                     & mask);
@@ -89,8 +92,8 @@ macro_rules! impl_bits_for_int_type {
                     if START >> S_SHIFT == (END - 1) >> S_SHIFT {
                         let i = START >> S_SHIFT;
                         let written_bits = END - START;
-                        let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                            << (START & S_MASK);
+                        let mask =
+                            field_mask::<$storage>(START & S_MASK, (START & S_MASK) + written_bits);
                         self[i] =
                             (self[i] & !mask) | ((value as $storage) << (START & S_MASK) & mask);
                     } else {
@@ -98,8 +101,10 @@ macro_rules! impl_bits_for_int_type {
                             let start = START.max(i << S_SHIFT);
                             let end = END.min((i + 1) << S_SHIFT);
                             let written_bits = end - start;
-                            let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                                << (start & S_MASK);
+                            let mask = field_mask::<$storage>(
+                                start & S_MASK,
+                                (start & S_MASK) + written_bits,
+                            );
                             self[i] = (self[i] & !mask)
                                 | (((value >> (start - START)) as $storage) << (start & S_MASK)
                                     & mask);
@@ -132,9 +137,7 @@ macro_rules! impl_bits_for_int_type {
                     for i in 0..=(END - START - 1) >> V_SHIFT {
                         let start = START + (i << V_SHIFT);
                         let end = (start + V_BITS).min(END);
-                        let written_bits = end - start;
-                        let mask =
-                            ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1) << start;
+                        let mask = field_mask::<$storage>(start, end);
                         self = (self & !mask) | ((value[i] as $storage) << start & mask);
                     }
                     self
@@ -193,8 +196,10 @@ macro_rules! impl_bits_for_int_type {
                         if start >> S_SHIFT == (end - 1) >> S_SHIFT {
                             let j = start >> S_SHIFT;
                             let written_bits = end - start;
-                            let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                                << (start & S_MASK);
+                            let mask = field_mask::<$storage>(
+                                start & S_MASK,
+                                (start & S_MASK) + written_bits,
+                            );
                             self[j] = (self[j] & !mask)
                                 | ((value[i] as $storage) << (start & S_MASK) & mask);
                         } else {
@@ -202,9 +207,10 @@ macro_rules! impl_bits_for_int_type {
                                 let start_ = start.max(j << S_SHIFT);
                                 let end_ = end.min((j + 1) << S_SHIFT);
                                 let written_bits = end_ - start_;
-                                let mask = ((1 as $storage) << (written_bits - 1) << 1)
-                                    .wrapping_sub(1)
-                                    << (start_ & S_MASK);
+                                let mask = field_mask::<$storage>(
+                                    start_ & S_MASK,
+                                    (start_ & S_MASK) + written_bits,
+                                );
                                 self[j] = (self[j] & !mask)
                                     | (((value[i] >> (start_ - start)) as $storage)
                                         << (start_ & S_MASK)
@@ -249,8 +255,10 @@ macro_rules! impl_bits_for_int_type {
                         if start >> S_SHIFT == (end - 1) >> S_SHIFT {
                             let j = start >> S_SHIFT;
                             let written_bits = end - start;
-                            let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1)
-                                << (start & S_MASK);
+                            let mask = field_mask::<$storage>(
+                                start & S_MASK,
+                                (start & S_MASK) + written_bits,
+                            );
                             self[j] = (self[j] & !mask)
                                 | ((value[i] as $storage) << (start & S_MASK) & mask);
                         } else {
@@ -258,9 +266,10 @@ macro_rules! impl_bits_for_int_type {
                                 let start_ = start.max(j << S_SHIFT);
                                 let end_ = end.min((j + 1) << S_SHIFT);
                                 let written_bits = end_ - start_;
-                                let mask = ((1 as $storage) << (written_bits - 1) << 1)
-                                    .wrapping_sub(1)
-                                    << (start_ & S_MASK);
+                                let mask = field_mask::<$storage>(
+                                    start_ & S_MASK,
+                                    (start_ & S_MASK) + written_bits,
+                                );
                                 self[j] = (self[j] & !mask)
                                     | (((value[i] >> (start_ - start)) as $storage)
                                         << (start_ & S_MASK)
@@ -329,7 +338,7 @@ macro_rules! impl_bit_for_arr_int_type {
                 #[inline]
                 fn set_bit<const BIT: usize>(&mut self, value: bool) {
                     self[BIT >> SHIFT] =
-                        (self[BIT >> SHIFT] & !(1 << (BIT & MASK))) | (value as $t) << BIT;
+                        (self[BIT >> SHIFT] & !(1 << (BIT & MASK))) | (value as $t) << (BIT & MASK);
                 }
             }
 
@@ -344,7 +353,7 @@ macro_rules! impl_bit_for_arr_int_type {
                 #[inline]
                 fn set_bit<const BIT: usize>(&mut self, value: bool) {
                     self[BIT >> SHIFT] =
-                        (self[BIT >> SHIFT] & !(1 << (BIT & MASK))) | (value as $t) << BIT;
+                        (self[BIT >> SHIFT] & !(1 << (BIT & MASK))) | (value as $t) << (BIT & MASK);
                 }
             }
         }
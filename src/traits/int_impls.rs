@@ -1,4 +1,5 @@
 use super::{Bit, Bits, SetBit, SetBits, WithBit, WithBits};
+use crate::ops::field_mask;
 
 macro_rules! impl_bits_for_int_type {
     ($storage: ty, $value: ty) => {
@@ -7,15 +8,20 @@ macro_rules! impl_bits_for_int_type {
             fn bits<const START: usize, const END: usize>(&self) -> $value {
                 const VALUE_BITS: usize = <$value>::BITS as usize;
                 let read_bits = END - START;
-                ((*self >> START) as $value) << (VALUE_BITS - read_bits) >> (VALUE_BITS - read_bits)
+                let value = (*self >> START) as $value;
+                // Ranges at least as wide as the value type are truncated, without extension
+                if read_bits >= VALUE_BITS {
+                    value
+                } else {
+                    value << (VALUE_BITS - read_bits) >> (VALUE_BITS - read_bits)
+                }
             }
         }
 
         impl WithBits<$value> for $storage {
             #[inline]
             fn with_bits<const START: usize, const END: usize>(self, value: $value) -> Self {
-                let written_bits = END - START;
-                let mask = ((1 as $storage) << (written_bits - 1) << 1).wrapping_sub(1) << START;
+                let mask = field_mask::<$storage>(START, END);
                 (self & !mask) | ((value as $storage) << START & mask)
             }
         }
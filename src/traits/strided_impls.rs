@@ -0,0 +1,127 @@
+use super::{SetStridedBits, StridedBits, WithStridedBits};
+
+macro_rules! impl_strided_bits_for_int_type {
+    ($storage: ty, $value: ty) => {
+        impl StridedBits<$value> for $storage {
+            #[inline]
+            fn strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &self,
+            ) -> $value {
+                const V_BITS: usize = <$value>::BITS as usize;
+                let mut value: $value = 0;
+                for i in 0..COUNT {
+                    value |= ((*self >> (START + i * STEP)) & 1) as $value << i;
+                }
+                value << (V_BITS - COUNT) >> (V_BITS - COUNT)
+            }
+        }
+
+        impl WithStridedBits<$value> for $storage {
+            #[inline]
+            fn with_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                mut self,
+                value: $value,
+            ) -> Self {
+                for i in 0..COUNT {
+                    let bit = START + i * STEP;
+                    self = (self & !(1 << bit)) | (((value >> i) & 1) as $storage) << bit;
+                }
+                self
+            }
+        }
+
+        impl SetStridedBits<$value> for $storage {
+            #[inline]
+            fn set_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &mut self,
+                value: $value,
+            ) {
+                *self = self.with_strided_bits::<START, STEP, COUNT>(value);
+            }
+        }
+    };
+}
+
+macro_rules! impl_strided_bits_for_arr_int_type {
+    ($storage: ty, $value: ty) => {
+        impl<const N: usize> StridedBits<$value> for [$storage; N] {
+            #[inline]
+            fn strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &self,
+            ) -> $value {
+                self[..].strided_bits::<START, STEP, COUNT>()
+            }
+        }
+
+        impl<const N: usize> WithStridedBits<$value> for [$storage; N] {
+            #[inline]
+            fn with_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                mut self,
+                value: $value,
+            ) -> Self {
+                self[..].set_strided_bits::<START, STEP, COUNT>(value);
+                self
+            }
+        }
+
+        impl<const N: usize> SetStridedBits<$value> for [$storage; N] {
+            #[inline]
+            fn set_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &mut self,
+                value: $value,
+            ) {
+                self[..].set_strided_bits::<START, STEP, COUNT>(value);
+            }
+        }
+
+        impl StridedBits<$value> for [$storage] {
+            #[inline]
+            fn strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &self,
+            ) -> $value {
+                const S_SHIFT: u32 = <$storage>::BITS.trailing_zeros();
+                const S_MASK: usize = <$storage>::BITS as usize - 1;
+                const V_BITS: usize = <$value>::BITS as usize;
+                let mut value: $value = 0;
+                for i in 0..COUNT {
+                    let bit = START + i * STEP;
+                    value |= ((self[bit >> S_SHIFT] >> (bit & S_MASK)) & 1) as $value << i;
+                }
+                value << (V_BITS - COUNT) >> (V_BITS - COUNT)
+            }
+        }
+
+        impl SetStridedBits<$value> for [$storage] {
+            #[inline]
+            fn set_strided_bits<const START: usize, const STEP: usize, const COUNT: usize>(
+                &mut self,
+                value: $value,
+            ) {
+                const S_SHIFT: u32 = <$storage>::BITS.trailing_zeros();
+                const S_MASK: usize = <$storage>::BITS as usize - 1;
+                for i in 0..COUNT {
+                    let bit = START + i * STEP;
+                    let elem = &mut self[bit >> S_SHIFT];
+                    *elem = (*elem & !(1 << (bit & S_MASK)))
+                        | (((value >> i) & 1) as $storage) << (bit & S_MASK);
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_strided_bits_for_int_types {
+    (=> $($dst_ty: ty),*) => {};
+    ($src_ty: ty $(, $other_src_ty: ty)* => $($dst_ty: ty),*) => {
+        $(
+            impl_strided_bits_for_int_type!($src_ty, $dst_ty);
+            impl_strided_bits_for_arr_int_type!($src_ty, $dst_ty);
+        )*
+        impl_strided_bits_for_int_types!($($other_src_ty),* => $($dst_ty),*);
+    };
+}
+
+impl_strided_bits_for_int_types!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+        => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
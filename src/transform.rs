@@ -0,0 +1,116 @@
+//! Whole-register transforms, for raw values that are stored encoded (i.e. XORed with a key or
+//! with their bits scrambled, as in some lock and ID registers and savegame formats) and decoded
+//! by bitfields declared with the `transform` option.
+
+/// A reversible transform between encoded raw values and the decoded values seen by a bitfield's
+/// fields.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, transform::{self, Transform}};
+/// /// Swaps the bit pairs of each nibble.
+/// struct Scramble;
+///
+/// const TABLE: [u8; 8] = [1, 0, 3, 2, 5, 4, 7, 6];
+///
+/// impl Transform<u8> for Scramble {
+///     fn decode(raw: u8) -> u8 {
+///         transform::permute_bits(raw as u128, &TABLE) as u8
+///     }
+///
+///     fn encode(value: u8) -> u8 {
+///         transform::unpermute_bits(value as u128, &TABLE) as u8
+///     }
+/// }
+///
+/// bitfield! {
+///     pub struct Lock(pub u8): options(transform = Scramble) {
+///         pub locked: bool @ 0,
+///         pub key: u8 @ 4..8,
+///     }
+/// }
+///
+/// let lock = Lock::from_encoded(0x92);
+/// assert!(lock.locked());
+/// assert_eq!(lock.key(), 6);
+/// assert_eq!(lock.into_encoded(), 0x92);
+/// ```
+pub trait Transform<S> {
+    /// Decodes a raw value.
+    fn decode(raw: S) -> S;
+
+    /// Encodes a value, so that decoding it returns it back.
+    fn encode(value: S) -> S;
+}
+
+/// A transform XORing raw values with a key, truncated to the storage type's width.
+///
+/// ```rust
+/// # use proc_bitfield::{bitfield, transform::Xor};
+/// bitfield! {
+///     pub struct ChipId(pub u16): options(transform = Xor<0xA5A5>) {
+///         pub revision: u8 @ 0..4,
+///         pub part: u16 @ 4..16,
+///     }
+/// }
+///
+/// let id = ChipId::from_encoded(0xA5A7);
+/// assert_eq!(id.revision(), 2);
+/// assert_eq!(id.part(), 0);
+/// ```
+pub struct Xor<const KEY: u128>;
+
+macro_rules! impl_xor {
+    ($($ty: ty),*) => {
+        $(
+            impl<const KEY: u128> Transform<$ty> for Xor<KEY> {
+                #[inline]
+                fn decode(raw: $ty) -> $ty {
+                    raw ^ KEY as $ty
+                }
+
+                #[inline]
+                fn encode(value: $ty) -> $ty {
+                    value ^ KEY as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_xor!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Gathers the bits of a value according to a scramble table: bit `i` of the result is bit
+/// `table[i]` of `value`, for each of the table's entries.
+///
+/// # Panics
+/// Panics if any of the table's entries is 128 or more, or if the table has more than 128
+/// entries.
+#[inline]
+pub const fn permute_bits(value: u128, table: &[u8]) -> u128 {
+    assert!(table.len() <= 128);
+    let mut result = 0;
+    let mut i = 0;
+    while i < table.len() {
+        result |= (value >> table[i] & 1) << i;
+        i += 1;
+    }
+    result
+}
+
+/// Scatters the bits of a value according to a scramble table, reversing [`permute_bits`]: bit
+/// `table[i]` of the result is bit `i` of `value`, for each of the table's entries.
+///
+/// # Panics
+/// Panics if any of the table's entries is 128 or more, or if the table has more than 128
+/// entries.
+#[inline]
+pub const fn unpermute_bits(value: u128, table: &[u8]) -> u128 {
+    assert!(table.len() <= 128);
+    let mut result = 0;
+    let mut i = 0;
+    while i < table.len() {
+        result |= (value >> i & 1) << table[i];
+        i += 1;
+    }
+    result
+}
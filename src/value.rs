@@ -0,0 +1,106 @@
+//! Dynamically-typed field values, to inspect bitfields without knowing their types in advance
+//! (i.e. to feed register state to debugger frontends).
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// A dynamically-typed field value, as returned by the generated `to_value_map` functions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue {
+    /// A boolean value.
+    Bool(bool),
+    /// An unsigned integer value.
+    UInt(u128),
+    /// A signed integer value.
+    Int(i128),
+    /// An enum variant, identified by its name.
+    Enum(&'static str),
+    /// An array of values.
+    Array(Vec<FieldValue>),
+    /// A nested bitfield's fields.
+    Bitfield(BTreeMap<&'static str, FieldValue>),
+    /// A value that failed to convert from its raw representation (i.e. an error returned by a
+    /// fallible getter).
+    Invalid,
+    /// A value of a type that doesn't implement [`ToFieldValue`].
+    Opaque,
+}
+
+/// Converts a value into a [`FieldValue`].
+///
+/// This is implemented for builtin integer types, `bool`, arrays, `Result`s (with errors being
+/// converted to [`FieldValue::Invalid`]), enums deriving [`ConvRaw`](crate::ConvRaw) and all
+/// bitfield structs.
+pub trait ToFieldValue {
+    fn to_field_value(&self) -> FieldValue;
+}
+
+impl ToFieldValue for bool {
+    #[inline]
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_field_value_for_ints {
+    ($variant: ident; $($t: ty),*) => {
+        $(
+            impl ToFieldValue for $t {
+                #[inline]
+                fn to_field_value(&self) -> FieldValue {
+                    FieldValue::$variant(*self as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_field_value_for_ints!(UInt; u8, u16, u32, u64, u128, usize);
+impl_to_field_value_for_ints!(Int; i8, i16, i32, i64, i128, isize);
+
+impl<T: ToFieldValue, const N: usize> ToFieldValue for [T; N] {
+    fn to_field_value(&self) -> FieldValue {
+        FieldValue::Array(self.iter().map(ToFieldValue::to_field_value).collect())
+    }
+}
+
+impl<T: ToFieldValue, E> ToFieldValue for Result<T, E> {
+    #[inline]
+    fn to_field_value(&self) -> FieldValue {
+        match self {
+            Ok(value) => value.to_field_value(),
+            Err(_) => FieldValue::Invalid,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __probe {
+    //! Autoref-based dispatch used by the generated code to fall back to [`FieldValue::Opaque`]
+    //! for field types that don't implement [`ToFieldValue`].
+
+    use super::{FieldValue, ToFieldValue};
+
+    pub struct Probe<'a, T>(pub &'a T);
+
+    pub trait ViaToFieldValue {
+        fn __to_field_value(&self) -> FieldValue;
+    }
+
+    impl<T: ToFieldValue> ViaToFieldValue for Probe<'_, T> {
+        #[inline]
+        fn __to_field_value(&self) -> FieldValue {
+            self.0.to_field_value()
+        }
+    }
+
+    pub trait ViaOpaque {
+        fn __to_field_value(&self) -> FieldValue;
+    }
+
+    impl<T> ViaOpaque for &Probe<'_, T> {
+        #[inline]
+        fn __to_field_value(&self) -> FieldValue {
+            FieldValue::Opaque
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! A versioned wire format for bitfields, to store them (i.e. in savestates) in a way that allows
+//! their layout to evolve.
+//!
+//! An encoded bitfield consists of a version byte followed by its storage's little-endian bytes.
+//! When decoding a value encoded with an older version, [`Versioned::migrate`] is called to
+//! convert its storage bytes, which can have a different length, to the current layout.
+
+use crate::Bitfield;
+use core::fmt;
+
+/// Storage types that can be converted to and from a fixed number of little-endian bytes.
+pub trait StorageBytes: Sized {
+    /// The number of bytes a value is encoded as.
+    const LEN: usize;
+
+    /// Writes the value's little-endian bytes into `bytes`, which is exactly `LEN` bytes long.
+    fn write_le_bytes(&self, bytes: &mut [u8]);
+
+    /// Reads a value from its little-endian bytes, `bytes` being exactly `LEN` bytes long.
+    ///
+    /// Fails with [`Error::OutOfRange`] if the encoded value doesn't fit in the type on the
+    /// current target.
+    fn read_le_bytes(bytes: &[u8]) -> Result<Self, Error>;
+}
+
+macro_rules! impl_storage_bytes_for_ints {
+    ($($t: ty),*) => {
+        $(
+            impl StorageBytes for $t {
+                const LEN: usize = core::mem::size_of::<$t>();
+
+                #[inline]
+                fn write_le_bytes(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+
+                #[inline]
+                fn read_le_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                    let mut le_bytes = [0; core::mem::size_of::<$t>()];
+                    le_bytes.copy_from_slice(bytes);
+                    Ok(<$t>::from_le_bytes(le_bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_storage_bytes_for_ints!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+// Pointer-sized integers are always encoded as 64-bit values, so that encoded bitfields can be
+// exchanged between 32-bit and 64-bit targets; decoding values that don't fit on narrower targets
+// fails
+macro_rules! impl_storage_bytes_for_ptr_sized_ints {
+    ($($t: ty => $encoded_t: ty),*) => {
+        $(
+            impl StorageBytes for $t {
+                const LEN: usize = core::mem::size_of::<$encoded_t>();
+
+                #[inline]
+                fn write_le_bytes(&self, bytes: &mut [u8]) {
+                    (*self as $encoded_t).write_le_bytes(bytes);
+                }
+
+                #[inline]
+                fn read_le_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                    <$t>::try_from(<$encoded_t>::read_le_bytes(bytes)?)
+                        .map_err(|_| Error::OutOfRange)
+                }
+            }
+        )*
+    };
+}
+
+impl_storage_bytes_for_ptr_sized_ints!(usize => u64, isize => i64);
+
+impl<T: StorageBytes + Copy + Default, const N: usize> StorageBytes for [T; N] {
+    const LEN: usize = T::LEN * N;
+
+    fn write_le_bytes(&self, bytes: &mut [u8]) {
+        for (elem, bytes) in self.iter().zip(bytes.chunks_exact_mut(T::LEN)) {
+            elem.write_le_bytes(bytes);
+        }
+    }
+
+    fn read_le_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut result = [T::default(); N];
+        for (elem, bytes) in result.iter_mut().zip(bytes.chunks_exact(T::LEN)) {
+            *elem = T::read_le_bytes(bytes)?;
+        }
+        Ok(result)
+    }
+}
+
+/// An error that occurred while encoding or decoding a versioned bitfield.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer's length doesn't match the encoded bitfield's length.
+    InvalidLength { expected: usize, found: usize },
+    /// The encoded value's version is newer than the bitfield's current one, or
+    /// [`Versioned::migrate`] doesn't support it.
+    UnsupportedVersion(u8),
+    /// The encoded storage value doesn't fit in the storage type on the current target (i.e. a
+    /// `usize` encoded on a 64-bit target and decoded on a 32-bit one).
+    OutOfRange,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidLength { expected, found } => {
+                write!(
+                    f,
+                    "invalid length: expected {expected} bytes, found {found}"
+                )
+            }
+            Error::UnsupportedVersion(version) => write!(f, "unsupported version {version}"),
+            Error::OutOfRange => f.write_str("value out of range for the storage type"),
+        }
+    }
+}
+
+/// A bitfield with a versioned wire format.
+pub trait Versioned: Bitfield
+where
+    Self::Storage: StorageBytes,
+{
+    /// The version of the bitfield's current layout.
+    const VERSION: u8;
+
+    /// Converts the storage bytes of a value encoded with an older version of the bitfield's
+    /// layout to a raw value for the current one; `old_version` is always lower than
+    /// [`VERSION`](Self::VERSION).
+    ///
+    /// `bytes` holds everything following the version byte, and its length isn't checked, as older
+    /// layouts can have a different storage type; [`read_storage`] can be used to read it as the
+    /// older storage type.
+    ///
+    /// By default, all older versions are rejected.
+    #[inline]
+    fn migrate(old_version: u8, bytes: &[u8]) -> Result<Self::Storage, Error> {
+        let _ = bytes;
+        Err(Error::UnsupportedVersion(old_version))
+    }
+}
+
+/// Reads a storage value from its little-endian bytes, which must be exactly
+/// [`S::LEN`](StorageBytes::LEN) bytes long.
+pub fn read_storage<S: StorageBytes>(bytes: &[u8]) -> Result<S, Error> {
+    if bytes.len() != S::LEN {
+        return Err(Error::InvalidLength {
+            expected: S::LEN,
+            found: bytes.len(),
+        });
+    }
+    S::read_le_bytes(bytes)
+}
+
+/// Returns the length of `T`'s encoded values in bytes.
+#[inline]
+pub const fn encoded_len<T: Versioned>() -> usize
+where
+    T::Storage: StorageBytes,
+{
+    1 + T::Storage::LEN
+}
+
+/// Encodes a bitfield using its current version into `bytes`, which must be exactly
+/// [`encoded_len::<T>()`](encoded_len) bytes long.
+pub fn encode<T: Versioned>(value: &T, bytes: &mut [u8]) -> Result<(), Error>
+where
+    T::Storage: StorageBytes,
+{
+    let expected = encoded_len::<T>();
+    if bytes.len() != expected {
+        return Err(Error::InvalidLength {
+            expected,
+            found: bytes.len(),
+        });
+    }
+    bytes[0] = T::VERSION;
+    value.storage().write_le_bytes(&mut bytes[1..]);
+    Ok(())
+}
+
+/// Decodes a bitfield from `bytes`, migrating its storage bytes with [`Versioned::migrate`] if it
+/// was encoded with an older version.
+///
+/// The length of `bytes` is only checked against [`encoded_len::<T>()`](encoded_len) for the
+/// current version, as older versions can have different lengths.
+pub fn decode<T: Versioned>(bytes: &[u8]) -> Result<T, Error>
+where
+    T::Storage: StorageBytes,
+{
+    let Some((&version, storage_bytes)) = bytes.split_first() else {
+        return Err(Error::InvalidLength {
+            expected: encoded_len::<T>(),
+            found: 0,
+        });
+    };
+    let raw = match version.cmp(&T::VERSION) {
+        // Lengths are reported for the whole encoded value, including the version byte
+        core::cmp::Ordering::Equal => read_storage(storage_bytes).map_err(|err| match err {
+            Error::InvalidLength { .. } => Error::InvalidLength {
+                expected: encoded_len::<T>(),
+                found: bytes.len(),
+            },
+            err => err,
+        })?,
+        core::cmp::Ordering::Less => T::migrate(version, storage_bytes)?,
+        core::cmp::Ordering::Greater => return Err(Error::UnsupportedVersion(version)),
+    };
+    Ok(T::from_storage(raw))
+}
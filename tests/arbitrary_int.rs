@@ -0,0 +1,58 @@
+#![cfg(feature = "arbitrary-int")]
+
+use proc_bitfield::bitfield;
+
+use arbitrary_int::{u1, u12, u3};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct DmaCtrl(pub u64): Debug {
+        pub en: arbitrary_int::u1 @ 0,
+        pub mode: arbitrary_int::u3 = 2 @ 1..4,
+        pub count: arbitrary_int::u12 @ 4..16,
+        pub addr: ::arbitrary_int::u24 @ 16..40,
+        pub prio: arbitrary_int::u3 [read_only] @ 40..43,
+        pub lanes: arbitrary_int::u3; 2 @ 43..49,
+        pub packed: arbitrary_int::u3,
+
+        pub const PRESET = { mode: 5, count: 0xFFF };
+    }
+}
+
+#[test]
+fn arbitrary_int_fields() {
+    let ctrl = DmaCtrl::DEFAULT
+        .with_en(u1::new(1))
+        .with_addr(arbitrary_int::u24::new(0xAB_CDEF))
+        .with_lanes(1, u3::new(6))
+        .with_packed(u3::new(7));
+    assert_eq!(ctrl.mode(), u3::new(2));
+    assert_eq!(ctrl.addr().value(), 0xAB_CDEF);
+    assert_eq!(ctrl.lanes(1), u3::new(6));
+    assert_eq!(ctrl.0, 0xF_80AB_CDEF_0005);
+
+    let mut preset = DmaCtrl::PRESET;
+    assert_eq!(preset.count(), u12::new(0xFFF));
+    preset.set_count(u12::new(0x123));
+    assert_eq!(DmaCtrl(0x700_0000_0000).prio(), u3::new(7));
+    assert_eq!(preset.0, 0x123A);
+}
+
+// Types that only share their names with `arbitrary-int`'s aren't affected by the feature
+#[allow(non_camel_case_types)]
+type u4 = u8;
+
+bitfield! {
+    pub struct Nibbles(pub u8) {
+        pub low: u4 @ 0..4,
+        pub high: u4 @ 4..8,
+    }
+}
+
+#[test]
+fn arbitrary_int_unqualified_names() {
+    let nibbles = Nibbles(0).with_low(0x1F).with_high(3);
+    assert_eq!(nibbles.low(), 0xF);
+    assert_eq!(nibbles.high(), 3_u8);
+    assert_eq!(nibbles.0, 0x3F);
+}
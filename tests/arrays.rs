@@ -0,0 +1,52 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ChannelCtrl(pub u32): Debug {
+        pub channel_enable: bool; 8 @ 0..8,
+        pub gain: u8; 4 @ 8..24,
+        pub status: u8; 2 [read_only] @ 24; 4,
+        pub calc enabled_count -> u32 = self.0.count_ones(),
+        pub mode: u8; 2 [get u16] @ 28..32,
+    }
+}
+
+#[test]
+fn array_fields() {
+    let mut ctrl = ChannelCtrl(0);
+    ctrl.set_channel_enable(3, true);
+    ctrl.set_channel_enable_7(true);
+    assert_eq!(ctrl.0, 0x88);
+    assert!(ctrl.channel_enable(3) && ctrl.channel_enable_3());
+    assert!(!ctrl.channel_enable(0));
+
+    let ctrl = ctrl.with_gain(1, 0xA).with_gain_3(0x5);
+    assert_eq!(ctrl.0, 0x50_A088);
+    assert_eq!(ctrl.gain(1), 0xA);
+    assert_eq!(ctrl.gain(3), 0x5);
+
+    let ctrl = ChannelCtrl(0x0C00_0000);
+    assert_eq!(ctrl.status(0), 0);
+    assert_eq!(ctrl.status(1), 3);
+    assert_eq!(ctrl.enabled_count(), 2);
+
+    let ctrl = ChannelCtrl(0).with_mode_1(2);
+    assert_eq!(ctrl.mode_1(), 2_u16);
+    assert_eq!(ctrl.0, 0x8000_0000);
+
+    let names = ChannelCtrl::FIELDS
+        .iter()
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        &names[..3],
+        ["channel_enable_0", "channel_enable_1", "channel_enable_2"]
+    );
+    assert_eq!(names.len(), 16);
+}
+
+#[test]
+#[should_panic = "index out of bounds: the len is 8 but the index is 8"]
+fn array_fields_out_of_bounds() {
+    let _ = ChannelCtrl(0).channel_enable(8);
+}
@@ -0,0 +1,60 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    pub struct Ctrl(pub u16): Debug, Valid {
+        pub en: bool @ 0,
+        pub mode: u8 [try_get Mode] @ 1..3,
+    }
+}
+
+bitfield! {
+    pub struct Wide(pub u32; 2) {
+        pub en: bool @ 40,
+    }
+}
+
+bitfield! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ExplicitClone(pub u8) {
+        pub en: bool @ 0,
+    }
+}
+
+bitfield! {
+    pub struct NotCopy(pub u8): options(no_copy) {
+        pub en: bool @ 0,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mode(u8);
+
+impl TryFrom<u8> for Mode {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, u8> {
+        if value < 3 {
+            Ok(Mode(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+fn assert_copy<T: Copy>(_: T) {}
+
+#[test]
+fn auto_copy_derives() {
+    let ctrl = Ctrl(0).with_en(true);
+    let copy = ctrl;
+    assert!(ctrl.en() && copy.en());
+    assert_copy(ValidCtrl::new(ctrl).unwrap());
+    assert_copy(Wide([0; 2]));
+
+    let explicit = ExplicitClone(1);
+    assert_eq!(explicit.clone(), explicit);
+
+    let not_copy = NotCopy(1);
+    let moved = not_copy;
+    assert!(moved.en());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "binfmt")]
+
+use proc_bitfield::binfmt::{
+    ElfSectionFlags, ElfSegmentFlags, ElfSymbolBinding, ElfSymbolInfo, ElfSymbolType,
+    PeFileCharacteristics, PeSectionAlign, PeSectionCharacteristics,
+};
+
+#[test]
+fn binfmt_elf_flags() {
+    // `.text` and `.tbss`
+    assert!(ElfSectionFlags(0x6).set_flags().eq(["alloc", "execinstr"]));
+    assert!(ElfSectionFlags(0x403)
+        .set_flags()
+        .eq(["write", "alloc", "tls"]));
+    assert_eq!(
+        ElfSectionFlags::mask_from_names(&["alloc", "merge", "strings"]),
+        Ok(ElfSectionFlags(0x32))
+    );
+    assert_eq!(ElfSectionFlags(0xF000_0000).mask_proc(), 0xF);
+
+    let flags = ElfSegmentFlags(0x5);
+    assert!(flags.r() && flags.x() && !flags.w());
+}
+
+#[test]
+fn binfmt_elf_symbol_info() {
+    let info = ElfSymbolInfo(0x12);
+    assert_eq!(info.binding(), Ok(ElfSymbolBinding::Global));
+    assert_eq!(info.ty(), Ok(ElfSymbolType::Func));
+    assert_eq!(
+        ElfSymbolInfo(0)
+            .with_binding(ElfSymbolBinding::GnuUnique)
+            .with_ty(ElfSymbolType::Object)
+            .0,
+        0xA1
+    );
+    assert!(ElfSymbolInfo(0x3D).binding().is_err());
+    assert!(ElfSymbolInfo(0x3D).ty().is_err());
+}
+
+#[test]
+fn binfmt_pe_characteristics() {
+    // A typical 64-bit DLL
+    let file = PeFileCharacteristics(0x2022);
+    assert!(file
+        .set_flags()
+        .eq(["executable_image", "large_address_aware", "dll"]));
+
+    // `.text` in an object file
+    let section = PeSectionCharacteristics(0x6050_0020);
+    assert!(section.cnt_code() && section.mem_execute() && section.mem_read());
+    assert!(!section.mem_write());
+    assert_eq!(section.align(), Ok(PeSectionAlign::Align16));
+    assert_eq!(section.align().map(PeSectionAlign::bytes), Ok(16));
+    assert_eq!(PeSectionAlign::Align8192.bytes(), 8192);
+    assert_eq!(PeSectionAlign::Default.bytes(), 16);
+    assert!(PeSectionCharacteristics(0x00F0_0000).align().is_err());
+    assert_eq!(
+        PeSectionCharacteristics(0)
+            .with_align(PeSectionAlign::Align4096)
+            .0,
+        0x00D0_0000
+    );
+}
@@ -0,0 +1,40 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Sample(pub bits 24): Debug, FromStorage, IntoStorage {
+        pub value: i32 @ ..,
+        pub low: u8 @ 0..8,
+        pub sign: bool @ 23,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Packed12(pub bits 12): Debug {
+        pub a: u8 @ 0..6,
+        pub b: u8 @ 6..12,
+    }
+}
+
+#[test]
+fn bits_storage() {
+    assert_eq!(Sample::BITS, 24);
+    assert_eq!(Packed12::BITS, 12);
+    assert_eq!(core::mem::size_of::<Sample>(), 4);
+    assert_eq!(core::mem::size_of::<Packed12>(), 2);
+
+    let sample = Sample(0).with_value(-1);
+    assert_eq!(sample.0, 0x00FF_FFFF);
+    assert!(sample.sign());
+    assert_eq!(sample.value(), -1);
+
+    assert_eq!(Sample::from(0xFF12_3456).0, 0x12_3456);
+    assert_eq!(u32::from(Sample::from(0x12_3456)), 0x12_3456);
+    assert_eq!(Sample(0x12_3456).to_le_bytes(), [0x56, 0x34, 0x12]);
+    assert_eq!(Sample(0x12_3456).to_be_bytes(), [0x12, 0x34, 0x56]);
+    assert_eq!(Sample::from_le_bytes([0x56, 0x34, 0x12]), Sample(0x12_3456));
+    assert_eq!(Sample::from_be_bytes([0x12, 0x34, 0x56]), Sample(0x12_3456));
+
+    assert_eq!(Packed12(0).with_b(0x3F).0, 0xFC0);
+}
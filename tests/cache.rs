@@ -0,0 +1,66 @@
+use proc_bitfield::{bitfield, cache::Cached};
+use std::cell::Cell;
+
+thread_local! {
+    static CONVERSIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Table(Vec<u8>);
+
+impl From<u8> for Table {
+    fn from(len: u8) -> Self {
+        CONVERSIONS.with(|conversions| conversions.set(conversions.get() + 1));
+        Table((0..len).collect())
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u16): Debug {
+        pub table: u8 [get_cached Table] @ 0..4,
+        pub other: u8 @ 8..16,
+    }
+}
+
+fn conversions() -> usize {
+    CONVERSIONS.with(Cell::get)
+}
+
+#[test]
+fn cache_basic_functionality() {
+    let mut cache = Cached::new();
+    assert_eq!(cache.get(), None);
+
+    let mut ctrl = Ctrl(3);
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0, 1, 2]));
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0, 1, 2]));
+    assert_eq!(conversions(), 1);
+
+    // Writes to other fields don't invalidate the cache
+    ctrl.set_other(0xFF);
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0, 1, 2]));
+    assert_eq!(conversions(), 1);
+
+    ctrl.set_table(2);
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0, 1]));
+    assert_eq!(conversions(), 2);
+
+    ctrl.0 = 0xFF01;
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0]));
+    assert_eq!(conversions(), 3);
+    assert_eq!(cache.get(), Some(&Table(vec![0])));
+
+    cache.invalidate();
+    assert_eq!(cache.get(), None);
+    assert_eq!(ctrl.table(&mut cache), &Table(vec![0]));
+    assert_eq!(conversions(), 4);
+}
+
+#[test]
+fn cache_debug() {
+    assert_eq!(
+        format!("{:?}", Ctrl(0x0102)),
+        "Ctrl { 0: 258, table: Table([0, 1]), other: 1 }"
+    );
+}
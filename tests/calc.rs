@@ -0,0 +1,23 @@
+use proc_bitfield::bitfield;
+
+const CLOCK: u32 = 48_000_000;
+
+bitfield! {
+    pub struct UartBaud(pub u32) {
+        pub div: u16 @ 0..16,
+        /// The resulting baud rate for the given input clock frequency.
+        pub calc baud(clock: u32) -> u32 = clock / (16 * self.div() as u32),
+        pub fast: bool @ 16,
+        pub calc default_baud -> u32 = self.baud(CLOCK) * if self.fast() { 2 } else { 1 },
+        pub calc: u8 @ 24..32,
+    }
+}
+
+#[test]
+fn calc_accessors() {
+    let uart = UartBaud(26);
+    assert_eq!(uart.baud(CLOCK), 115_384);
+    assert_eq!(uart.default_baud(), 115_384);
+    assert_eq!(uart.with_fast(true).default_baud(), 230_768);
+    assert_eq!(UartBaud(0x0500_0000).calc(), 5);
+}
@@ -0,0 +1,47 @@
+use core::cell::{Cell, RefCell};
+use proc_bitfield::{bitfield, cell::StorageCell};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Ctrl(pub u32) {
+        pub irq_en: bool @ 0,
+        pub mode: u8 @ 4..8,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Wide(pub [u16; 2]) {
+        pub hi: u16 @ 16..32,
+    }
+}
+
+#[test]
+fn cell_basic_functionality() {
+    let ctrl = Cell::new(0_u32);
+    assert!(!ctrl.get_bitfield::<Ctrl>().irq_en());
+
+    let old_mode = ctrl.update_bitfield(|ctrl: &mut Ctrl| {
+        let old_mode = ctrl.mode();
+        ctrl.set_irq_en(true);
+        ctrl.set_mode(0xA);
+        old_mode
+    });
+    assert_eq!(old_mode, 0);
+    assert_eq!(ctrl.get(), 0xA1);
+
+    ctrl.set_bitfield(Ctrl(0).with_mode(2));
+    assert_eq!(ctrl.get(), 0x20);
+    assert_eq!(ctrl.get_bitfield::<Ctrl>(), Ctrl(0x20));
+}
+
+#[test]
+fn cell_ref_cell() {
+    let wide = RefCell::new([0_u16; 2]);
+    wide.update_bitfield(|wide: &mut Wide| wide.set_hi(0x1234));
+    assert_eq!(*wide.borrow(), [0, 0x1234]);
+    assert_eq!(wide.get_bitfield::<Wide>().hi(), 0x1234);
+
+    wide.set_bitfield(Wide([1, 2]));
+    assert_eq!(*wide.borrow(), [1, 2]);
+}
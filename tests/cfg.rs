@@ -0,0 +1,65 @@
+use proc_bitfield::{bitfield, ConvRaw, Merge};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    On,
+    Auto,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u16): Debug, Display, Valid, Flags, Merge {
+        #[cfg(any())]
+        pub legacy: bool = true @ 0,
+        pub en: bool = true @ 1,
+        #[cfg(test)]
+        pub mode: u8 [try_get Mode, set Mode] = 2 @ 2..4,
+        #[cfg(not(test))]
+        pub mode: u8 [try_get Mode, set Mode] @ 2..5,
+        #[cfg(all())]
+        pub irq: bool @ 8,
+
+        pub const ENABLED = { en: true, legacy: true, mode: 1 };
+    }
+}
+
+#[test]
+fn cfg_accessors() {
+    let ctrl = Ctrl(0).with_en(true).with_mode(Mode::Auto).with_irq(true);
+    assert_eq!(ctrl.0, 0x010A);
+    assert_eq!(ctrl.mode(), Ok(Mode::Auto));
+    assert_eq!(Ctrl::ENABLED.0, 0x0006);
+    assert_eq!(Ctrl::DEFAULT.0, 0x000A);
+}
+
+#[test]
+fn cfg_metadata() {
+    assert!(Ctrl::FIELDS
+        .iter()
+        .map(|field| field.name)
+        .eq(["en", "mode", "irq"]));
+    assert_eq!(Ctrl::FIELDS[1].end, 4);
+    assert_eq!((Ctrl::LAYOUT.field_enum)(0), None);
+    assert_eq!(
+        (Ctrl::LAYOUT.field_enum)(1).map(|(name, _)| name),
+        Some("Mode")
+    );
+}
+
+#[test]
+fn cfg_auto_impls() {
+    let ctrl = Ctrl(0x0103);
+    assert_eq!(
+        format!("{ctrl:?}"),
+        "Ctrl { 0: 259, en: true, mode: Ok(Off), irq: true }"
+    );
+    assert_eq!(
+        ctrl.to_string(),
+        "Ctrl { en: true, mode: Ok(Off), irq: true }"
+    );
+    assert!(ctrl.set_flags().eq(["en", "irq"]));
+    assert_eq!(Ctrl::mask_from_names(&["legacy"]), Err("legacy"));
+    assert!(ValidCtrl::new(Ctrl(0x000C)).is_err());
+    assert_eq!(Ctrl(0x0001).merge(Ctrl(0x0100)).0, 0x0100);
+}
@@ -0,0 +1,65 @@
+use proc_bitfield::{
+    bitfield,
+    compose::{concat, split},
+};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct TimerLo(pub u32) {
+        pub count_lo: u32 @ ..,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct TimerHi(pub u32) {
+        pub count_hi: u16 @ 0..16,
+        pub irq: bool @ 30,
+        pub en: bool @ 31,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Timer(pub u64) {
+        pub count: u64 @ 0..48,
+        pub irq: bool @ 62,
+        pub en: bool @ 63,
+    }
+}
+
+#[test]
+fn compose_basic_functionality() {
+    let timer: Timer = concat(
+        TimerLo(0x89AB_CDEF),
+        TimerHi(0).with_count_hi(0x4567).with_en(true),
+    );
+    assert_eq!(timer.0, 0x8000_4567_89AB_CDEF);
+    assert_eq!(timer.count(), 0x4567_89AB_CDEF);
+    assert!(timer.en());
+    assert!(!timer.irq());
+
+    let (lo, hi): (TimerLo, TimerHi) = split(timer.with_irq(true));
+    assert_eq!(lo.count_lo(), 0x89AB_CDEF);
+    assert_eq!(hi.count_hi(), 0x4567);
+    assert!(hi.irq());
+    assert!(hi.en());
+}
+
+#[test]
+fn compose_signed_storage() {
+    bitfield! {
+        pub struct Half(pub i8) {}
+    }
+
+    bitfield! {
+        pub struct Full(pub i16) {}
+    }
+
+    let full: Full = concat(Half(-1), Half(0x12));
+    assert_eq!(full.0, 0x12FF);
+
+    let (lo, hi): (Half, Half) = split(Full(-2));
+    assert_eq!(lo.0, -2);
+    assert_eq!(hi.0, -1);
+}
@@ -0,0 +1,51 @@
+use proc_bitfield::{bitfield, bits, set_bits, with_bits};
+
+const CH_BASE: usize = 4;
+const CH_WIDTH: usize = 3;
+const EN_BIT: usize = 15;
+
+const fn channel_start(channel: usize) -> usize {
+    CH_BASE + channel * CH_WIDTH
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Channels(pub u16) {
+        pub ch0: u8 @ CH_BASE..CH_BASE + CH_WIDTH,
+        pub ch1: u8 @ CH_BASE + 3 ..= CH_BASE + 5,
+        pub ch2: u8 @ channel_start(2); CH_WIDTH,
+        pub low: u8 @ 0; CH_BASE,
+        pub en: bool @ EN_BIT,
+        pub busy: bool @ below,
+        pub busy_prev: u8 @ below; 1 << 0,
+
+        pub const CH1_FULL = { ch1: 7, en: EN_BIT == 15 };
+    }
+}
+
+#[test]
+fn const_exprs_basic_functionality() {
+    let value = Channels(0)
+        .with_ch0(1)
+        .with_ch1(2)
+        .with_ch2(3)
+        .with_low(0xF)
+        .with_en(true);
+    assert_eq!(value.0, 0x8000 | 3 << 10 | 2 << 7 | 1 << 4 | 0xF);
+    assert_eq!(Channels::CH1_FULL.0, 0x8000 | 7 << 7);
+    assert_eq!(
+        (Channels::FIELDS[6].start, Channels::FIELDS[6].end),
+        (EN_BIT - 2, EN_BIT - 1)
+    );
+}
+
+#[test]
+fn const_exprs_bits_macros() {
+    let mut value = 0x1234_u16;
+    assert_eq!(bits!(value, u8 @ CH_BASE..CH_BASE + 4), 3);
+    assert_eq!(bits!(value, u8 @ CH_BASE * 2; 4), 2);
+    assert!(bits!(value, EN_BIT - 3));
+    assert_eq!(with_bits!(value, CH_BASE..CH_BASE + 4 = 0xF_u8), 0x12F4);
+    set_bits!(value, EN_BIT = true);
+    assert_eq!(value, 0x9234);
+}
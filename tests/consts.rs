@@ -0,0 +1,42 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Ctrl(pub u16) {
+        pub en: bool @ 0,
+        pub mode: u8 @ 1..=3,
+        pub div: i8 @ 4; 4,
+        pub ro_status: u8 [read_only] @ 12..16,
+
+        pub const DISABLED = { en: false, mode: 0 };
+        pub const FAST = { en: true, mode: 5, div: -1 };
+        pub const STATUS_SET = { ro_status: 0xF };
+    }
+}
+
+#[test]
+fn consts_basic_functionality() {
+    assert_eq!(Ctrl::DISABLED.0, 0);
+    assert_eq!(Ctrl::FAST.0, 0x00FB);
+    assert_eq!(Ctrl::STATUS_SET.0, 0xF000);
+
+    assert!(Ctrl::FAST.en());
+    assert_eq!(Ctrl::FAST.mode(), 5);
+    assert_eq!(Ctrl::FAST.div(), -1);
+}
+
+#[test]
+fn consts_truncate_values() {
+    bitfield! {
+        pub struct Narrow(pub u8) {
+            pub low: u8 @ 0..4,
+            pub whole: u8 @ ..,
+
+            pub const LOW_OVERFLOW = { low: 0xFF };
+            pub const WHOLE = { whole: 0xA5 };
+        }
+    }
+
+    assert_eq!(Narrow::LOW_OVERFLOW.0, 0x0F);
+    assert_eq!(Narrow::WHOLE.0, 0xA5);
+}
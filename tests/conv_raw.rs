@@ -0,0 +1,279 @@
+use proc_bitfield::ConvRaw;
+
+#[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+#[conv_raw(lut)]
+pub enum Opcode {
+    Nop = -2,
+    Load = 0,
+    Store,
+    Jump = 5,
+    Halt = 200,
+}
+
+#[test]
+fn conv_raw_lut_basic_functionality() {
+    assert_eq!(Opcode::try_from(-2_i8), Ok(Opcode::Nop));
+    assert_eq!(Opcode::try_from(0_i16), Ok(Opcode::Load));
+    assert_eq!(Opcode::try_from(1_u8), Ok(Opcode::Store));
+    assert_eq!(Opcode::try_from(5_u64), Ok(Opcode::Jump));
+    assert_eq!(Opcode::try_from(200_u8), Ok(Opcode::Halt));
+    assert_eq!(Opcode::try_from(200_i16), Ok(Opcode::Halt));
+
+    // Values inside the table's range without a variant
+    assert_eq!(Opcode::try_from(-1_i32), Err(()));
+    assert_eq!(Opcode::try_from(3_u8), Err(()));
+    // Values outside the table's range
+    assert_eq!(Opcode::try_from(-3_i8), Err(()));
+    assert_eq!(Opcode::try_from(201_u8), Err(()));
+    assert_eq!(Opcode::try_from(u128::MAX), Err(()));
+    assert_eq!(Opcode::try_from(-56_i8), Err(()));
+}
+
+#[test]
+fn conv_raw_lut_matches_default() {
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Plain {
+        A,
+        B = 3,
+        C = -4,
+    }
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[conv_raw(lut)]
+    pub enum Lut {
+        A,
+        B = 3,
+        C = -4,
+    }
+
+    for raw in i8::MIN..=i8::MAX {
+        assert_eq!(
+            Plain::try_from(raw).map(|v| v as i8),
+            Lut::try_from(raw).map(|v| v as i8)
+        );
+    }
+    for raw in u8::MIN..=u8::MAX {
+        assert_eq!(
+            Plain::try_from(raw).map(|v| v as i8),
+            Lut::try_from(raw).map(|v| v as i8)
+        );
+    }
+}
+
+#[test]
+fn conv_raw_signed_targets() {
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Mixed {
+        MinI8 = -128,
+        MinusOne = -1,
+        Zero = 0,
+        MaxI8 = 127,
+        AboveI8 = 128,
+        MaxU8 = 255,
+    }
+
+    // Conversions are value-preserving: raw values are never reinterpreted as a different
+    // signedness, so `u8::MAX` maps to `MaxU8` instead of `MinusOne`
+    assert_eq!(Mixed::try_from(-128_i8), Ok(Mixed::MinI8));
+    assert_eq!(Mixed::try_from(-1_i8), Ok(Mixed::MinusOne));
+    assert_eq!(Mixed::try_from(127_i8), Ok(Mixed::MaxI8));
+    assert_eq!(Mixed::try_from(0xFF_u8), Ok(Mixed::MaxU8));
+    assert_eq!(Mixed::try_from(0x80_u8), Ok(Mixed::AboveI8));
+    assert_eq!(Mixed::try_from(-1_i64), Ok(Mixed::MinusOne));
+    assert_eq!(Mixed::try_from(u64::MAX), Err(()));
+    assert_eq!(Mixed::try_from(-129_i16), Err(()));
+    assert_eq!(Mixed::try_from(256_u16), Err(()));
+
+    // `From<Mixed>` is only implemented for types that contain all discriminants
+    assert_eq!(i16::from(Mixed::MinI8), -128);
+    assert_eq!(i16::from(Mixed::MaxU8), 255);
+    assert_eq!(i128::from(Mixed::MinusOne), -1);
+}
+
+#[test]
+fn conv_raw_suffixed_and_wide_discriminants() {
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[repr(u8)]
+    pub enum Suffixed {
+        A = 0x80_u8,
+        B = 0xFF_u8,
+    }
+
+    assert_eq!(u16::from(Suffixed::A), 0x80);
+    assert_eq!(i32::from(Suffixed::B), 0xFF);
+    assert_eq!(Suffixed::try_from(0xFF_i16), Ok(Suffixed::B));
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[repr(u64)]
+    pub enum Wide {
+        Low = 1,
+        High = 0xFFFF_FFFF_FFFF_FFFF,
+    }
+
+    assert_eq!(u64::from(Wide::High), u64::MAX);
+    assert_eq!(Wide::try_from(u64::MAX), Ok(Wide::High));
+    assert_eq!(Wide::try_from(-1_i64), Err(()));
+    assert_eq!(Wide::try_from(1_i8), Ok(Wide::Low));
+}
+
+#[test]
+fn conv_raw_non_exhaustive() {
+    use proc_bitfield::bitfield;
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[non_exhaustive]
+    pub enum Speed {
+        Low,
+        High = 3,
+        Max = 300,
+    }
+
+    assert_eq!(Speed::try_from(3_u8), Ok(Speed::High));
+    assert_eq!(u8::try_from(Speed::High), Ok(3));
+    assert_eq!(u8::try_from(Speed::Max), Err(()));
+    assert_eq!(u16::try_from(Speed::Max), Ok(300));
+    assert_eq!(i64::try_from(Speed::Low), Ok(0));
+
+    bitfield! {
+        pub struct Ctrl(pub u8) {
+            pub speed: u8 [try_both Speed] @ 0..2,
+        }
+    }
+
+    let mut ctrl = Ctrl(0);
+    assert_eq!(ctrl.speed(), Ok(Speed::Low));
+    assert_eq!(ctrl.set_speed(Speed::High), Ok(()));
+    assert_eq!(ctrl.0, 3);
+    assert_eq!(ctrl.speed(), Ok(Speed::High));
+    assert!(ctrl.with_speed(Speed::Max).is_err());
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[non_exhaustive]
+    pub enum Flag {
+        /// Disabled.
+        Off,
+        On,
+    }
+
+    assert_eq!(Flag::from(true), Flag::On);
+    assert_eq!(bool::try_from(Flag::Off), Ok(false));
+}
+
+#[test]
+fn conv_raw_aliases() {
+    use proc_bitfield::bitfield;
+
+    // Shift encodings where the low bit is a don't-care for some of the operations
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Shift {
+        #[conv_raw(alias = 0b001)]
+        Lsl = 0b000,
+        Lsr = 0b010,
+        #[conv_raw(alias = 0b101, alias = 0b111)]
+        Asr = 0b100,
+        #[conv_raw(alias = -1)]
+        Ror = 0b110,
+    }
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[conv_raw(lut)]
+    pub enum LutShift {
+        #[conv_raw(alias = 0b001)]
+        Lsl = 0b000,
+        Lsr = 0b010,
+        #[conv_raw(alias = 0b101, alias = 0b111)]
+        Asr = 0b100,
+        #[conv_raw(alias = -1)]
+        Ror = 0b110,
+    }
+
+    for raw in i8::MIN..=i8::MAX {
+        assert_eq!(
+            Shift::try_from(raw).map(|v| v as i8),
+            LutShift::try_from(raw).map(|v| v as i8)
+        );
+    }
+    assert_eq!(Shift::try_from(0b001_u8), Ok(Shift::Lsl));
+    assert_eq!(Shift::try_from(0b111_u32), Ok(Shift::Asr));
+    assert_eq!(Shift::try_from(0b011_u8), Err(()));
+    assert_eq!(Shift::try_from(-1_i8), Ok(Shift::Ror));
+    assert_eq!(Shift::try_from(u8::MAX), Err(()));
+
+    // Variants are always converted back to their canonical encoding
+    assert_eq!(u8::from(Shift::Asr), 0b100);
+
+    bitfield! {
+        pub struct Instr(pub u8) {
+            pub shift: u8 [try_get Shift, set Shift] @ 4..7,
+        }
+    }
+
+    let instr = Instr(0b0111_0000);
+    assert_eq!(instr.shift(), Ok(Shift::Asr));
+    assert_eq!(instr.with_shift(Shift::Asr).0, 0b0100_0000);
+}
+
+#[test]
+fn conv_raw_patterns() {
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Op {
+        #[conv_raw(pattern = "0000_xxxx")]
+        Nop,
+        #[conv_raw(pattern = "01xx_xxx0")]
+        LoadEven,
+        #[conv_raw(pattern = "01xx_xxxx")]
+        Load,
+        #[conv_raw(pattern = "1x11_0000")]
+        Halt,
+        Invalid,
+    }
+
+    assert_eq!(Op::NOP_PATTERN, (0xF0, 0x00));
+    assert_eq!(Op::LOAD_EVEN_PATTERN, (0xC1, 0x40));
+    assert_eq!(Op::HALT_PATTERN, (0xBF, 0xB0));
+
+    const DECODED: Option<Op> = Op::match_pattern(0x07);
+    assert_eq!(DECODED, Some(Op::Nop));
+    assert_eq!(Op::match_pattern(0x42), Some(Op::LoadEven));
+    assert_eq!(Op::match_pattern(0x43), Some(Op::Load));
+    assert_eq!(Op::match_pattern(0xF0), Some(Op::Halt));
+    assert_eq!(Op::match_pattern(0xB0), Some(Op::Halt));
+    assert_eq!(Op::match_pattern(0x80), None);
+
+    // Patterns don't affect the conversions from discriminants
+    assert_eq!(Op::try_from(4_u8), Ok(Op::Invalid));
+    assert_eq!(Op::try_from(0x40_u8), Err(()));
+
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Wide {
+        #[conv_raw(pattern = "1xxxxxxxx")]
+        High,
+    }
+
+    assert_eq!(Wide::HIGH_PATTERN, (0x100_u16, 0x100_u16));
+    assert_eq!(Wide::match_pattern(0x1FF), Some(Wide::High));
+}
+
+#[test]
+fn conv_raw_i128_boundaries() {
+    #[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+    #[repr(i128)]
+    pub enum Extreme {
+        Min = -170_141_183_460_469_231_731_687_303_715_884_105_728,
+        MinusOne = -1,
+        Max = 170_141_183_460_469_231_731_687_303_715_884_105_727,
+    }
+
+    assert_eq!(Extreme::try_from(i128::MIN), Ok(Extreme::Min));
+    assert_eq!(Extreme::try_from(i128::MAX), Ok(Extreme::Max));
+    assert_eq!(Extreme::try_from(-1_i8), Ok(Extreme::MinusOne));
+    assert_eq!(i128::from(Extreme::Min), i128::MIN);
+    assert_eq!(i128::from(Extreme::Max), i128::MAX);
+
+    // Unsigned raw values aren't reinterpreted as two's complement: `1 << 127` and `u128::MAX`
+    // don't correspond to `i128::MIN` and `-1`
+    assert_eq!(Extreme::try_from(i128::MAX as u128), Ok(Extreme::Max));
+    assert_eq!(Extreme::try_from(1_u128 << 127), Err(()));
+    assert_eq!(Extreme::try_from(u128::MAX), Err(()));
+    assert_eq!(Extreme::try_from(u64::MAX), Err(()));
+}
@@ -0,0 +1,39 @@
+use proc_bitfield::{bitfield, copy_bits, ops};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Hw(pub u32): Debug {
+        pub irq: bool @ 0,
+        pub divider: u16 @ 4..20,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Shadow<T>(pub [u8; 4]): Debug {
+        pub irq: bool @ 31,
+        pub divider: u16 @ 8..24,
+    }
+}
+
+#[test]
+fn copy_bits_raw() {
+    let mut dst = 0xFFFF_u16;
+    ops::copy_bits::<8, 12, 4, 8>(&0x0500_u32, &mut dst);
+    assert_eq!(dst, 0xFF5F);
+
+    let mut dst = [0_u8; 2];
+    ops::copy_bits::<0, 12, 2, 14>(&0xABC_u16, &mut dst);
+    assert_eq!(u16::from_le_bytes(dst), 0xABC << 2);
+}
+
+#[test]
+fn copy_bits_fields() {
+    let hw = Hw(0).with_irq(true).with_divider(0x1234);
+    let mut shadow = Shadow::<()>([0xFF; 4], core::marker::PhantomData);
+    copy_bits!(Hw::divider(&hw) => Shadow::<()>::divider(&mut shadow));
+    copy_bits!(Hw::irq(&Hw(0)) => Shadow::<()>::irq(&mut shadow));
+    assert_eq!(shadow.divider(), 0x1234);
+    assert!(!shadow.irq());
+    assert_eq!(shadow.0, [0xFF, 0x34, 0x12, 0x7F]);
+}
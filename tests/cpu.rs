@@ -0,0 +1,66 @@
+#![cfg(feature = "cpu")]
+
+use proc_bitfield::cpu::{
+    ArmInstrSet, ArmMode, Cpsr, Mstatus, RiscvExtState, RiscvPrivilege, RiscvXlen, ValidCpsr,
+    ValidMstatus,
+};
+
+#[test]
+fn cpu_cpsr() {
+    assert_eq!(Cpsr::RESET.0, 0x1D3);
+    assert_eq!(Cpsr::RESET.mode(), Ok(ArmMode::Supervisor));
+    assert_eq!(Cpsr::RESET.instr_set(), ArmInstrSet::Arm);
+
+    let cpsr = Cpsr(0x6000_0030);
+    assert_eq!(cpsr.mode(), Ok(ArmMode::User));
+    assert!(!ArmMode::User.is_privileged());
+    assert_eq!(cpsr.instr_set(), ArmInstrSet::Thumb);
+    assert!(cpsr.z() && cpsr.c() && !cpsr.n() && !cpsr.v());
+    assert_eq!(cpsr.nzcv(), 0b0110);
+
+    let cpsr = Cpsr(0).with_it(0xA7);
+    assert_eq!(cpsr.0, 0x0600_A400);
+    assert_eq!(cpsr.it(), 0xA7);
+
+    assert!(Cpsr(0x14).mode().is_err());
+    assert!(ValidCpsr::new(Cpsr(0x14)).is_err());
+    let valid = ValidCpsr::new(Cpsr(0x8000_001F)).unwrap();
+    assert_eq!(valid.mode(), ArmMode::System);
+    assert!(valid.n());
+
+    // Reserved bits are preserved by setters
+    assert_eq!(Cpsr(0x00F0_0000).with_q(true).0, 0x08F0_0000);
+    assert_eq!(Cpsr(0xFFFF_FFFF).0 & !Cpsr::RESERVED_MASK, 0xFF0F_FFFF);
+}
+
+#[test]
+fn cpu_mstatus() {
+    assert_eq!(Mstatus::RESET.0, 0xA_0000_1800);
+    assert_eq!(Mstatus::RESET.mpp(), Ok(RiscvPrivilege::Machine));
+    assert_eq!(Mstatus::RESET.uxl(), Ok(RiscvXlen::Rv64));
+    assert_eq!(Mstatus::RESET.sxl(), Ok(RiscvXlen::Rv64));
+    assert_eq!(Mstatus::RESET.fs(), RiscvExtState::Off);
+
+    let mut mstatus = Mstatus::RESET.with_fs(RiscvExtState::Dirty);
+    assert!(!mstatus.sd());
+    mstatus.update_sd();
+    assert!(mstatus.sd());
+    mstatus.set_fs(RiscvExtState::Clean);
+    mstatus.update_sd();
+    assert!(!mstatus.sd());
+    assert!(Mstatus(3 << 15).with_sd_updated().sd());
+    assert_eq!(Mstatus(3 << 15).xs(), RiscvExtState::Dirty);
+
+    assert!(Mstatus(0x1000).mpp().is_err());
+    assert!(ValidMstatus::new(Mstatus(0x1000)).is_err());
+    assert!(ValidMstatus::new(Mstatus(0)).is_err());
+    assert_eq!(
+        ValidMstatus::new(Mstatus::RESET).unwrap().mpp(),
+        RiscvPrivilege::Machine
+    );
+
+    assert_eq!(
+        Mstatus(!0).0 & !Mstatus::RESERVED_MASK,
+        0x8000_003F_007F_FFEA
+    );
+}
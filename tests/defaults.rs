@@ -0,0 +1,57 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Slow,
+    Fast,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Ctrl(pub u16) {
+        pub en: bool = true @ 0,
+        pub mode: u8 [unwrap_get Mode, set Mode] = 2 @ 1..=2,
+        pub div: i8 = -1 @ 3..7,
+        pub status: u8 [read_only] @ 7..12,
+        pub channels: bool; 4 = true @ 12..16,
+    }
+}
+
+#[test]
+fn defaults_basic_functionality() {
+    assert_eq!(Ctrl::DEFAULT.0, 0xF07D);
+    assert_eq!(Ctrl::default(), Ctrl::DEFAULT);
+
+    let ctrl = Ctrl::default();
+    assert!(ctrl.en());
+    assert_eq!(ctrl.mode(), Mode::Fast);
+    assert_eq!(ctrl.div(), -1);
+    assert_eq!(ctrl.status(), 0);
+    assert!((0..4).all(|i| ctrl.channels(i)));
+}
+
+#[test]
+fn defaults_truncate_values() {
+    bitfield! {
+        pub struct Narrow(pub u8) {
+            pub low: u8 = 0xFF @ 0..4,
+            pub high: u8 @ 4..8,
+        }
+    }
+
+    assert_eq!(Narrow::DEFAULT.0, 0x0F);
+    assert_eq!(Narrow::default().0, 0x0F);
+}
+
+#[test]
+fn defaults_packed_fields() {
+    bitfield! {
+        pub struct Packed(pub u16) {
+            pub low: u8 = 0x12,
+            pub high: u8 = 0x34,
+        }
+    }
+
+    assert_eq!(Packed::DEFAULT.0, 0x3412);
+}
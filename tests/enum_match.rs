@@ -0,0 +1,32 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Slow,
+    Fast,
+}
+
+bitfield! {
+    pub struct FanCtrl(pub u8): Debug {
+        pub mode: u8 [unwrap_get Mode, set Mode] @ 0..2,
+        pub try_mode: u8 [try_get Mode, set Mode] @ 2..4,
+    }
+}
+
+fn rpm(ctrl: FanCtrl) -> u32 {
+    match ctrl.mode() {
+        Mode::Off => 0,
+        Mode::Slow => 1200,
+        Mode::Fast => 3000,
+    }
+}
+
+#[test]
+fn exhaustive_dispatch() {
+    assert_eq!(rpm(FanCtrl(0)), 0);
+    assert_eq!(rpm(FanCtrl(0).with_mode(Mode::Slow)), 1200);
+    assert_eq!(rpm(FanCtrl(0).with_mode(Mode::Fast)), 3000);
+    assert_eq!(FanCtrl(0x8).try_mode(), Ok(Mode::Fast));
+    assert!(FanCtrl(0xC).try_mode().is_err());
+}
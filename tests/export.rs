@@ -0,0 +1,52 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Slow,
+    Fast,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct ExportCtrl(pub u32): options(export_prefix = reg_ctrl) {
+        pub en: bool [export] @ 0,
+        pub mode: u8 [unwrap_get Mode, set Mode, export] @ 1..=2,
+        pub status: u8 [read_only, export] @ 8..12,
+        pub div: u16 @ 16..32,
+    }
+}
+
+bitfield! {
+    pub struct DmaChannelCfg(pub u16) {
+        pub ch: bool; 2 [export] @ 0..2,
+    }
+}
+
+#[test]
+fn export_get() {
+    let raw = ExportCtrl(0)
+        .with_en(true)
+        .with_mode(Mode::Fast)
+        .with_div(0x1234)
+        .0
+        | 0x500;
+    assert!(ExportCtrl::reg_ctrl_get_en(raw));
+    assert_eq!(ExportCtrl::reg_ctrl_get_mode(raw), 2);
+    assert_eq!(ExportCtrl::reg_ctrl_get_status(raw), 5);
+}
+
+#[test]
+fn export_set() {
+    let raw = ExportCtrl::reg_ctrl_set_mode(0x1234_0001, 0xFF);
+    assert_eq!(raw, 0x1234_0007);
+    assert_eq!(ExportCtrl::reg_ctrl_set_en(raw, false), 0x1234_0006);
+}
+
+#[test]
+fn export_default_prefix() {
+    let raw = DmaChannelCfg::dma_channel_cfg_set_ch_1(0, true);
+    assert_eq!(raw, 2);
+    assert!(DmaChannelCfg::dma_channel_cfg_get_ch_1(raw));
+    assert!(!DmaChannelCfg::dma_channel_cfg_get_ch_0(raw));
+}
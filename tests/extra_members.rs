@@ -0,0 +1,61 @@
+use core::marker::PhantomData;
+use proc_bitfield::{bitfield, Bitfield, Merge};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Uart0;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(
+        pub u32,
+        /// The ID of the owning task.
+        pub u16 = 0,
+        PhantomData<Uart0> = PhantomData,
+    ): Debug, Merge, FromStorage {
+        pub en: bool = true @ 0,
+        pub mode: u8 [range 0..4] @ 1..4,
+        pub baud: u16 @ 16..32,
+        pub const FAST = { baud: 0x100 };
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ConstCtrl(pub u8, pub u32 = 7): options(const) {
+        pub en: bool @ 0,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy)]
+    pub struct Generic<T: Copy>(pub u8, u8 = 0xFF) {
+        pub value: u8 @ 0..4,
+    }
+}
+
+#[test]
+fn extra_members_initialization() {
+    assert_eq!(Ctrl::DEFAULT.1, 0);
+    assert_eq!(Ctrl::FAST.0, 0x100_0000);
+    assert_eq!(Ctrl::from(0x20).1, 0);
+    assert_eq!(Ctrl::from_storage(0x10).1, 0);
+    assert_eq!(ConstCtrl::from_storage(1).1, 7);
+    assert_eq!(Generic::<u8>::from_storage(3).1, 0xFF);
+}
+
+#[test]
+fn extra_members_carried_over() {
+    let mut ctrl = Ctrl(0, 42, PhantomData);
+    ctrl = ctrl.with_en(true).with_baud(9600);
+    ctrl.set_mode(3).unwrap();
+    assert_eq!(ctrl.0, 9600 << 16 | 7);
+    assert_eq!(ctrl.1, 42);
+    assert_eq!(ctrl.merge(Ctrl::FAST).1, 0);
+    assert_eq!(Ctrl::FAST.merge(ctrl).1, 42);
+
+    const CTRL: ConstCtrl = ConstCtrl(0, 3).with_en(true);
+    assert_eq!((CTRL.0, CTRL.1), (1, 3));
+
+    let generic = Generic::<u16>(0, 1, PhantomData).with_value(5);
+    assert_eq!((generic.0, generic.1), (5, 1));
+}
@@ -0,0 +1,37 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct IrqMask(pub u16): Debug, Flags {
+        pub timer: bool @ 0,
+        pub dma: bool [read_only] @ 1,
+        pub source: u8 @ 4..8,
+        pub uart: bool @ 15,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct NoFlags(pub [u8; 2]): Debug, Flags {
+        pub value: u16 @ ..,
+    }
+}
+
+#[test]
+fn flags_set_flags() {
+    let mask = IrqMask(0x80F3);
+    assert!(mask.set_flags().eq(["timer", "dma", "uart"]));
+    assert_eq!(IrqMask(0x00F0).set_flags().count(), 0);
+    assert_eq!(NoFlags([0xFF; 2]).set_flags().count(), 0);
+}
+
+#[test]
+fn flags_mask_from_names() {
+    assert_eq!(
+        IrqMask::mask_from_names(&["uart", "timer"]),
+        Ok(IrqMask(0x8001))
+    );
+    assert_eq!(IrqMask::mask_from_names(&[]), Ok(IrqMask(0)));
+    assert_eq!(IrqMask::mask_from_names(&["dma", "source"]), Err("source"));
+    assert_eq!(NoFlags::mask_from_names(&["value"]), Err("value"));
+}
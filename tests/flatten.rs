@@ -0,0 +1,56 @@
+use proc_bitfield::{bitfield, bitfield_template};
+
+bitfield_template! {
+    template channel(INDEX) {
+        pub struct _(pub u8) {
+            /// The channel's mode.
+            pub mode: u8 @ 0..3,
+            pub en: bool,
+            reserved @ 4..msb,
+            pub irq: bool @ msb,
+        }
+    }
+}
+
+bitfield_template! {
+    template status() {
+        pub struct _(pub u8) {
+            pub code: u8 @ ..,
+        }
+    }
+}
+
+channel!(Channel, INDEX = 0);
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Dma(pub u32): Debug, options(deny_gaps) {
+        flatten status!() @ 24,
+        pub busy: bool @ 8,
+        #[cfg(all())]
+        flatten channel!(INDEX = 0) @ 0,
+        reserved @ 9..16,
+        pub count: u8 @ 16..24,
+    }
+}
+
+#[test]
+fn flatten_basic_functionality() {
+    let dma = Dma(0)
+        .with_mode(5)
+        .with_en(true)
+        .with_irq(true)
+        .with_code(0xAB)
+        .with_busy(true);
+    assert_eq!(dma.0, 0xAB00_018D);
+    assert_eq!((dma.mode(), dma.en(), dma.irq()), (5, true, true));
+    assert_eq!(Channel(0x8D).mode(), 5);
+
+    let names = Dma::FIELDS
+        .iter()
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["code", "busy", "mode", "en", "irq", "count"]);
+    assert_eq!((Dma::FIELDS[0].start, Dma::FIELDS[0].end), (24, 32));
+    assert_eq!((Dma::FIELDS[4].start, Dma::FIELDS[4].end), (7, 8));
+}
@@ -0,0 +1,87 @@
+use proc_bitfield::{bitfield, Bit, Bitfield, Bits, SetBit, SetBits, WithBit, WithBits};
+
+bitfield! {
+    pub struct Ctrl<T>(pub T): Debug, FromStorage
+    where
+        T: Copy
+            + core::fmt::Debug
+            + Bits<u8>
+            + WithBits<u8>
+            + SetBits<u8>
+            + Bit
+            + WithBit
+            + SetBit,
+    {
+        pub mode: u8 @ 0..4,
+        pub level: u8 @ above; 3,
+        pub enable: bool @ 7,
+    }
+}
+
+/// A storage newtype counting the writes to it, standing in for a register access wrapper.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counted {
+    value: u16,
+    writes: u8,
+}
+
+impl Bits<u8> for Counted {
+    fn bits<const START: usize, const END: usize>(&self) -> u8 {
+        self.value.bits::<START, END>()
+    }
+}
+
+impl WithBits<u8> for Counted {
+    fn with_bits<const START: usize, const END: usize>(mut self, value: u8) -> Self {
+        self.set_bits::<START, END>(value);
+        self
+    }
+}
+
+impl SetBits<u8> for Counted {
+    fn set_bits<const START: usize, const END: usize>(&mut self, value: u8) {
+        self.value.set_bits::<START, END>(value);
+        self.writes += 1;
+    }
+}
+
+impl Bit for Counted {
+    fn bit<const BIT: usize>(&self) -> bool {
+        self.value.bit::<BIT>()
+    }
+}
+
+impl WithBit for Counted {
+    fn with_bit<const BIT: usize>(mut self, value: bool) -> Self {
+        self.set_bit::<BIT>(value);
+        self
+    }
+}
+
+impl SetBit for Counted {
+    fn set_bit<const BIT: usize>(&mut self, value: bool) {
+        self.value.set_bit::<BIT>(value);
+        self.writes += 1;
+    }
+}
+
+#[test]
+fn int_storage() {
+    let ctrl = Ctrl::from(0x8B_u32);
+    assert_eq!(ctrl.mode(), 0xB);
+    assert_eq!(ctrl.level(), 0);
+    assert!(ctrl.enable());
+    let ctrl = Ctrl::<u16>::from_storage(0).with_level(5).with_enable(true);
+    assert_eq!(ctrl.into_storage(), 0xD0);
+}
+
+#[test]
+fn newtype_storage() {
+    let mut ctrl = Ctrl::from(Counted::default());
+    ctrl.set_mode(3);
+    ctrl.set_enable(true);
+    assert_eq!(ctrl.mode(), 3);
+    assert!(ctrl.enable());
+    assert_eq!(ctrl.storage().value, 0x83);
+    assert_eq!(ctrl.storage().writes, 2);
+}
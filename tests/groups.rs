@@ -0,0 +1,47 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(PartialEq, Eq)]
+    pub struct Ctrl(pub u16): Debug, options(deny_gaps) {
+        pub enable: bool @ 0,
+        group dma {
+            pub enable: bool @ 1,
+            pub dir: u8 @ 2..=3,
+            reserved @ 4..8,
+        },
+        #[cfg(all())]
+        group irq {
+            pub mask: u8 [name = "mask_bits"] @ 8..12,
+            pub pending: bool [read_only] @ 12,
+        },
+        reserved @ 13..16,
+
+        pub const DMA_OUT = { dma_enable: true, dma_dir: 2 };
+    }
+}
+
+#[test]
+fn groups() {
+    let mut ctrl = Ctrl::DMA_OUT.with_enable(true);
+    assert!(ctrl.dma_enable());
+    assert_eq!(ctrl.dma_dir(), 2);
+    assert_eq!(ctrl.0, 0xB);
+
+    ctrl.set_irq_mask_bits(0xA);
+    assert_eq!(ctrl.irq_mask_bits(), 0xA);
+    assert_eq!(ctrl.0, 0xA0B);
+    assert!(Ctrl(0x1000).irq_pending());
+
+    assert_eq!(
+        Ctrl::FIELDS
+            .iter()
+            .map(|field| field.name)
+            .collect::<Vec<_>>(),
+        ["enable", "dma_enable", "dma_dir", "irq_mask", "irq_pending"],
+    );
+    assert_eq!(
+        format!("{:?}", Ctrl(0xA06)),
+        "Ctrl { 0: 2566, enable: false, dma_enable: true, dma_dir: 1, irq_mask: 10, \
+         irq_pending: false }"
+    );
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "headers")]
+
+use core::net::Ipv4Addr;
+use proc_bitfield::headers::{EthernetHeader, Ipv4Header, TcpHeader, UdpHeader};
+
+const IPV4: [u8; 20] = [
+    0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xB8, 0x61, 0xC0, 0xA8, 0x00, 0x01,
+    0xC0, 0xA8, 0x00, 0xC7,
+];
+
+#[test]
+fn headers_ethernet() {
+    let header = EthernetHeader([
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06,
+    ]);
+    assert_eq!(header.dst(), [0xFF; 6]);
+    assert_eq!(header.src(), [2, 0, 0, 0, 0, 1]);
+    assert_eq!(header.ether_type(), 0x0806);
+    assert_eq!(header.with_ether_type(0x0800).0[12..], [0x08, 0x00]);
+}
+
+#[test]
+fn headers_ipv4() {
+    let header = Ipv4Header(IPV4);
+    assert_eq!(header.version(), 4);
+    assert_eq!(header.ihl(), 5);
+    assert_eq!(header.total_len(), 0x73);
+    assert!(header.dont_fragment());
+    assert!(!header.more_fragments());
+    assert_eq!(header.fragment_offset(), 0);
+    assert_eq!(header.ttl(), 64);
+    assert_eq!(header.protocol(), 17);
+    assert_eq!(header.checksum(), 0xB861);
+    assert_eq!(header.src(), Ipv4Addr::new(192, 168, 0, 1));
+    assert_eq!(header.dst(), Ipv4Addr::new(192, 168, 0, 199));
+    assert!(header.is_checksum_valid());
+    assert_eq!(header.compute_checksum(), 0xB861);
+
+    let fragment = header
+        .with_fragment_offset(0x1234)
+        .with_more_fragments(true);
+    assert_eq!(fragment.fragment_offset(), 0x1234);
+    assert!(fragment.dont_fragment());
+    assert_eq!(fragment.0[6..8], [0x72, 0x34]);
+    assert!(!fragment.is_checksum_valid());
+
+    let built = Ipv4Header::default()
+        .with_version(4)
+        .with_ihl(5)
+        .with_total_len(0x73)
+        .with_dont_fragment(true)
+        .with_ttl(64)
+        .with_protocol(17)
+        .with_src(Ipv4Addr::new(192, 168, 0, 1))
+        .with_dst(Ipv4Addr::new(192, 168, 0, 199));
+    assert_eq!(built.with_checksum(built.compute_checksum()).0, IPV4);
+}
+
+#[test]
+fn headers_udp() {
+    let header = UdpHeader([0x30, 0x39, 0x00, 0x35, 0x00, 0x1C, 0xAB, 0xCD]);
+    assert_eq!(header.src_port(), 12345);
+    assert_eq!(header.dst_port(), 53);
+    assert_eq!(header.length(), 28);
+    assert_eq!(header.checksum(), 0xABCD);
+}
+
+#[test]
+fn headers_tcp() {
+    let header = TcpHeader::default()
+        .with_src_port(443)
+        .with_dst_port(50000)
+        .with_seq_num(0x0102_0304)
+        .with_ack_num(0xA0B0_C0D0)
+        .with_data_offset(5)
+        .with_syn(true)
+        .with_ack(true)
+        .with_window(0xFAF0);
+    assert_eq!(
+        header.0,
+        [
+            0x01, 0xBB, 0xC3, 0x50, 0x01, 0x02, 0x03, 0x04, 0xA0, 0xB0, 0xC0, 0xD0, 0x50, 0x12,
+            0xFA, 0xF0, 0x00, 0x00, 0x00, 0x00,
+        ]
+    );
+    assert!(header.set_flags().eq(["syn", "ack"]));
+    assert_eq!(
+        TcpHeader::mask_from_names(&["fin", "ns"]).unwrap().0[12..14],
+        [0x01, 0x01]
+    );
+}
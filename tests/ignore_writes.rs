@@ -0,0 +1,30 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct UartStatus(pub u8): Debug {
+        pub rx_ready: bool [ignore_writes] @ 0,
+        pub error_count: u8 [ignore_writes, try_set u8] @ 1..4,
+        pub version: u8 [ignore_writes(2)] @ 4..7,
+        pub irq_en: bool @ 7,
+    }
+}
+
+#[test]
+fn ignore_writes() {
+    let mut status = UartStatus(0x05);
+    status.set_rx_ready(false);
+    assert!(status.rx_ready());
+    assert_eq!(status.with_rx_ready(false).0, 0x05);
+
+    assert_eq!(status.error_count(), 2);
+    assert_eq!(status.set_error_count(7_u8), Ok(()));
+    assert_eq!(status.error_count(), 2);
+
+    status.set_version(5);
+    assert_eq!(status.version(), 2);
+    assert_eq!(status.0, 0x05);
+
+    status.set_irq_en(true);
+    assert_eq!(status.0, 0x85);
+}
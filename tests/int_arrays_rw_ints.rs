@@ -6,6 +6,13 @@ fn int_arrays_rw_ints_basic_functionality() {
     assert_eq!(with_bits!([0x21_u8, 0x43], u8 @ 4; 8 = 0xFE), [0xE1, 0x4F]);
 }
 
+#[test]
+fn int_arrays_rw_bools() {
+    assert!(bits!([0_u8, 0x10], 12));
+    assert_eq!(with_bits!([0_u8, 0], 12 = true), [0, 0x10]);
+    assert_eq!(with_bits!([0_u16, 0xFFFF], 31 = false), [0, 0x7FFF]);
+}
+
 #[test]
 fn int_arrays_read_ints_signs_basic() {
     assert_eq!(bits!([0x21_i8, 0x43], u8 @ 0; 8), 0x21);
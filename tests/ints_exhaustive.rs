@@ -0,0 +1,84 @@
+use proc_bitfield::{ops::field_mask, Bits, WithBits};
+
+/// Calls `$check::<START, END>()` for every non-empty range within the given bit positions.
+macro_rules! for_all_ranges {
+    ($check: ident,) => {};
+    ($check: ident, $start: literal $($end: literal)*) => {
+        $($check::<$start, $end>();)*
+        for_all_ranges!($check, $($end)*);
+    };
+}
+
+/// Checks reading and writing `START..END` in `storage` through values of the given types against
+/// a reference implementation operating on `u128`s.
+macro_rules! check_value_types {
+    ($storage_ty: ty, $storage: expr, $($value_ty: ty),*) => {$({
+        let storage: $storage_ty = $storage;
+        let width = END - START;
+        let value_bits = <$value_ty>::BITS as usize;
+        let field = (storage as u128 & field_mask::<u128>(START, END)) >> START;
+        let expected = if width >= value_bits {
+            field as $value_ty
+        } else {
+            (field as $value_ty) << (value_bits - width) >> (value_bits - width)
+        };
+        let read: $value_ty = storage.bits::<START, END>();
+        assert_eq!(read, expected, "{storage:#x} @ {START}..{END}");
+
+        if width <= value_bits {
+            assert_eq!(storage.with_bits::<START, END>(read), storage);
+        }
+
+        for value in [
+            0 as $value_ty,
+            1,
+            <$value_ty>::MAX,
+            <$value_ty>::MIN,
+            0xA5A5_A5A5_u32 as $value_ty,
+            0x5A5A_5A5A_u32 as $value_ty,
+        ] {
+            let mask = field_mask::<u128>(START, END);
+            let expected =
+                (storage as u128 & !mask | (value as i128 as u128) << START & mask) as $storage_ty;
+            let written = storage.with_bits::<START, END>(value);
+            assert_eq!(written, expected, "{storage:#x} @ {START}..{END} = {value:#x}");
+
+            let mut set = storage;
+            proc_bitfield::SetBits::set_bits::<START, END>(&mut set, value);
+            assert_eq!(set, expected);
+        }
+    })*};
+}
+
+fn check_u8<const START: usize, const END: usize>() {
+    for storage in 0..=u8::MAX {
+        check_value_types!(u8, storage, u8, i8, u16, i16, u32, i32, u128);
+    }
+}
+
+fn check_u16<const START: usize, const END: usize>() {
+    for storage in [0, 1, 0x8000, 0xFFFF, 0x1234, 0xA5A5, 0x5A5A, 0x7FFE] {
+        check_value_types!(u16, storage, u8, i8, u16, i16, u32, i32, u128);
+    }
+}
+
+fn check_i8<const START: usize, const END: usize>() {
+    for storage in i8::MIN..=i8::MAX {
+        check_value_types!(i8, storage, u8, i8, u16, i16, u32, i32, u128);
+    }
+}
+
+#[test]
+fn ints_exhaustive_u8() {
+    for_all_ranges!(check_u8, 0 1 2 3 4 5 6 7 8);
+}
+
+#[test]
+fn ints_exhaustive_i8() {
+    for_all_ranges!(check_i8, 0 1 2 3 4 5 6 7 8);
+}
+
+#[test]
+fn ints_exhaustive_u16() {
+    for_all_ranges!(check_u16, 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+}
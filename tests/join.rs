@@ -0,0 +1,33 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    Load = 0b00_0001,
+    Store = 0b00_0010,
+    Branch = 0b10_0011,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Instr(pub u32): Debug {
+        pub op_lo: u8 @ 0..4,
+        pub rd: u8 @ 4..9,
+        pub imm: i16 @ 9..25,
+        pub op_hi: u8 @ 30..32,
+        pub join opcode: u8 [try_get Opcode] = op_hi ++ op_lo,
+        pub join raw_opcode: u8 = op_hi ++ op_lo,
+        pub join wide: u32 [get u64] = op_hi ++ imm ++ op_lo,
+    }
+}
+
+#[test]
+fn join_getters() {
+    let instr = Instr(0).with_op_hi(0b10).with_op_lo(0b0011).with_rd(0x1F);
+    assert_eq!(instr.opcode(), Ok(Opcode::Branch));
+    assert_eq!(instr.raw_opcode(), 0b10_0011);
+    assert_eq!(Instr(0).with_op_lo(1).opcode(), Ok(Opcode::Load));
+    assert!(Instr(0).with_op_lo(4).opcode().is_err());
+
+    let instr = Instr(0).with_op_hi(0b11).with_imm(-1).with_op_lo(0b0101);
+    assert_eq!(instr.wide(), 0x3F_FFF5_u64);
+}
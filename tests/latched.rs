@@ -0,0 +1,28 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct IrqStatus(pub u8): Debug {
+        pub vblank: bool [latched] @ 0,
+        pub timer: bool [latched] @ 1,
+        pub button: bool @ 2,
+    }
+}
+
+#[test]
+fn latched_basic_functionality() {
+    let mut status = IrqStatus(0);
+    status.set_vblank(true);
+    status.set_vblank(false);
+    assert!(status.vblank());
+    assert_eq!(status.with_timer(true).with_timer(false).0, 0x03);
+
+    status.set_button(true);
+    status.set_button(false);
+    assert!(!status.button());
+
+    status.ack_vblank();
+    assert_eq!(status.0, 0);
+    status.set_vblank(false);
+    assert!(!status.vblank());
+}
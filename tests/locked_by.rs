@@ -0,0 +1,33 @@
+use proc_bitfield::{access::Locked, bitfield};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ClkCfg(pub u16): Debug {
+        pub divider: u8 [locked_by cfg_lock] @ 0..4,
+        pub source: u8 [get u16, locked_by cfg_lock] @ 4..6,
+        pub enable: bool @ 6,
+        pub cfg_lock: bool @ 15,
+    }
+}
+
+#[test]
+fn locked_by_basic_functionality() {
+    let mut cfg = ClkCfg(0);
+    assert_eq!(cfg.set_divider(5), Ok(()));
+    assert_eq!(cfg.0, 0x0005);
+    assert_eq!(cfg.with_source(2), Ok(ClkCfg(0x0025)));
+
+    cfg.set_cfg_lock(true);
+    assert_eq!(cfg.set_divider(3), Err(Locked));
+    assert_eq!(cfg.with_source(1), Err(Locked));
+    assert_eq!(cfg.divider(), 5);
+    assert_eq!(cfg.source(), 0_u16);
+
+    cfg.set_enable(true);
+    assert_eq!(cfg.0, 0x8045);
+
+    cfg.set_cfg_lock(false);
+    assert_eq!(cfg.set_divider(3), Ok(()));
+    assert!(!cfg.cfg_lock());
+    assert_eq!(Locked.to_string(), "field is locked");
+}
@@ -0,0 +1,70 @@
+use proc_bitfield::{bitfield, match_layout};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Load(pub u16): Debug {
+        pub reg: u8 @ 0..4,
+        pub offset: u8 @ 4..12,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Jump(pub u16): Debug {
+        pub target: u16 @ 0..12,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Flagged(pub u16): Debug {
+        pub value: u16 @ 0..15,
+    }
+}
+
+match_layout! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Insn(u16) match opcode: u8 @ 12..16 {
+        0x1 => Load(Load),
+        0x2 | 0x3 => Jump(Jump),
+        _ => Other(Flagged),
+    }
+}
+
+match_layout! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    enum Word(u16) match flagged: bool @ 15 {
+        true => Flagged(Flagged),
+    }
+}
+
+#[test]
+fn match_layout_basic_functionality() {
+    let insn = Insn::decode(0x1A53).unwrap();
+    assert_eq!(insn, Insn::Load(Load(0x1A53)));
+    assert_eq!(insn.opcode(), 1);
+    if let Insn::Load(load) = insn {
+        assert_eq!(load.reg(), 3);
+        assert_eq!(load.offset(), 0xA5);
+    }
+
+    assert_eq!(Insn::decode(0x2123), Some(Insn::Jump(Jump(0x2123))));
+    assert_eq!(Insn::decode(0x3123), Some(Insn::Jump(Jump(0x3123))));
+    assert_eq!(Insn::decode(0xF123), Some(Insn::Other(Flagged(0xF123))));
+    assert_eq!(Insn::decode(0x3123).unwrap().opcode(), 3);
+}
+
+#[test]
+fn match_layout_storage() {
+    let insn = Insn::decode(0x2FFF).unwrap();
+    assert_eq!(*insn.storage(), 0x2FFF);
+    assert_eq!(insn.into_storage(), 0x2FFF);
+}
+
+#[test]
+fn match_layout_unmatched_tag() {
+    assert_eq!(Word::decode(0x7FFF), None);
+    let word = Word::decode(0x8001).unwrap();
+    assert!(word.flagged());
+    assert_eq!(word, Word::Flagged(Flagged(0x8001)));
+}
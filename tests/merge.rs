@@ -0,0 +1,52 @@
+use proc_bitfield::{bitfield, Merge};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct IrqStatus(pub u16): Merge {
+        pub timer: bool @ 0,
+        pub dma: bool @ 1,
+        pub last_dma: bool [merge_last] @ 2,
+        pub source: u8 @ 8..12,
+        pub pending: u8 [merge_or] @ 12..16,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Wide(pub [u8; 2]): Merge {
+        pub flag: bool @ 0,
+        pub value: u8 @ 8..16,
+    }
+}
+
+#[test]
+fn merge_basic_functionality() {
+    let a = IrqStatus(0)
+        .with_timer(true)
+        .with_last_dma(true)
+        .with_source(3);
+    let b = IrqStatus(0).with_dma(true).with_source(5).with_pending(2);
+    let merged = a.merge(b);
+    assert!(merged.timer());
+    assert!(merged.dma());
+    assert!(!merged.last_dma());
+    assert_eq!(merged.source(), 5);
+    assert_eq!(merged.pending(), 2);
+
+    let merged = b.with_pending(1).merge(a.with_pending(4));
+    assert!(merged.last_dma());
+    assert_eq!(merged.source(), 3);
+    assert_eq!(merged.pending(), 5);
+}
+
+#[test]
+fn merge_from_iter() {
+    let status = [IrqStatus(0x1001), IrqStatus(0x2302), IrqStatus(0x4000)]
+        .into_iter()
+        .collect::<IrqStatus>();
+    assert_eq!(status, IrqStatus(0x7003));
+    assert_eq!(core::iter::empty().collect::<IrqStatus>(), IrqStatus(0));
+
+    let wide = [Wide([1, 2]), Wide([0, 3])].into_iter().collect::<Wide>();
+    assert_eq!(wide, Wide([1, 3]));
+}
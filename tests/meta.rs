@@ -0,0 +1,296 @@
+use proc_bitfield::{bitfield, meta::FieldInfo, ConvRaw};
+
+bitfield! {
+    pub struct Status(pub u16) {
+        pub irq_en: bool @ 13,
+        pub mode: u8 [read_only] @ 0..4,
+        pub prescaler: u8 [write_only] @ above; 3,
+        pub raw: u16 @ ..,
+    }
+}
+
+#[test]
+fn meta_basic_functionality() {
+    assert_eq!(
+        Status::FIELDS,
+        &[
+            FieldInfo {
+                name: "irq_en",
+                start: 13,
+                end: 14,
+                stride: 1,
+                readable: true,
+                writable: true,
+                ty: "bool",
+                unit: None,
+                nested: false,
+            },
+            FieldInfo {
+                name: "mode",
+                start: 0,
+                end: 4,
+                stride: 1,
+                readable: true,
+                writable: false,
+                ty: "u8",
+                unit: None,
+                nested: false,
+            },
+            FieldInfo {
+                name: "prescaler",
+                start: 4,
+                end: 7,
+                stride: 1,
+                readable: false,
+                writable: true,
+                ty: "u8",
+                unit: None,
+                nested: false,
+            },
+            FieldInfo {
+                name: "raw",
+                start: 0,
+                end: 16,
+                stride: 1,
+                readable: true,
+                writable: true,
+                ty: "u16",
+                unit: None,
+                nested: false,
+            },
+        ]
+    );
+    assert_eq!(Status::FIELDS[2].len(), 3);
+}
+
+#[test]
+fn meta_field_at_bit() {
+    assert_eq!(
+        Status::field_at_bit(13).map(|field| field.name),
+        Some("irq_en")
+    );
+    assert_eq!(
+        Status::field_at_bit(2).map(|field| field.name),
+        Some("mode")
+    );
+    assert_eq!(
+        Status::field_at_bit(6).map(|field| field.name),
+        Some("prescaler")
+    );
+    // Overlapping fields resolve to the first one declared
+    assert_eq!(
+        Status::field_at_bit(15).map(|field| field.name),
+        Some("raw")
+    );
+    assert_eq!(Status::field_at_bit(16), None);
+
+    bitfield! {
+        pub struct Sparse(pub u8) {
+            pub low: u8 @ 0..2,
+        }
+    }
+    const HIGH: Option<&FieldInfo> = Sparse::field_at_bit(7);
+    assert_eq!(HIGH, None);
+}
+
+#[derive(ConvRaw)]
+pub enum Mode {
+    Idle,
+    Run,
+    Reset = -1,
+}
+
+bitfield! {
+    pub struct Inner(pub u8) {}
+}
+
+bitfield! {
+    pub struct Ctrl(pub u16) {
+        pub mode: i8 [try_get Mode] @ 0..2,
+        pub inner: nested Inner [read_only] @ 8..16,
+    }
+}
+
+bitfield! {
+    pub struct Escaped(pub u8) {
+        pub level: u8 [unit "\"dB\"\n\tpeak\\\x01"] @ 0..4,
+    }
+}
+
+#[test]
+fn meta_layout_json_escapes() {
+    let mut json = String::new();
+    Escaped::write_layout_json(&mut json).unwrap();
+    assert!(json.contains(r#""unit":"\"dB\"\n\tpeak\\\u0001","#));
+}
+
+#[test]
+fn meta_layout_json() {
+    let mut json = String::new();
+    Ctrl::write_layout_json(&mut json).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"name":"Ctrl","bits":16,"fields":["#,
+            r#"{"name":"mode","start":0,"end":2,"stride":1,"readable":true,"writable":true,"#,
+            r#""type":"i8","unit":null,"#,
+            r#""nested":false,"enum":{"name":"Mode","variants":["#,
+            r#"{"name":"Idle","value":0},{"name":"Run","value":1},{"name":"Reset","value":-1}"#,
+            r#"]}},"#,
+            r#"{"name":"inner","start":8,"end":16,"stride":1,"readable":true,"#,
+            r#""writable":false,"#,
+            r#""type":"Inner","unit":null,"nested":true}"#,
+            r#"]}"#,
+        )
+    );
+}
+
+#[test]
+fn meta_pretty_printers() {
+    let mut script = String::new();
+    proc_bitfield::meta::write_pretty_printers(&mut script, &[Ctrl::LAYOUT, Inner::LAYOUT])
+        .unwrap();
+    assert!(script.contains(concat!(
+        "_LAYOUTS = {\n",
+        "    \"Ctrl\": (16, [\n",
+        "        (\"mode\", 0, 2, 1, \"i8\", False, {0: \"Idle\", 1: \"Run\", -1: \"Reset\"}),\n",
+        "        (\"inner\", 8, 16, 1, \"Inner\", True, None),\n",
+        "    ]),\n",
+        "    \"Inner\": (8, [\n",
+        "    ]),\n",
+        "}\n",
+    )));
+    assert!(script.contains("def __lldb_init_module(debugger, internal_dict):"));
+}
+
+#[test]
+fn meta_layout_hash() {
+    bitfield! {
+        pub struct Same(pub u16) {
+            pub irq_en: bool [read_only] @ 13,
+            pub mode: u16 @ 0..4,
+            pub prescaler: u8 @ 4..7,
+            pub raw: u16 @ ..,
+        }
+    }
+
+    bitfield! {
+        pub struct Moved(pub u16) {
+            pub irq_en: bool @ 14,
+            pub mode: u8 @ 0..4,
+            pub prescaler: u8 @ 4..7,
+            pub raw: u16 @ ..,
+        }
+    }
+
+    bitfield! {
+        pub struct Wider(pub u32) {
+            pub irq_en: bool @ 13,
+            pub mode: u8 @ 0..4,
+            pub prescaler: u8 @ 4..7,
+            pub raw: u16 @ 0..16,
+        }
+    }
+
+    assert_eq!(Status::LAYOUT_HASH, Same::LAYOUT_HASH);
+    assert_ne!(Status::LAYOUT_HASH, Moved::LAYOUT_HASH);
+    assert_ne!(Status::LAYOUT_HASH, Wider::LAYOUT_HASH);
+    assert_eq!(
+        Status::LAYOUT_HASH,
+        proc_bitfield::meta::layout_hash(16, Status::FIELDS)
+    );
+}
+
+#[test]
+fn meta_sizes() {
+    bitfield! {
+        pub struct Ctrl(pub u32) {
+            pub mode: u8 @ 0..4,
+            pub irq_en: bool @ 13,
+            pub data: u8 @ 2, step 3, count 4,
+        }
+    }
+
+    bitfield! {
+        pub struct Wide([u16; 3]) {
+            pub low: u8 @ 0..8,
+        }
+    }
+
+    bitfield! {
+        pub struct Empty(pub u8) {}
+    }
+
+    assert_eq!((Ctrl::BITS, Ctrl::BYTES, Ctrl::USED_BITS), (32, 4, 14));
+    assert_eq!((Wide::BITS, Wide::BYTES, Wide::USED_BITS), (48, 6, 8));
+    assert_eq!((Empty::BITS, Empty::BYTES, Empty::USED_BITS), (8, 1, 0));
+    assert_eq!(proc_bitfield::meta::used_bits(Ctrl::FIELDS), 14);
+}
+
+#[test]
+fn meta_any_bitfield() {
+    use proc_bitfield::meta::AnyBitfield;
+
+    bitfield! {
+        pub struct Wide(pub [u64; 3]) {
+            pub high: u16 @ 120..136,
+            pub data: u8 @ 1, step 2, count 4,
+            pub top: u8 @ 136..144,
+            pub spread: u8 @ 96, step 16, count 3,
+        }
+    }
+
+    let wide = Wide([0xAA, 0xFF00_0000_0000_0000, 0xFF]);
+    let regs: [&dyn AnyBitfield; 4] = [
+        &Status(0x2005),
+        &Ctrl(0x12C3),
+        &Wide([0xAA, 0xFF00_0000_0000_0000, 0xFF]),
+        Inner(0).as_dyn(),
+    ];
+    assert_eq!(regs[0].raw_u128(), 0x2005);
+    assert_eq!(
+        regs[2].raw_u128(),
+        0xFF00_0000_0000_0000_0000_0000_0000_00AA
+    );
+    assert_eq!(regs[2].field_raw(&Wide::FIELDS[0]), 0xFF);
+    assert_eq!(regs[2].field_raw(&Wide::FIELDS[1]), 0xF);
+    assert_eq!(wide.field_raw(&Wide::FIELDS[1]), 0xF);
+    assert_eq!(
+        format!("{regs:?}"),
+        "[Status { irq_en: 0x1, mode: 0x5, raw: 0x2005 }, Ctrl { mode: Reset, inner: 0x12 }, \
+         Wide { high: <wide>, data: 0xf, top: <wide>, spread: <wide> }, Inner {}]"
+    );
+    assert_eq!(
+        regs.iter().map(|reg| reg.layout().name).collect::<Vec<_>>(),
+        ["Status", "Ctrl", "Wide", "Inner"]
+    );
+}
+
+#[test]
+fn meta_register_name() {
+    use proc_bitfield::meta::AnyBitfield;
+
+    bitfield! {
+        pub struct SpiCr1(pub u16): Debug, options(name = "SPI1_CR1") {
+            pub cpha: bool @ 0,
+            pub cpol: bool @ 1,
+        }
+    }
+
+    assert_eq!(SpiCr1::LAYOUT.name, "SpiCr1");
+    assert_eq!(SpiCr1::LAYOUT.register_name, Some("SPI1_CR1"));
+    assert_eq!(SpiCr1::LAYOUT.display_name(), "SPI1_CR1");
+    assert_eq!(Status::LAYOUT.register_name, None);
+    assert_eq!(Status::LAYOUT.display_name(), "Status");
+
+    let mut json = String::new();
+    SpiCr1::write_layout_json(&mut json).unwrap();
+    assert!(json.starts_with(r#"{"name":"SpiCr1","register_name":"SPI1_CR1","bits":16,"#));
+
+    assert_eq!(
+        format!("{:?}", SpiCr1(2)),
+        "SPI1_CR1 { 0: 2, cpha: false, cpol: true }"
+    );
+    let reg: &dyn AnyBitfield = &SpiCr1(2);
+    assert_eq!(format!("{reg:?}"), "SPI1_CR1 { cpha: 0x0, cpol: 0x1 }");
+}
@@ -0,0 +1,49 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    pub struct Ipv4Word0(pub u32): Debug, options(bit_order = msb0) {
+        pub version: u8 @ 0..4,
+        pub ihl: u8 @ 4..8,
+        pub dscp: u8 @ 8..14,
+        pub ecn: u8 @ 14..16,
+        pub total_len: u16 @ 16..32,
+    }
+}
+
+bitfield! {
+    pub struct Flags(pub u16): Debug, options(bit_order = msb0, const) {
+        pub first: bool @ 0,
+        pub kind: u8 @ above; 3,
+        pub lanes: bool; 4,
+        pub last: bool @ 15,
+    }
+}
+
+#[test]
+fn msb0_ranges() {
+    let word = Ipv4Word0(0x4500_0054);
+    assert_eq!(word.version(), 4);
+    assert_eq!(word.ihl(), 5);
+    assert_eq!(word.dscp(), 0);
+    assert_eq!(word.total_len(), 0x54);
+    assert_eq!(
+        Ipv4Word0(0)
+            .with_version(6)
+            .with_ihl(0xF)
+            .with_dscp(0x2E)
+            .with_ecn(1)
+            .with_total_len(0x1234)
+            .0,
+        0x6FB9_1234
+    );
+}
+
+#[test]
+fn msb0_packing() {
+    const FLAGS: Flags = Flags(0).with_first(true).with_kind(5);
+    assert_eq!(FLAGS.0, 0xD000);
+    let flags = FLAGS.with_lanes_0(true).with_last(true);
+    assert_eq!(flags.0, 0xD801);
+    assert!(flags.lanes(0));
+    assert!(!flags.lanes(1));
+}
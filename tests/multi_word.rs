@@ -0,0 +1,39 @@
+use proc_bitfield::bitfield;
+
+const WORDS: usize = 4;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Descriptor(pub u32; WORDS): Debug {
+        pub opcode: u8 @ 0..8,
+        pub cid: u16 @ 16..32,
+        pub addr: u64 @ 32..96,
+        pub length: u32 @ 96..128,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Wide(pub u64; 2) {
+        pub lo: u16 @ 0..16,
+        pub straddle: u32 @ 48..80,
+        pub key: u128 @ ..,
+    }
+}
+
+#[test]
+fn multi_word_basic_functionality() {
+    let desc = Descriptor::default()
+        .with_opcode(0x02)
+        .with_cid(0x1234)
+        .with_addr(0xDEAD_BEEF_0000_1000)
+        .with_length(0x200);
+    assert_eq!(desc.0, [0x1234_0002, 0x0000_1000, 0xDEAD_BEEF, 0x200]);
+    assert_eq!(desc.addr(), 0xDEAD_BEEF_0000_1000);
+    assert_eq!(Descriptor::BITS, 128);
+
+    let wide = Wide([0, 0]).with_straddle(0xAABB_CCDD).with_lo(0xFFFF);
+    assert_eq!(wide.0, [0xCCDD_0000_0000_FFFF, 0xAABB]);
+    assert_eq!(wide.straddle(), 0xAABB_CCDD);
+    assert_eq!(wide.key(), 0xAABB_CCDD_0000_0000_FFFF);
+}
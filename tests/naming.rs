@@ -0,0 +1,76 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    On,
+    Auto,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u16): Debug, Valid, options(get_prefix = "get_") {
+        pub en: bool @ 0,
+        pub mode: u8 [try_get Mode, set Mode] @ 1..3,
+        pub fc: u8 [name = "frame_count"] @ 4..8,
+        pub lanes: bool; 2 [name = "lane"] @ 8..10,
+        pub status: nested Status [name = "stat"] @ 12..16,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Status(pub u8): Debug {
+        pub busy: bool [name = "is_busy"] @ 0,
+        pub err: bool [read_only] @ 1,
+    }
+}
+
+#[test]
+fn get_prefix_and_names() {
+    let mut ctrl = Ctrl(0)
+        .with_en(true)
+        .with_mode(Mode::Auto)
+        .with_frame_count(5);
+    ctrl.set_lane(1, true);
+    ctrl.stat_mut().set_is_busy(true);
+    assert_eq!(ctrl.0, 0x1255);
+    assert!(ctrl.get_en());
+    assert_eq!(ctrl.get_mode(), Ok(Mode::Auto));
+    assert_eq!(ctrl.get_frame_count(), 5);
+    assert!(ctrl.get_lane(1) && ctrl.get_lane_1() && !ctrl.get_lane_0());
+    assert!(ctrl.get_stat().is_busy());
+    assert_eq!(ValidCtrl::new(ctrl).unwrap().get_mode(), Mode::Auto);
+
+    // Field names are still used for metadata and formatting
+    assert_eq!(Ctrl::FIELDS[2].name, "fc");
+    assert_eq!(
+        format!("{:?}", Status(3)),
+        "Status { 0: 3, busy: true, err: true }"
+    );
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct IntCtrl(pub u8): Debug, options(rename_all = "snake_case") {
+        pub IRQEnable: bool @ 0,
+        pub DMAReqMask: u8 @ 1..4,
+        pub RxFIFO_Level: u8 @ 4..7,
+        pub TxDone: bool [name = "tx_complete"] @ 7,
+    }
+}
+
+#[test]
+fn rename_all() {
+    let ctrl = IntCtrl(0)
+        .with_irq_enable(true)
+        .with_dma_req_mask(5)
+        .with_rx_fifo_level(3)
+        .with_tx_complete(true);
+    assert_eq!(ctrl.0, 0xBB);
+    assert!(ctrl.irq_enable());
+    assert_eq!(ctrl.dma_req_mask(), 5);
+    assert_eq!(ctrl.rx_fifo_level(), 3);
+    assert!(ctrl.tx_complete());
+    assert_eq!(IntCtrl::FIELDS[0].name, "IRQEnable");
+}
@@ -0,0 +1,31 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct CtrlBits(pub u8): Debug {
+        pub enable: bool @ 0,
+        pub mode: u8 @ 1..4,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Reg(pub u16): Debug {
+        pub status: nested CtrlBits @ 0..=7,
+        pub ctrl: CtrlBits [nested] @ 8..=15,
+        pub shadow: CtrlBits [read_only, nested] @ 8..=15,
+    }
+}
+
+#[test]
+fn nested_option() {
+    let mut reg = Reg(0).with_ctrl(CtrlBits(0).with_mode(5).with_enable(true));
+    assert_eq!(reg.0, 0x0B00);
+    assert_eq!(reg.ctrl().mode(), 5);
+    assert!(reg.shadow().enable());
+
+    reg.ctrl_mut().set_enable(false);
+    reg.set_status(CtrlBits(0x03));
+    assert_eq!(reg.0, 0x0A03);
+    assert!(Reg::FIELDS[1].nested);
+}
@@ -0,0 +1,34 @@
+use core::num::{NonZeroU16, NonZeroU8};
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Timer(pub u32): Debug {
+        pub prescaler: u8 [nonzero] @ 0..4,
+        pub reload: u16 [nonzero, read_only] @ 16..32,
+    }
+}
+
+#[test]
+fn nonzero_get() {
+    let timer = Timer(0x1234_0005);
+    assert_eq!(timer.prescaler(), NonZeroU8::new(5));
+    assert_eq!(timer.reload(), NonZeroU16::new(0x1234));
+    assert_eq!(Timer(0).prescaler(), None);
+    assert_eq!(Timer(0).reload(), None);
+}
+
+#[test]
+fn nonzero_set() {
+    let timer = Timer(0).with_prescaler(NonZeroU8::new(0xF));
+    assert_eq!(timer.0, 0xF);
+    assert_eq!(timer.with_prescaler(None).0, 0);
+}
+
+#[test]
+#[should_panic(expected = "value doesn't fit in the nonzero field's bits")]
+fn nonzero_set_truncated_to_zero() {
+    // 0x10 would be truncated to zero, and read back as `None`
+    let mut timer = Timer(0);
+    timer.set_prescaler(NonZeroU8::new(0x10));
+}
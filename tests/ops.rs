@@ -0,0 +1,130 @@
+use proc_bitfield::{bitfield, ops};
+
+fn naive_encode(coords: &[u32], bits: usize) -> u64 {
+    let mut code = 0;
+    for bit in 0..bits {
+        for (i, coord) in coords.iter().enumerate() {
+            code |= ((*coord as u64 >> bit) & 1) << (bit * coords.len() + i);
+        }
+    }
+    code
+}
+
+const SAMPLES: [u32; 6] = [0, 1, 0x1F_FFFF, 0x12_3456, 0xFFFF_FFFF, 0x8765_4321];
+
+#[test]
+fn ops_morton_2d() {
+    for x in SAMPLES {
+        for y in SAMPLES {
+            let code = ops::morton_encode_2d(x, y);
+            assert_eq!(code, naive_encode(&[x, y], 32));
+            assert_eq!(ops::morton_decode_2d(code), (x, y));
+            assert_eq!(ops::morton_x_2d(code), x);
+            assert_eq!(ops::morton_y_2d(code), y);
+        }
+    }
+}
+
+#[test]
+fn ops_morton_3d() {
+    for x in SAMPLES {
+        for y in SAMPLES {
+            for z in SAMPLES {
+                let code = ops::morton_encode_3d(x, y, z);
+                assert_eq!(code, naive_encode(&[x, y, z], 21));
+                assert_eq!(
+                    ops::morton_decode_3d(code),
+                    (x & 0x1F_FFFF, y & 0x1F_FFFF, z & 0x1F_FFFF)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn ops_morton_field_conversions() {
+    bitfield! {
+        pub struct TexAddr(pub u64) {
+            pub x: u64 [get_fn ops::morton_x_2d -> u32, read_only] @ ..,
+            pub coords: u64 [
+                get_fn ops::morton_decode_2d -> (u32, u32),
+                set_fn (|(x, y)| ops::morton_encode_2d(x, y)) ((u32, u32)),
+            ] @ ..,
+        }
+    }
+
+    let addr = TexAddr(0).with_coords((5, 3));
+    assert_eq!(addr.0, 0b1_1011);
+    assert_eq!(addr.x(), 5);
+    assert_eq!(addr.coords(), (5, 3));
+}
+
+#[test]
+fn ops_durations() {
+    use core::time::Duration;
+    use ops::Rounding;
+
+    assert_eq!(
+        ops::ticks_to_duration(3, 2, Rounding::Floor),
+        Duration::from_millis(1500)
+    );
+    assert_eq!(
+        ops::ticks_to_duration(2, 3, Rounding::Nearest),
+        Duration::from_nanos(666_666_667)
+    );
+    assert_eq!(
+        ops::ticks_to_duration(u64::MAX, 1, Rounding::Floor),
+        Duration::from_secs(u64::MAX)
+    );
+    assert_eq!(
+        ops::duration_to_ticks(Duration::from_nanos(1), 1_000, Rounding::Floor),
+        0
+    );
+    assert_eq!(
+        ops::duration_to_ticks(Duration::from_nanos(1), 1_000, Rounding::Ceil),
+        1
+    );
+    assert_eq!(
+        ops::duration_to_ticks(Duration::MAX, u64::MAX, Rounding::Nearest),
+        u64::MAX
+    );
+}
+
+#[test]
+fn ops_duration_field_conversions() {
+    use core::time::Duration;
+
+    bitfield! {
+        pub struct Timer(pub u32) {
+            pub compare: u16 [duration(1_000)] @ 0..16,
+            pub prescaled: u16 [duration(3, ceil)] @ 16..32,
+        }
+    }
+
+    let timer = Timer(0)
+        .with_compare(Duration::from_micros(2_500))
+        .with_prescaled(Duration::from_millis(500));
+    assert_eq!(timer.0, 0x0002_0002);
+    assert_eq!(timer.compare(), Duration::from_millis(2));
+    assert_eq!(timer.prescaled(), Duration::from_nanos(666_666_667));
+}
+
+#[test]
+fn ops_field_mask() {
+    for start in 0..128 {
+        for end in start + 1..=128 {
+            let mask = ops::field_mask::<u128>(start, end);
+            assert_eq!(mask.count_ones() as usize, end - start);
+            assert_eq!(mask.trailing_zeros() as usize, start);
+            assert_eq!(mask.leading_zeros() as usize, 128 - end);
+        }
+    }
+    assert_eq!(ops::field_mask::<u128>(0, 128), u128::MAX);
+    assert_eq!(ops::field_mask::<u128>(127, 128), 1 << 127);
+    assert_eq!(ops::field_mask::<u8>(0, 8), u8::MAX);
+    assert_eq!(ops::field_mask::<u32>(0, 32), u32::MAX);
+    assert_eq!(ops::field_mask::<i64>(0, 64), -1);
+    assert_eq!(ops::field_mask::<i16>(15, 16), i16::MIN);
+    assert_eq!(ops::field_mask::<u64>(60, 64), 0xF << 60);
+    assert_eq!(ops::field_mask::<usize>(4, 12), 0x0FF0);
+}
@@ -0,0 +1,200 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ConstCtrl(pub u32): Debug, options(const, inline = always, must_use = all) {
+        pub en: bool @ 0,
+        pub mode: u8 @ 1..4,
+        pub offset: i8 @ 4..8,
+        pub lanes: u8 @ 8, step 4, count 4,
+        pub level: u8 [get u32] @ 24..28,
+        pub raw: u32 @ ..,
+        pub calc doubled_mode -> u8 = self.mode() * 2,
+    }
+}
+
+const CTRL: ConstCtrl = ConstCtrl(0)
+    .with_en(true)
+    .with_mode(5)
+    .with_offset(-3)
+    .with_lanes(0b1010);
+
+const fn set_mode(mut ctrl: ConstCtrl, mode: u8) -> ConstCtrl {
+    ctrl.set_mode(mode);
+    ctrl
+}
+
+#[test]
+fn options_const_accessors() {
+    const EN: bool = CTRL.en();
+    const MODE: u8 = CTRL.mode();
+    const OFFSET: i8 = CTRL.offset();
+    const LANES: u8 = CTRL.lanes();
+    const { assert!(EN) };
+    assert_eq!(MODE, 5);
+    assert_eq!(OFFSET, -3);
+    assert_eq!(LANES, 0b1010);
+    assert_eq!(CTRL.0, 0x0010_10DB);
+    assert_eq!(CTRL.raw(), CTRL.0);
+    assert_eq!(set_mode(CTRL, 2).mode(), 2);
+    assert_eq!(CTRL.doubled_mode(), 10);
+
+    // Fields with conversions keep non-const getters
+    const LEVEL: ConstCtrl = CTRL.with_level(9);
+    assert_eq!(LEVEL.level(), 9_u32);
+}
+
+bitfield! {
+    pub struct ConstSwapped(pub u32): options(const) {
+        pub addr: u16 [byte_swap] @ 0..16,
+        pub nibbles: u8 [nibble_swap] @ 16..24,
+        pub fixed: u8 [ignore_writes] @ 24..32,
+    }
+}
+
+#[test]
+fn options_const_swapped_and_ignored_writes() {
+    const SWAPPED: ConstSwapped = ConstSwapped(0)
+        .with_addr(0x1234)
+        .with_nibbles(0xAB)
+        .with_fixed(0xFF);
+    const ADDR: u16 = SWAPPED.addr();
+    const NIBBLES: u8 = SWAPPED.nibbles();
+    assert_eq!(SWAPPED.0, 0x00BA_3412);
+    assert_eq!(ADDR, 0x1234);
+    assert_eq!(NIBBLES, 0xAB);
+}
+
+mod regs {
+    use proc_bitfield::bitfield;
+
+    bitfield! {
+        pub struct Ctrl(pub u16): options(inline = never, must_use = none, vis = pub(crate)) {
+            pub en: bool @ 0,
+            pub mode: u8 @ 1..4,
+        }
+    }
+}
+
+#[test]
+fn options_attrs_and_vis() {
+    let ctrl = regs::Ctrl(0).with_en(true).with_mode(3);
+    assert_eq!(ctrl.0, 7);
+    assert_eq!(regs::Ctrl::FIELDS.len(), 2);
+    assert_eq!(regs::Ctrl::BITS, 16);
+    let mut ctrl = ctrl;
+    ctrl.set_mode(1);
+    assert_eq!(ctrl.mode(), 1);
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct UartLcr(pub u8): Debug, options(debug_name = "LCR", debug_raw = false) {
+        pub word_len: u8 @ 0..2,
+        pub parity_en: bool @ 3,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct UartIer(pub u8): Debug, options(debug_raw = true) {
+        pub rx_en: bool @ 0,
+    }
+}
+
+#[test]
+fn options_debug_output() {
+    assert_eq!(
+        format!("{:?}", UartLcr(0x0B)),
+        "LCR { word_len: 3, parity_en: true }"
+    );
+    assert_eq!(format!("{:?}", UartIer(1)), "UartIer { 0: 1, rx_en: true }");
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct SetOnly(pub u16): Debug, Valid, Merge, options(no_with) {
+        pub en: bool = true @ 0,
+        pub mode: u8 [range 0..4] @ 1..4,
+        pub lanes: bool; 4 @ 4..8,
+        pub inner: nested ConstCtrl @ ..,
+        pub const PRESET = { mode: 2 };
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct WithOnly(pub u16): Debug, options(no_set, const) {
+        pub en: bool @ 0,
+        pub lanes: bool; 4 @ 4..8,
+    }
+}
+
+#[test]
+fn options_no_with_no_set() {
+    let mut ctrl = SetOnly::PRESET;
+    ctrl.set_en(true);
+    ctrl.set_mode(3).unwrap();
+    ctrl.set_lanes(2, true);
+    assert_eq!(ctrl.0, 0x47);
+    assert_eq!(SetOnly::DEFAULT.0, 1);
+
+    const CTRL: WithOnly = WithOnly(0).with_en(true);
+    assert_eq!(CTRL.with_lanes(3, true).0, 0x81);
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Split<T: Copy>(pub u16): Debug, options(split_impls = 2) where T: Default {
+        pub a: bool @ 0,
+        pub b: bool @ 1,
+        pub calc ab -> bool = self.a() && self.b(),
+        pub c: u8 @ 2..6,
+        pub lanes: bool; 4 @ 8..12,
+        pub const ALL = { a: true, b: true, c: 0xF };
+    }
+}
+
+#[test]
+fn options_split_impls() {
+    let value = Split::<u8>::ALL.with_lanes(1, true);
+    assert!(value.ab());
+    assert_eq!(value.c(), 0xF);
+    assert!(value.lanes(1) && !value.lanes_0());
+    assert_eq!(value.0, 0x23F);
+}
+
+bitfield! {
+    pub struct Minimal(pub u16): options(minimal, deny_gaps) {
+        pub en: bool = true @ 0,
+        pub mode: u8 @ 1..4,
+        pub lanes: bool; 4 @ 4..8,
+        reserved @ 8..16,
+    }
+}
+
+// None of these would compile if the macro generated them as well
+impl Clone for Minimal {
+    fn clone(&self) -> Self {
+        Minimal(self.0)
+    }
+}
+
+impl Default for Minimal {
+    fn default() -> Self {
+        Minimal(0x10)
+    }
+}
+
+impl Minimal {
+    const BITS: u32 = 3;
+}
+
+#[test]
+fn options_minimal() {
+    let mut value = Minimal::DEFAULT.with_mode(5).with_lanes(2, true);
+    value.set_en(false);
+    assert_eq!(value.clone().0, 0x4A);
+    assert_eq!(Minimal::default().0, 0x10);
+    assert_eq!(Minimal::BITS, 3);
+}
@@ -0,0 +1,56 @@
+mod regs {
+    use proc_bitfield::{bitfield, ConvRaw};
+
+    #[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Mode {
+        Off,
+        On,
+        Auto,
+    }
+
+    bitfield! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub struct Ctrl(priv u16): Debug, IntoStorage {
+            pub mode: u8 [try_get Mode, set Mode] @ 0..2,
+            pub prescaler: u8 [try_get_fn prescaler -> Option<u8>] @ 2..5,
+            pub en: bool @ 15,
+        }
+    }
+
+    fn prescaler(raw: u8) -> Option<u8> {
+        (raw < 6).then_some(raw)
+    }
+
+    bitfield! {
+        pub struct Plain(priv u8) {
+            pub value: u8 @ ..,
+        }
+    }
+}
+
+use regs::{Ctrl, Mode, Plain};
+
+#[test]
+fn private_storage_validated_construction() {
+    let ctrl = Ctrl::try_from_storage(0x8016).unwrap();
+    assert_eq!(ctrl.mode(), Ok(Mode::Auto));
+    assert_eq!(ctrl.prescaler(), Some(5));
+    assert!(ctrl.en());
+    assert_eq!(*ctrl.storage(), 0x8016);
+    assert_eq!(u16::from(ctrl), 0x8016);
+
+    assert_eq!(Ctrl::try_from_storage(0x0003), Err(0x0003));
+    assert_eq!(Ctrl::try_from_storage(0x0018), Err(0x0018));
+}
+
+#[test]
+fn private_storage_unchecked_construction() {
+    let ctrl = unsafe { Ctrl::from_storage_unchecked(0x0003) };
+    assert!(ctrl.mode().is_err());
+    assert_eq!(ctrl.with_mode(Mode::On).mode(), Ok(Mode::On));
+}
+
+#[test]
+fn private_storage_without_fallible_fields() {
+    assert_eq!(Plain::try_from_storage(0xFF).unwrap().value(), 0xFF);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "rand")]
+
+use proc_bitfield::{
+    bitfield,
+    random::{RawRng, SplitMix64},
+    ConvRaw,
+};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Idle = 1,
+    Run = 5,
+    Sleep = 0x1F,
+}
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Low = -2,
+    High = 1,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u32) {
+        pub mode: u8 [try_get Mode, set Mode] @ 0..5,
+        pub level: i8 [unwrap_get Level, set Level] @ 5..7,
+        pub en: bool @ 8,
+        pub status: u8 [read_only] @ 16..24,
+        pub data: u8 @ 24, step 2, count 4,
+    }
+}
+
+#[test]
+fn random_split_mix() {
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+    assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    let mut counter = 0;
+    let mut rng = || {
+        counter += 1;
+        counter
+    };
+    assert_eq!(rng.next_u128(), 2 << 64 | 1);
+}
+
+#[test]
+fn random_randomize() {
+    let mut rng = SplitMix64::new(0x1234);
+    let mut seen_en = [false; 2];
+    for _ in 0..256 {
+        let mut ctrl = Ctrl(0x00AB_0000);
+        ctrl.randomize(&mut rng);
+        assert!(ctrl.mode().is_ok());
+        let _ = ctrl.level();
+        assert_eq!(ctrl.status(), 0xAB);
+        assert_eq!(ctrl.0 & 0x00FF_FE80, 0x00AB_0000);
+        seen_en[ctrl.en() as usize] = true;
+    }
+    assert_eq!(seen_en, [true, true]);
+
+    let mut ctrl = Ctrl(0);
+    ctrl.randomize(&mut || u64::MAX);
+    assert_eq!(ctrl.mode(), Ok(Mode::Idle));
+    assert_eq!(ctrl.level(), Level::High);
+    assert!(ctrl.en());
+    assert_eq!(ctrl.data(), 0xF);
+}
@@ -0,0 +1,33 @@
+use proc_bitfield::{access::OutOfRange, bitfield};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Date(pub u16): Debug, Valid {
+        pub day: u8 [range 1..=31] @ 0..5,
+        pub month: u8 [range 1..13] @ 5..9,
+        pub year: u8 [range ..100, set u8] @ 9..16,
+    }
+}
+
+#[test]
+fn range_setters() {
+    let mut date = Date(0);
+    assert_eq!(date.set_day(15), Ok(()));
+    assert_eq!(date.set_day(0), Err(OutOfRange));
+    assert_eq!(date.day(), 15);
+    assert_eq!(date.with_month(12).map(|date| date.month()), Ok(12));
+    assert_eq!(date.with_month(13), Err(OutOfRange));
+    assert_eq!(date.set_year(99), Ok(()));
+    assert_eq!(date.set_year(100), Err(OutOfRange));
+    assert_eq!(OutOfRange.to_string(), "value is out of range");
+}
+
+#[test]
+fn range_is_valid() {
+    let date = Date(0).with_day(1).unwrap().with_month(1).unwrap();
+    assert!(date.is_valid());
+    assert!(ValidDate::new(date).is_ok());
+    assert!(!Date(0).is_valid());
+    assert!(!Date(0x0D << 5 | 1).is_valid());
+    assert!(ValidDate::new(Date(0)).is_err());
+}
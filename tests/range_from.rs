@@ -0,0 +1,82 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Header(pub u16): Debug, options(deny_gaps) {
+        pub kind: u8 @ 0..4,
+        pub tail: u16 @ 4..,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct WideHeader(pub u32): Debug {
+        pub kind: u8 @ 0..4,
+        pub tail: u32 @ 4..,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Msb0Header(pub u8): Debug, options(bit_order = msb0) {
+        pub version: u8 @ 0..2,
+        pub flags: u8 @ 2..,
+    }
+}
+
+#[test]
+fn range_from() {
+    let header = Header(0).with_kind(3).with_tail(0xABC);
+    assert_eq!(header.0, 0xABC3);
+    assert_eq!(header.tail(), 0xABC);
+    assert_eq!(Header::FIELDS[1].end, 16);
+
+    let header = WideHeader(0xFFFF_FFF0);
+    assert_eq!(header.tail(), 0x0FFF_FFFF);
+    assert_eq!(WideHeader::FIELDS[1].end, 32);
+
+    let header = Msb0Header(0b10_010110);
+    assert_eq!(header.version(), 2);
+    assert_eq!(header.flags(), 0b01_0110);
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Packet(pub bits 12): Debug {
+        pub kind: u8 @ 0..4,
+        pub payload: u8 @ 4..,
+    }
+}
+
+#[test]
+fn range_from_arbitrary_width() {
+    assert_eq!(Packet::FIELDS[1].end, 12);
+    assert_eq!(Packet(0).with_payload(0xFF).0, 0xFF0);
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Status16(pub u16): Debug {
+        pub code: u8 @ msb-3..=msb,
+        pub busy: bool @ msb - 4,
+        pub low: u8 @ 0..4,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Status32(pub u32): Debug {
+        pub code: u8 @ msb-3..=msb,
+        pub busy: bool @ msb - 4,
+        reserved @ msb - 7..msb - 4,
+        pub low: u8 @ 0..4,
+    }
+}
+
+#[test]
+fn msb_relative_positions() {
+    assert_eq!(Status16(0).with_code(0xA).with_busy(true).0, 0xA800);
+    assert_eq!(Status32(0).with_code(0xA).with_busy(true).0, 0xA800_0000);
+    assert_eq!(Status32(0xF000_0000).code(), 0xF);
+    assert_eq!(Status32::RESERVED[0].start, 24);
+}
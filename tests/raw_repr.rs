@@ -0,0 +1,41 @@
+use proc_bitfield::{bitfield, RawRepr};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Status(pub u32): Debug {
+        pub ready: bool @ 0,
+        pub code: u8 @ 8..16,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Pair(pub [u8; 2]): Debug {
+        pub lo: u8 @ 0..8,
+        pub hi: u8 @ 8..16,
+    }
+}
+
+fn roundtrip<R: RawRepr>(value: R) -> R {
+    R::from_raw(value.into_raw())
+}
+
+fn set_low_bit<R: RawRepr<Raw = u32>>(value: R) -> R {
+    R::from_raw(value.into_raw() | 1)
+}
+
+#[test]
+fn raw_repr_basic_functionality() {
+    let status = Status(0x1200);
+    assert_eq!(status.into_raw(), 0x1200);
+    assert_eq!(Status::from_raw(0x3401).code(), 0x34);
+    assert_eq!(roundtrip(status), status);
+    assert!(set_low_bit(status).ready());
+}
+
+#[test]
+fn raw_repr_arrays() {
+    let pair = Pair::from_raw([1, 2]);
+    assert_eq!(pair.hi(), 2);
+    assert_eq!(roundtrip(pair).into_raw(), [1, 2]);
+}
@@ -0,0 +1,111 @@
+use proc_bitfield::{
+    access::OutOfRange,
+    bitfield,
+    remote::{OutOfBounds, StorageBackend},
+    ConvRaw,
+};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct UartConfig(pub u16): Debug, Remote {
+        pub baud_div: u8 @ 0..8,
+        pub parity: u8 [try_get Parity, set Parity] @ 8..10,
+        pub stop_bits: u8 [range 1..=2] @ 10..12,
+        pub en: bool [read_only] @ 15,
+        pub reload: bool [trigger] @ 14,
+    }
+}
+
+bitfield! {
+    pub struct Calibration(pub u16): Remote, options(remote_name = CalibrationWord) {
+        pub offset: i8 @ 0..8,
+    }
+}
+
+/// An EEPROM whose write cycles can be exhausted, to check that errors are propagated.
+struct Eeprom {
+    words: [u16; 8],
+    writes_left: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum EepromError {
+    Nack,
+    WornOut,
+}
+
+impl StorageBackend<u16> for Eeprom {
+    type Error = EepromError;
+
+    fn read_word(&mut self, address: usize) -> Result<u16, EepromError> {
+        self.words.get(address).copied().ok_or(EepromError::Nack)
+    }
+
+    fn write_word(&mut self, address: usize, value: u16) -> Result<(), EepromError> {
+        if self.writes_left == 0 {
+            return Err(EepromError::WornOut);
+        }
+        self.writes_left -= 1;
+        *self.words.get_mut(address).ok_or(EepromError::Nack)? = value;
+        Ok(())
+    }
+}
+
+#[test]
+fn remote_accessors() {
+    let mut eeprom = Eeprom {
+        words: [0; 8],
+        writes_left: 4,
+    };
+    eeprom.words[3] = 0x8000;
+    let mut config = UartConfigRemote::new(&mut eeprom, 3);
+    assert_eq!(config.address(), 3);
+
+    config.set_baud_div(26).unwrap();
+    config.set_parity(Parity::Odd).unwrap();
+    assert_eq!(config.set_stop_bits(2), Ok(Ok(())));
+    assert_eq!(config.set_stop_bits(3), Ok(Err(OutOfRange)));
+    assert_eq!(config.baud_div(), Ok(26));
+    assert_eq!(config.parity(), Ok(Ok(Parity::Odd)));
+    assert_eq!(config.stop_bits(), Ok(2));
+    assert_eq!(config.en(), Ok(true));
+    assert_eq!(config.read(), Ok(UartConfig(0x8A1A)));
+
+    // All write cycles have been used, so further writes fail and leave the word untouched
+    assert_eq!(config.trigger_reload(), Err(EepromError::WornOut));
+    assert_eq!(config.write(UartConfig(0)), Err(EepromError::WornOut));
+    assert_eq!(eeprom.words[3], 0x8A1A);
+
+    let mut missing = UartConfigRemote::new(&mut eeprom, 8);
+    assert_eq!(missing.baud_div(), Err(EepromError::Nack));
+    assert_eq!(missing.modify(|_| ()), Err(EepromError::Nack));
+}
+
+#[test]
+fn remote_slice_backend() {
+    let mut words = [0_u16; 2];
+    let mut config = UartConfigRemote::new(&mut words[..], 1);
+    config.modify(|config| config.set_baud_div(0x55)).unwrap();
+    config.trigger_reload().unwrap();
+    assert_eq!(config.into_backend(), [0, 0x4055]);
+
+    let mut config = UartConfigRemote::new(words, 2);
+    assert_eq!(config.baud_div(), Err(OutOfBounds));
+    assert_eq!(config.backend_mut().read_word(1), Ok(0x4055));
+}
+
+#[test]
+fn remote_name() {
+    let mut words = [0_u16; 1];
+    let mut calibration = CalibrationWord::new(&mut words[..], 0);
+    calibration.set_offset(-2).unwrap();
+    assert_eq!(calibration.offset(), Ok(-2));
+    assert_eq!(words, [0xFE]);
+}
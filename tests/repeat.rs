@@ -0,0 +1,75 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    High,
+    Low,
+}
+
+const CHANNEL_BITS: usize = 8;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct PwmCtrl(pub u32): Debug, options(deny_gaps) {
+        repeat 4 stride CHANNEL_BITS {
+            pub en: bool @ 0,
+            pub mode: u8 @ above; 3,
+            pub polarity: bool [get Polarity, set Polarity] @ 4,
+            reserved @ 5..8,
+        },
+    }
+}
+
+bitfield! {
+    pub struct Timers(pub u16): options(rename_all = "snake_case") {
+        pub enable: bool @ 15,
+        repeat 3 stride 4 {
+            pub Prescaler: u8 @ 0..4,
+        },
+    }
+}
+
+#[test]
+fn repeat_suffixed_accessors() {
+    let ctrl = PwmCtrl(0)
+        .with_en_0(true)
+        .with_mode_1(5)
+        .with_polarity_3(Polarity::Low);
+    assert_eq!(ctrl.0, 0x1000_0A01);
+    assert!(ctrl.en_0());
+    assert!(!ctrl.en_1());
+    assert_eq!(ctrl.mode_1(), 5);
+    assert_eq!(ctrl.polarity_3(), Polarity::Low);
+    assert_eq!(ctrl.polarity_2(), Polarity::High);
+}
+
+#[test]
+fn repeat_indexed_accessors() {
+    let mut ctrl = PwmCtrl(0).with_en(2, true);
+    ctrl.set_mode(3, 7);
+    assert_eq!(ctrl.0, 0x0E01_0000);
+    assert!(ctrl.en(2));
+    assert_eq!(ctrl.mode(3), 7);
+    assert_eq!(
+        PwmCtrl::FIELDS
+            .iter()
+            .map(|field| field.name)
+            .take(4)
+            .collect::<Vec<_>>(),
+        ["en_0", "mode_0", "polarity_0", "en_1"]
+    );
+
+    let timers = Timers(0)
+        .with_prescaler(1, 0xA)
+        .with_prescaler_2(3)
+        .with_enable(true);
+    assert_eq!(timers.0, 0x83A0);
+    assert_eq!(timers.prescaler(1), 0xA);
+    assert_eq!(timers.prescaler_0(), 0);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 4 but the index is 4")]
+fn repeat_index_out_of_bounds() {
+    let _ = PwmCtrl(0).en(4);
+}
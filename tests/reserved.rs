@@ -0,0 +1,49 @@
+use proc_bitfield::{bitfield, meta};
+
+bitfield! {
+    pub struct Status(pub u16): Debug, options(deny_gaps) {
+        pub ready: bool @ 0,
+        reserved @ 1..4,
+        pub code: u8 @ 4..8,
+        reserved @ above; 4,
+        pub count: u8 @ 12..16,
+    }
+}
+
+bitfield! {
+    pub struct Sparse(pub u32) {
+        pub low: u8 @ 0..8,
+        reserved @ 28..32,
+    }
+}
+
+#[test]
+fn reserved_metadata() {
+    assert_eq!(Status::RESERVED.len(), 2);
+    assert_eq!((Status::RESERVED[0].start, Status::RESERVED[0].end), (1, 4));
+    assert_eq!(
+        (Status::RESERVED[1].start, Status::RESERVED[1].end),
+        (8, 12)
+    );
+    assert!(!Status::RESERVED[0].readable && !Status::RESERVED[0].writable);
+    assert_eq!(Status::FIELDS.len(), 3);
+    assert_eq!(
+        meta::first_uncovered_bit(16, Status::FIELDS, Status::RESERVED),
+        None
+    );
+    assert_eq!(
+        meta::first_uncovered_bit(32, Sparse::FIELDS, Sparse::RESERVED),
+        Some(8)
+    );
+}
+
+#[test]
+fn reserved_accessors() {
+    let status = Status(0xFFFF).with_code(0).with_count(5);
+    assert_eq!(status.0, 0x5F0F);
+    assert!(status.ready());
+    assert_eq!(
+        format!("{:?}", status),
+        "Status { 0: 24335, ready: true, code: 0, count: 5 }"
+    );
+}
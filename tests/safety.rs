@@ -0,0 +1,37 @@
+use proc_bitfield::{bitfield, UnsafeFrom, UnsafeInto};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Mode {
+    Off,
+    On,
+}
+
+impl UnsafeFrom<u8> for Mode {
+    unsafe fn unsafe_from(raw: u8) -> Self {
+        unsafe { core::mem::transmute::<u8, Mode>(raw) }
+    }
+}
+
+impl UnsafeInto<u8> for Mode {
+    unsafe fn unsafe_into(self) -> u8 {
+        self as u8
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u8) {
+        /// The current mode.
+        pub mode: u8 [unsafe_both Mode, safety "The field must contain a valid `Mode`."] @ 0..1,
+        pub checked_mode: u8 [unsafe_get! Mode, read_only] @ 0..1,
+    }
+}
+
+#[test]
+fn safety_documented_accessors() {
+    let ctrl = unsafe { Ctrl(0).with_mode(Mode::On) };
+    assert_eq!(ctrl.0, 1);
+    assert_eq!(unsafe { ctrl.mode() }, Mode::On);
+    assert_eq!(ctrl.checked_mode(), Mode::On);
+}
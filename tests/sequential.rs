@@ -0,0 +1,42 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    pub struct Packed(pub u32): Debug {
+        pub enable: bool,
+        pub mode: u8 @ above; 3,
+        pub level: u8,
+        pub flags: bool; 4,
+        pub tag: i8 @ above; 4,
+    }
+}
+
+bitfield! {
+    pub struct TopDown(pub u16): Debug {
+        pub valid: bool @ below,
+        pub id: u8 @ below; 7,
+    }
+}
+
+#[test]
+fn sequential_placement() {
+    let value = Packed(0)
+        .with_enable(true)
+        .with_mode(5)
+        .with_level(0xAB)
+        .with_flags_2(true)
+        .with_tag(-2);
+    assert_eq!(value.0, 1 | 5 << 1 | 0xAB << 4 | 1 << 14 | 0xE << 16);
+    assert!(value.enable());
+    assert_eq!(value.mode(), 5);
+    assert_eq!(value.level(), 0xAB);
+    assert!(value.flags(2));
+    assert_eq!(value.tag(), -2);
+}
+
+#[test]
+fn first_field_placement() {
+    let value = TopDown(0).with_valid(true).with_id(0x25);
+    assert_eq!(value.0, 0xA500);
+    assert!(value.valid());
+    assert_eq!(value.id(), 0x25);
+}
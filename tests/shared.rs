@@ -0,0 +1,105 @@
+#![cfg(feature = "alloc")]
+
+use proc_bitfield::{
+    bitfield,
+    shared::{LocalSharedReg, SharedReg},
+};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct IrqStatus(pub u16): Debug {
+        pub timer: bool @ 0,
+        pub dma: bool @ 1,
+        pub count: u8 @ 8..16,
+    }
+}
+
+#[test]
+fn shared_basic_functionality() {
+    let status = SharedReg::new(IrqStatus(0));
+    let handle = status.clone();
+    assert!(status.ptr_eq(&handle));
+    assert!(!status.ptr_eq(&SharedReg::new(IrqStatus(0))));
+
+    assert_eq!(handle.update(|status| status.set_dma(true)), IrqStatus(2));
+    assert!(status.get().dma());
+
+    status.set(IrqStatus(0).with_timer(true));
+    assert_eq!(handle.get(), IrqStatus(1));
+    assert_eq!(
+        format!("{status:?}"),
+        "SharedReg(IrqStatus { 0: 1, timer: true, dma: false, count: 0 })"
+    );
+}
+
+#[test]
+fn shared_concurrent_updates() {
+    let status = SharedReg::new(IrqStatus(0));
+    let threads = (0..4)
+        .map(|_| {
+            let status = status.clone();
+            std::thread::spawn(move || {
+                for _ in 0..50 {
+                    status.update(|status| status.set_count(status.count() + 1));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(status.get().count(), 200);
+}
+
+#[test]
+fn shared_local() {
+    let status = LocalSharedReg::new(IrqStatus(0));
+    let handle = status.clone();
+    assert!(status.ptr_eq(&handle));
+
+    assert_eq!(
+        handle.update(|status| status.set_count(3)),
+        IrqStatus(0x300)
+    );
+    assert_eq!(status.get().count(), 3);
+
+    status.set(IrqStatus(1));
+    assert!(handle.get().timer());
+}
+
+#[test]
+fn shared_compare_exchange_fields() {
+    let status = SharedReg::new(IrqStatus(0));
+    let stale = status.get();
+    status.update(|status| status.set_timer(true));
+
+    // The stale value is only used as a starting point, and the timer flag is preserved
+    assert_eq!(
+        status.compare_exchange_fields(stale, |status| Some(status.with_count(5))),
+        Ok(IrqStatus(0x501))
+    );
+    assert_eq!(
+        status.compare_exchange_fields(stale, |status| (!status.dma()).then_some(status)),
+        Ok(IrqStatus(0x501))
+    );
+    assert_eq!(
+        status.compare_exchange_fields(stale, |status| (status.count() == 0).then_some(status)),
+        Err(IrqStatus(0x501))
+    );
+
+    let handles = (0..4).map(|_| status.clone()).collect::<Vec<_>>();
+    std::thread::scope(|scope| {
+        for handle in &handles {
+            scope.spawn(move || {
+                for _ in 0..100 {
+                    let current = handle.get();
+                    let _ = handle.compare_exchange_fields(current, |status| {
+                        Some(status.with_count(status.count().wrapping_add(1)))
+                    });
+                }
+            });
+        }
+    });
+    assert_eq!(status.get().count(), 149);
+    assert!(status.get().timer());
+}
@@ -0,0 +1,59 @@
+use proc_bitfield::{bitfield, Bitfield};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Sample(pub i16): Debug, Merge {
+        pub low: u8 @ 0..8,
+        pub delta: i8 @ 8..12,
+        pub top: u8 @ 12..16,
+        pub sign: bool @ 15,
+        pub raw: u16 @ ..,
+        pub value: i16 @ ..,
+        pub odd: u8 @ 13, step 2, count 2,
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ConstSample(pub i8): options(const) {
+        pub low: u8 @ 0..4,
+        pub high: i8 @ 4..8,
+        pub sign: bool @ msb,
+    }
+}
+
+#[test]
+fn signed_storage_reads() {
+    let sample = Sample(-1);
+    assert_eq!(sample.low(), 0xFF);
+    assert_eq!(sample.delta(), -1);
+    assert_eq!(sample.top(), 0xF);
+    assert!(sample.sign());
+    assert_eq!(sample.raw(), 0xFFFF);
+    assert_eq!(sample.value(), -1);
+
+    let sample = Sample(0x7F80);
+    assert_eq!(sample.top(), 7);
+    assert!(!sample.sign());
+    assert_eq!(sample.delta(), -1);
+    assert_eq!(sample.low(), 0x80);
+
+    const SAMPLE: ConstSample = ConstSample(-0x80);
+    assert_eq!((SAMPLE.low(), SAMPLE.high(), SAMPLE.sign()), (0, -8, true));
+}
+
+#[test]
+fn signed_storage_writes() {
+    assert_eq!(Sample(0).with_sign(true).0, i16::MIN);
+    assert_eq!(Sample(0).with_top(0xF).0, -0x1000);
+    assert_eq!(Sample(0).with_delta(-1).0, 0xF00);
+    assert_eq!(Sample(-1).with_sign(false).0, i16::MAX);
+    assert_eq!(Sample(0).with_raw(0x8000).0, i16::MIN);
+    assert_eq!(Sample(0).with_odd(3).0, -0x6000);
+    assert_eq!(Sample(-1).odd(), 3);
+
+    const SAMPLE: ConstSample = ConstSample(0).with_high(-1).with_low(0xF);
+    assert_eq!(SAMPLE.0, -1);
+    assert_eq!(ConstSample(-1).with_sign(false).0, i8::MAX);
+    assert_eq!(ConstSample::from_storage(0x70).with_high(-8).0, -0x80);
+}
@@ -0,0 +1,63 @@
+use std::{fs, path::Path, process::Command};
+
+/// Builds a crate declaring two bitfields with `PROC_BITFIELD_STATS` set, and returns the
+/// statistics lines printed while expanding them.
+fn build_with_stats(dir: &Path) -> Vec<String> {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"stats_fixture\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nproc-bitfield = {{ path = {:?} }}\n\n[workspace]\n",
+            env!("CARGO_MANIFEST_DIR"),
+        ),
+    )
+    .unwrap();
+    // Rewritten on every run, so that Cargo rebuilds the crate and the macro runs again
+    fs::write(
+        dir.join("src/lib.rs"),
+        "proc_bitfield::bitfield! {\n    pub struct Ctrl(pub u8) {\n        pub enable: bool @ 0,\n        \
+         pub mode: u8 @ 1..4,\n    }\n}\n\nproc_bitfield::bitfield! {\n    pub struct Status(pub u16) \
+         {\n        pub busy: bool @ 0,\n    }\n}\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--offline", "--quiet"])
+        .arg("--target-dir")
+        .arg(dir.join("target"))
+        .current_dir(dir)
+        .env("PROC_BITFIELD_STATS", "1")
+        .env_remove("CARGO_TARGET_DIR")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    stderr
+        .lines()
+        .filter(|line| line.starts_with("proc-bitfield stats"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn stats_per_crate_totals() {
+    let lines = build_with_stats(&Path::new(env!("CARGO_TARGET_TMPDIR")).join("stats_fixture"));
+    assert_eq!(lines.len(), 2, "{lines:?}");
+    assert!(
+        lines[0].starts_with("proc-bitfield stats [stats_fixture]: `Ctrl`: 2 fields, "),
+        "{lines:?}"
+    );
+    assert!(
+        lines[0].contains("(totals: 1 structs, 2 fields, "),
+        "{lines:?}"
+    );
+    assert!(
+        lines[1].starts_with("proc-bitfield stats [stats_fixture]: `Status`: 1 fields, "),
+        "{lines:?}"
+    );
+    assert!(
+        lines[1].contains("(totals: 2 structs, 3 fields, "),
+        "{lines:?}"
+    );
+}
@@ -0,0 +1,72 @@
+use proc_bitfield::{bitfield, bits, set_bits, with_bits};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct TileRow(pub u16) {
+        pub plane_0: u8 @ 0, step 2, count 8,
+        pub plane_1: u8 @ 1, step 2, count 8,
+        pub signed: i8 @ 12, step 1, count 4,
+
+        const FILLED = { plane_0: 0xFF };
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Wide(pub [u8; 4]) {
+        pub spread: u8 @ 3, step 4, count 8,
+    }
+}
+
+#[test]
+fn strided_basic_functionality() {
+    let row = TileRow(0)
+        .with_plane_0(0b1010_0001)
+        .with_plane_1(0b0000_0011);
+    assert_eq!(row.0, 0b0100_0100_0000_1011);
+    assert_eq!(row.plane_0(), 0b1010_0001);
+    assert_eq!(row.plane_1(), 0b0000_0011);
+
+    let mut row = row;
+    row.set_plane_1(0);
+    assert_eq!(row.0, 0b0100_0100_0000_0001);
+
+    assert_eq!(TileRow(0xF000).signed(), -1);
+    assert_eq!(TileRow(0x7000).signed(), 7);
+    assert_eq!(TileRow::FILLED.0, 0x5555);
+}
+
+#[test]
+fn strided_arrays() {
+    let wide = Wide([0; 4]).with_spread(0xFF);
+    assert_eq!(wide.0, [0x88; 4]);
+    assert_eq!(Wide([0x08, 0x80, 0, 0x80]).spread(), 0b1000_1001);
+}
+
+#[test]
+fn strided_metadata() {
+    assert_eq!(TileRow::FIELDS[1].start, 1);
+    assert_eq!(TileRow::FIELDS[1].end, 16);
+    assert_eq!(TileRow::FIELDS[1].stride, 2);
+    assert_eq!(TileRow::FIELDS[1].len(), 8);
+    assert_eq!(
+        TileRow::field_at_bit(3).map(|field| field.name),
+        Some("plane_1")
+    );
+    assert_eq!(
+        TileRow::field_at_bit(4).map(|field| field.name),
+        Some("plane_0")
+    );
+}
+
+#[test]
+fn strided_bits_macros() {
+    assert_eq!(bits!(0b1010_u8, u8 @ 1, step 2, count 2), 0b11);
+    assert_eq!(
+        with_bits!(0_u8, u8 @ 0, step 3, count 3 = 0b101),
+        0b0100_0001
+    );
+    let mut value = 0xFF_u16;
+    set_bits!(value, u8 @ 0, step 4, count 2 = 0);
+    assert_eq!(value, 0xEE);
+}
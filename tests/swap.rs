@@ -0,0 +1,62 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct DspReg(pub u64): Debug {
+        pub word: u16 [byte_swap] @ 0..16,
+        pub sample: u32 [byte_swap] @ 16..40,
+        pub signed_sample: i32 [byte_swap, read_only] @ 16..40,
+        pub bcd: u8 [nibble_swap] @ 40..48,
+        pub wide: u64 [get u64, byte_swap] @ 48..64,
+    }
+}
+
+#[test]
+fn byte_swap() {
+    let reg = DspReg(0).with_word(0x1234);
+    assert_eq!(reg.0, 0x3412);
+    assert_eq!(reg.word(), 0x1234);
+
+    let reg = DspReg(0).with_sample(0x12_3456);
+    assert_eq!(reg.0, 0x56_3412_0000);
+    assert_eq!(reg.sample(), 0x12_3456);
+    assert_eq!(DspReg(0x00_FF7F_0000).sample(), 0x7F_FF00);
+
+    assert_eq!(DspReg(0x00_0080_0000).signed_sample(), -0x80_0000);
+    assert_eq!(DspReg(0x00_00FF_0000).signed_sample(), -0x1_0000);
+    assert_eq!(DspReg(0x7F_0000_0000).signed_sample(), 0x7F);
+
+    assert_eq!(DspReg(0).with_wide(0xABCD).0, 0xCDAB_0000_0000_0000);
+}
+
+#[test]
+fn nibble_swap() {
+    let reg = DspReg(0).with_bcd(0x12);
+    assert_eq!(reg.0, 0x2100_0000_0000);
+    assert_eq!(reg.bcd(), 0x12);
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Frame(pub u8; 8): Debug {
+        pub length: u16 [be] @ 0..16,
+        pub vendor_id: u16 [le] @ 16..32,
+        pub product_id: u16 [le, read_only] @ 32..48,
+        pub seq: i16 [be] @ 48..64,
+    }
+}
+
+#[test]
+fn field_byte_orders() {
+    let frame = Frame([0x01, 0x02, 0x6D, 0x04, 0x2B, 0xC0, 0xFF, 0xFE]);
+    assert_eq!(frame.length(), 0x0102);
+    assert_eq!(frame.vendor_id(), 0x046D);
+    assert_eq!(frame.product_id(), 0xC02B);
+    assert_eq!(frame.seq(), -2);
+
+    let frame = Frame([0; 8])
+        .with_length(0x1234)
+        .with_vendor_id(0x5678)
+        .with_seq(-0x100);
+    assert_eq!(frame.0, [0x12, 0x34, 0x78, 0x56, 0, 0, 0xFF, 0]);
+}
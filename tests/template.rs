@@ -0,0 +1,36 @@
+use proc_bitfield::bitfield_template;
+
+const BANK_STRIDE: usize = 8;
+
+bitfield_template! {
+    template bank_reg(BANK, WIDTH) {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub struct _(pub u32): Debug, FromStorage {
+            pub value: u8 @ BANK * BANK_STRIDE; WIDTH,
+            pub valid: bool @ BANK * BANK_STRIDE + WIDTH,
+
+            pub const VALID = { valid: true };
+        }
+    }
+}
+
+bank_reg!(Bank0, BANK = 0, WIDTH = 4);
+bank_reg!(
+    #[derive(Default)]
+    Bank3,
+    BANK = 3,
+    WIDTH = 6,
+);
+
+#[test]
+fn template_basic_functionality() {
+    assert_eq!(Bank0::VALID.0, 1 << 4);
+    assert_eq!(Bank3::VALID.0, 1 << 30);
+
+    assert_eq!(Bank0(0xFF).value(), 0xF);
+    assert_eq!(Bank3(0).with_value(0x3F).0, 0x3F00_0000);
+    assert_eq!(Bank3::default().0, 0);
+
+    assert_eq!(Bank0::FIELDS[0].end, 4);
+    assert_eq!(Bank3::FIELDS[0].end, 30);
+}
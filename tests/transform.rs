@@ -0,0 +1,73 @@
+use proc_bitfield::{
+    bitfield,
+    remote::OutOfBounds,
+    transform::{permute_bits, unpermute_bits, Transform, Xor},
+};
+
+/// Reverses the order of the bits of a byte.
+struct Reverse;
+
+const REVERSE: [u8; 8] = [7, 6, 5, 4, 3, 2, 1, 0];
+
+impl Transform<u8> for Reverse {
+    fn decode(raw: u8) -> u8 {
+        permute_bits(raw as u128, &REVERSE) as u8
+    }
+
+    fn encode(value: u8) -> u8 {
+        unpermute_bits(value as u128, &REVERSE) as u8
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct SaveHeader(pub u32): Debug, Remote, options(transform = Xor<0xDEAD_BEEF>) {
+        pub version: u8 @ 0..8,
+        pub slot: u8 @ 8..12,
+        pub checksum: u16 @ 16..32,
+    }
+}
+
+bitfield! {
+    pub struct LockReg(pub u8): options(transform = Reverse) {
+        pub locked: bool @ 0,
+        pub owner: u8 @ 4..8,
+    }
+}
+
+#[test]
+fn transform_xor() {
+    let header = SaveHeader::from_encoded(0xDEAD_BEEF ^ 0x1234_0503);
+    assert_eq!(header.0, 0x1234_0503);
+    assert_eq!(header.version(), 3);
+    assert_eq!(header.slot(), 5);
+    assert_eq!(header.checksum(), 0x1234);
+    assert_eq!(
+        header.with_slot(2).into_encoded(),
+        0xDEAD_BEEF ^ 0x1234_0203
+    );
+}
+
+#[test]
+fn transform_permutation() {
+    let lock = LockReg::from_encoded(0x81);
+    assert!(lock.locked());
+    assert_eq!(lock.owner(), 8);
+    assert_eq!(LockReg(0).with_owner(3).into_encoded(), 0x0C);
+    assert_eq!(permute_bits(0b0110, &[1, 2]), 0b11);
+    assert_eq!(unpermute_bits(0b11, &[1, 2]), 0b0110);
+}
+
+#[test]
+fn transform_remote() {
+    let mut words = [0xDEAD_BEEF ^ 0x0000_0001_u32, 0];
+    let mut header = SaveHeaderRemote::new(&mut words[..], 0);
+    assert_eq!(header.version(), Ok(1));
+    header.set_version(2).unwrap();
+    assert_eq!(header.read(), Ok(SaveHeader(2)));
+    assert_eq!(words[0], 0xDEAD_BEEF ^ 2);
+    assert_eq!(
+        SaveHeaderRemote::new(&mut words[..], 2).read(),
+        Err(OutOfBounds)
+    );
+}
@@ -0,0 +1,41 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct DmaCtrl(pub u16): Debug {
+        pub channel: u8 @ 0..4,
+        pub start: bool [trigger] @ 4,
+        pub reset: bool [trigger DmaCtrl::complete_reset] @ 5,
+        pub abort: bool [trigger (|ctrl: &mut DmaCtrl| ctrl.0 &= !(1 << 6))] @ 6,
+        pub busy: bool [read_only] @ 15,
+    }
+}
+
+impl DmaCtrl {
+    fn complete_reset(&mut self) {
+        *self = DmaCtrl(0);
+    }
+}
+
+#[test]
+fn trigger_basic_functionality() {
+    let mut ctrl = DmaCtrl(0).with_channel(3);
+    ctrl.trigger_start();
+    assert_eq!(ctrl.0, 0x0013);
+
+    ctrl.trigger_abort();
+    assert_eq!(ctrl.0, 0x0013);
+
+    ctrl.trigger_reset();
+    assert_eq!(ctrl.0, 0);
+
+    let start = DmaCtrl::FIELDS
+        .iter()
+        .find(|field| field.name == "start")
+        .unwrap();
+    assert!(!start.readable && !start.writable);
+    assert_eq!(
+        format!("{:?}", DmaCtrl(0x0015)),
+        "DmaCtrl { 0: 21, channel: 5, busy: false }"
+    );
+}
@@ -0,0 +1,28 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Wide(pub u16) {
+        pub low: u8 [truncate] @ ..,
+        pub signed_low: i8 [truncate] @ ..,
+        pub whole: u32 [truncate] @ ..,
+    }
+}
+
+#[test]
+fn truncate_basic_functionality() {
+    let value = Wide(0xA5F0);
+    assert_eq!(value.low(), 0xF0);
+    assert_eq!(value.signed_low(), -0x10);
+    assert_eq!(value.whole(), 0xA5F0);
+
+    assert_eq!(value.with_low(0x12).0, 0xA512);
+    assert_eq!(value.with_signed_low(-1).0, 0xA5FF);
+    assert_eq!(value.with_whole(0x1_2345).0, 0x2345);
+}
+
+#[test]
+fn truncate_metadata() {
+    assert_eq!((Wide::FIELDS[0].start, Wide::FIELDS[0].end), (0, 8));
+    assert_eq!((Wide::FIELDS[2].start, Wide::FIELDS[2].end), (0, 16));
+}
@@ -0,0 +1,33 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Timer(pub u16): Debug, Display {
+        pub freq: u16 [unit "kHz"] @ 0..12,
+        pub en: bool @ 15,
+        pub reload: u8 [write_only] @ 12..15,
+    }
+}
+
+bitfield! {
+    pub struct Empty(pub u8): Display {}
+}
+
+#[test]
+fn units_basic_functionality() {
+    assert_eq!(Timer::FIELDS[0].unit, Some("kHz"));
+    assert_eq!(Timer::FIELDS[1].unit, None);
+
+    let mut json = String::new();
+    Timer::write_layout_json(&mut json).unwrap();
+    assert!(json.contains(r#""type":"u16","unit":"kHz","#));
+}
+
+#[test]
+fn units_display() {
+    assert_eq!(
+        Timer(0).with_freq(32).with_en(true).to_string(),
+        "Timer { freq: 32 kHz, en: true }"
+    );
+    assert_eq!(Empty(0).to_string(), "Empty {}");
+}
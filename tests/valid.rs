@@ -0,0 +1,80 @@
+use proc_bitfield::{bitfield, ConvRaw};
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    On,
+    Auto,
+}
+
+fn prescaler(raw: u8) -> Option<u8> {
+    (raw < 6).then_some(raw)
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Ctrl(pub u16): Debug, Valid {
+        pub mode: u8 [try_get Mode, set Mode] @ 0..2,
+        pub prescaler: u8 [try_get_fn prescaler -> Option<u8>] @ 2..5,
+        pub en: bool @ 15,
+    }
+}
+
+bitfield! {
+    pub struct Wrapper<T>(pub u8): Valid {
+        pub mode: u8 [try_get Mode] @ 0..2,
+    }
+}
+
+/// An unrelated type that would collide with the default `Valid` wrapper name for `Status`.
+pub struct ValidStatus;
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Status(pub u8): Valid, options(valid_name = CheckedStatus) {
+        pub mode: u8 [try_get Mode] @ 0..2,
+    }
+}
+
+#[test]
+fn valid_construction() {
+    let ctrl = ValidCtrl::new(Ctrl(0x8016)).unwrap();
+    assert_eq!(ctrl.mode(), Mode::Auto);
+    assert_eq!(ctrl.prescaler(), 5);
+    assert!(ctrl.en());
+    assert_eq!(ctrl.into_inner().0, 0x8016);
+    assert_eq!(Ctrl::from(ctrl), Ctrl(0x8016));
+
+    assert_eq!(ValidCtrl::new(Ctrl(0x0003)), Err(Ctrl(0x0003)));
+    assert_eq!(ValidCtrl::try_from(Ctrl(0x0018)), Err(Ctrl(0x0018)));
+}
+
+#[test]
+fn valid_unchecked_construction() {
+    let ctrl = unsafe { ValidCtrl::new_unchecked(Ctrl(0x0001)) };
+    assert_eq!(ctrl.into_inner().with_mode(Mode::Off).mode(), Ok(Mode::Off));
+}
+
+#[test]
+fn valid_generics() {
+    let wrapper = ValidWrapper::new(Wrapper::<u32>(1, core::marker::PhantomData)).ok();
+    assert_eq!(wrapper.map(|wrapper| wrapper.mode()), Some(Mode::On));
+}
+
+#[test]
+fn valid_from_storage() {
+    let ctrl = ValidCtrl::try_from_storage(0x8016).unwrap();
+    assert_eq!(ctrl.mode(), Mode::Auto);
+    assert_eq!(ValidCtrl::try_from_storage(0x0003), Err(0x0003));
+
+    const CTRL: ValidCtrl = unsafe { ValidCtrl::from_storage_unchecked(0x0001) };
+    assert_eq!(CTRL.mode(), Mode::On);
+}
+
+#[test]
+fn valid_custom_name() {
+    let _ = ValidStatus;
+    let status = CheckedStatus::new(Status(0x02)).ok().unwrap();
+    assert_eq!(status.mode(), Mode::Auto);
+    assert!(CheckedStatus::new(Status(0x03)).is_err());
+}
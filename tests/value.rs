@@ -0,0 +1,73 @@
+#![cfg(feature = "alloc")]
+
+use proc_bitfield::{
+    bitfield,
+    value::{FieldValue, ToFieldValue},
+    ConvRaw,
+};
+
+#[derive(ConvRaw, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Idle,
+    Run,
+    Sleep = 3,
+}
+
+pub struct NotAValue(pub u8);
+
+impl From<u8> for NotAValue {
+    fn from(value: u8) -> Self {
+        NotAValue(value)
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct Inner(pub u8) {
+        pub lo: u8 @ 0..4,
+        pub hi: i8 @ 4..8,
+    }
+}
+
+bitfield! {
+    pub struct Status(pub u32) {
+        pub irq_en: bool @ 0,
+        pub mode: u8 [try_get Mode] @ 1..3,
+        pub count: u16 @ 8..20,
+        pub offset: i8 @ 20..24,
+        pub bytes: [u8; 2] [read_only] @ 8..24,
+        pub inner: nested Inner @ 24..32,
+        pub opaque: u8 [get NotAValue] @ 24..32,
+        pub reset: bool [write_only] @ 31,
+    }
+}
+
+#[test]
+fn value_basic_functionality() {
+    let status = Status(0xA5F0_1203);
+    let map = status.to_value_map();
+    assert_eq!(map.len(), 7);
+    assert_eq!(map["irq_en"], FieldValue::Bool(true));
+    assert_eq!(map["mode"], FieldValue::Enum("Run"));
+    assert_eq!(map["count"], FieldValue::UInt(0x12));
+    assert_eq!(map["offset"], FieldValue::Int(-1));
+    assert_eq!(
+        map["bytes"],
+        FieldValue::Array(vec![FieldValue::UInt(0x12), FieldValue::UInt(0xF0)])
+    );
+    assert_eq!(map["inner"], Inner(0xA5).to_field_value());
+    assert_eq!(map["opaque"], FieldValue::Opaque);
+    assert!(!map.contains_key("reset"));
+
+    let FieldValue::Bitfield(inner) = &map["inner"] else {
+        panic!("nested bitfields should be converted to maps");
+    };
+    assert_eq!(inner["lo"], FieldValue::UInt(5));
+    assert_eq!(inner["hi"], FieldValue::Int(-6));
+}
+
+#[test]
+fn value_invalid_conversions() {
+    let map = Status(0x0000_0004).to_value_map();
+    assert_eq!(map["mode"], FieldValue::Invalid);
+}
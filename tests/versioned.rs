@@ -0,0 +1,145 @@
+use proc_bitfield::{
+    bitfield,
+    versioned::{self, Versioned},
+};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Ctrl(pub u16) {
+        pub mode: u8 @ 0..4,
+        pub irq_en: bool @ 15,
+    }
+}
+
+impl Versioned for Ctrl {
+    const VERSION: u8 = 2;
+
+    fn migrate(old_version: u8, bytes: &[u8]) -> Result<u16, versioned::Error> {
+        match old_version {
+            // Version 1 was stored in a single byte, with `irq_en` at bit 4
+            1 => {
+                let raw = versioned::read_storage::<u8>(bytes)? as u16;
+                Ok((raw & 0xF) | (raw >> 4 & 1) << 15)
+            }
+            _ => Err(versioned::Error::UnsupportedVersion(old_version)),
+        }
+    }
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Wide(pub [u16; 2]) {
+        pub lo: u16 @ 0..16,
+        pub hi: u16 @ 16..32,
+    }
+}
+
+impl Versioned for Wide {
+    const VERSION: u8 = 0;
+}
+
+#[test]
+fn versioned_basic_functionality() {
+    assert_eq!(versioned::encoded_len::<Ctrl>(), 3);
+
+    let ctrl = Ctrl(0).with_mode(5).with_irq_en(true);
+    let mut bytes = [0; 3];
+    versioned::encode(&ctrl, &mut bytes).unwrap();
+    assert_eq!(bytes, [2, 0x05, 0x80]);
+    assert_eq!(versioned::decode::<Ctrl>(&bytes), Ok(ctrl));
+
+    assert_eq!(
+        versioned::encode(&ctrl, &mut [0; 4]),
+        Err(versioned::Error::InvalidLength {
+            expected: 3,
+            found: 4
+        })
+    );
+    assert_eq!(
+        versioned::decode::<Ctrl>(&[2, 0]),
+        Err(versioned::Error::InvalidLength {
+            expected: 3,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn versioned_migration() {
+    assert_eq!(versioned::decode::<Ctrl>(&[1, 0x15]), Ok(Ctrl(0x8005)));
+    assert_eq!(
+        versioned::decode::<Ctrl>(&[1, 0x15, 0]),
+        Err(versioned::Error::InvalidLength {
+            expected: 1,
+            found: 2
+        })
+    );
+    assert_eq!(
+        versioned::decode::<Ctrl>(&[]),
+        Err(versioned::Error::InvalidLength {
+            expected: 3,
+            found: 0
+        })
+    );
+    assert_eq!(
+        versioned::decode::<Ctrl>(&[0, 0x15, 0]),
+        Err(versioned::Error::UnsupportedVersion(0))
+    );
+    assert_eq!(
+        versioned::decode::<Ctrl>(&[3, 0x15, 0]),
+        Err(versioned::Error::UnsupportedVersion(3))
+    );
+}
+
+#[test]
+fn versioned_array_storage() {
+    let wide = Wide([0x1234, 0x5678]);
+    let mut bytes = [0; 5];
+    versioned::encode(&wide, &mut bytes).unwrap();
+    assert_eq!(bytes, [0, 0x34, 0x12, 0x78, 0x56]);
+    assert_eq!(versioned::decode::<Wide>(&bytes), Ok(wide));
+    assert_eq!(
+        versioned::decode::<Wide>(&[1, 0, 0, 0, 0]),
+        Err(versioned::Error::UnsupportedVersion(1))
+    );
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct PtrSized(pub usize) {
+        pub index: u16 @ 0..16,
+        pub offset: i16 @ 16..32,
+    }
+}
+
+impl Versioned for PtrSized {
+    const VERSION: u8 = 0;
+}
+
+#[test]
+fn versioned_ptr_sized_storage() {
+    assert_eq!(versioned::encoded_len::<PtrSized>(), 9);
+
+    let value = PtrSized(0).with_index(0x1234).with_offset(-2);
+    let mut bytes = [0; 9];
+    versioned::encode(&value, &mut bytes).unwrap();
+    assert_eq!(bytes, [0, 0x34, 0x12, 0xFE, 0xFF, 0, 0, 0, 0]);
+    assert_eq!(versioned::decode::<PtrSized>(&bytes), Ok(value));
+
+    let mut bytes = [0; 9];
+    versioned::StorageBytes::write_le_bytes(&-2_isize, &mut bytes[1..]);
+    assert_eq!(bytes[1..], [0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(
+        <isize as versioned::StorageBytes>::read_le_bytes(&bytes[1..]),
+        Ok(-2)
+    );
+}
+
+#[test]
+#[cfg(target_pointer_width = "32")]
+fn versioned_ptr_sized_out_of_range() {
+    assert_eq!(
+        versioned::decode::<PtrSized>(&[0, 0, 0, 0, 0, 1, 0, 0, 0]),
+        Err(versioned::Error::OutOfRange)
+    );
+}
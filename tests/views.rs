@@ -0,0 +1,57 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    /// A UART data register, multiplexing different fields depending on the direction.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct UartData(pub u16): Debug {
+        pub data: u8 @ 0..8,
+        /// The transmit-side fields.
+        pub view tx {
+            pub parity: bool @ 8,
+            pub stop_bits: u8 @ 9..11,
+            pub start: bool [write_only] @ 15,
+        },
+        /// The receive-side fields.
+        pub view rx {
+            pub parity_err: bool @ 8,
+            pub framing_err: bool @ 9,
+            pub overrun: bool [read_only] @ 10,
+        },
+    }
+}
+
+bitfield! {
+    pub struct SpiCtrl(pub u8) {
+        pub view master as SpiMasterCtrl {
+            pub clk_div: u8 @ 0..4,
+        },
+    }
+}
+
+#[test]
+fn views() {
+    let mut reg = UartData(0).with_data(0x5A);
+    reg.tx_mut().set_stop_bits(2);
+    reg.tx_mut().set_parity(true);
+    assert_eq!(reg.0, 0x055A);
+    assert_eq!(reg.tx().stop_bits(), 2);
+    assert!(reg.rx().parity_err());
+    assert!(!reg.rx().framing_err());
+    assert!(reg.rx().overrun());
+
+    let reg = reg.with_tx(UartDataTx(0).with_start(true));
+    assert_eq!(reg.0, 0x8000);
+    assert_eq!(*UartData(0x300).rx(), UartDataRx(0x300));
+    assert_eq!(
+        format!("{:?}", UartData(0x201)),
+        "UartData { 0: 513, data: 1, tx: UartDataTx { 0: 513, parity: false, stop_bits: 1 }, \
+         rx: UartDataRx { 0: 513, parity_err: false, framing_err: true, overrun: false } }"
+    );
+}
+
+#[test]
+fn renamed_views() {
+    let ctrl = SpiCtrl(0).with_master(SpiMasterCtrl(0).with_clk_div(5));
+    assert_eq!(ctrl.0, 5);
+    assert_eq!(ctrl.master().clk_div(), 5);
+}
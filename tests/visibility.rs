@@ -0,0 +1,51 @@
+use proc_bitfield::ConvRaw;
+
+mod regs {
+    use proc_bitfield::bitfield;
+
+    bitfield! {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub struct Status(pub u16): Debug, Valid {
+            pub ready: bool [set_vis pub(crate)] @ 0,
+            pub error_code: u8 [set_vis pub(self)] @ 4..8,
+            pub(crate) irq: bool [get_vis pub] @ 8,
+            pub(self) mode: u8 [get_vis pub, try_get super::Mode, set super::Mode] @ 9..11,
+            pub lanes: bool; 2 [set_vis pub(self)] @ 12..14,
+        }
+    }
+
+    impl Status {
+        pub fn fail(&mut self, code: u8) {
+            self.set_error_code(code);
+            self.set_lanes(1, true);
+        }
+
+        pub fn enter(&mut self, mode: super::Mode) {
+            self.set_mode(mode);
+        }
+    }
+}
+
+#[derive(ConvRaw, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Idle,
+    Active,
+    Sleep,
+}
+
+#[test]
+fn asymmetric_visibility() {
+    let mut status = regs::Status(0x100);
+    status.set_ready(true);
+    status.fail(3);
+    status.enter(Mode::Active);
+    assert!(status.ready());
+    assert_eq!(status.error_code(), 3);
+    assert!(status.irq());
+    status.set_irq(false);
+    assert!(!status.irq());
+    assert_eq!(status.mode(), Ok(Mode::Active));
+    assert!(status.lanes(1));
+    assert_eq!(status.0, 0x2231);
+    assert_eq!(regs::ValidStatus::new(status).unwrap().mode(), Mode::Active);
+}
@@ -0,0 +1,96 @@
+use proc_bitfield::bitfield;
+
+bitfield! {
+    pub struct KeyReg(pub u128): Debug {
+        pub key: u128 @ 0..128,
+        pub high: u128 @ 32..128,
+        pub mid: u128 @ 8..88,
+        pub signed: i128 @ 4..100,
+    }
+}
+
+bitfield! {
+    pub struct ConstKeyReg(pub u128): Debug, options(const) {
+        pub key: u128 @ 0..128,
+        pub high: u128 @ 32..128,
+        pub signed: i128 @ 4..100,
+    }
+}
+
+bitfield! {
+    pub struct Descriptor(pub [u64; 3]): Debug {
+        pub key: u128 @ 0..128,
+        pub iv: u128 @ 40..140,
+        pub tail: u128 @ 64..192,
+        pub signed: i128 @ 60..160,
+    }
+}
+
+bitfield! {
+    pub struct ByteDescriptor(pub [u8; 20]): Debug {
+        pub key: u128 @ 4..132,
+        pub nonce: u128 @ 132..160,
+    }
+}
+
+const KEY: u128 = 0xF0E1_D2C3_B4A5_9687_7869_5A4B_3C2D_1E0F;
+
+#[test]
+fn int_storage() {
+    let reg = KeyReg(KEY);
+    assert_eq!(reg.key(), KEY);
+    assert_eq!(reg.high(), KEY >> 32);
+    assert_eq!(reg.mid(), KEY >> 8 & ((1 << 80) - 1));
+    assert_eq!(reg.signed(), ((KEY << 28) as i128) >> 32);
+    assert_eq!(KeyReg(0).with_key(KEY).0, KEY);
+    assert_eq!(KeyReg(0).with_high(u128::MAX).0, u128::MAX << 32);
+    assert_eq!(KeyReg(0).with_mid(u128::MAX).0, ((1 << 80) - 1) << 8);
+    let reg = KeyReg(0).with_signed(-1);
+    assert_eq!(reg.0, ((1 << 96) - 1) << 4);
+    assert_eq!(reg.signed(), -1);
+}
+
+#[test]
+fn const_accessors() {
+    const REG: ConstKeyReg = ConstKeyReg(0).with_key(KEY);
+    const HIGH: u128 = REG.high();
+    assert_eq!(REG.key(), KEY);
+    assert_eq!(HIGH, KEY >> 32);
+    assert_eq!(ConstKeyReg(0).with_high(u128::MAX).0, u128::MAX << 32);
+    let reg = ConstKeyReg(0).with_signed(-1);
+    assert_eq!(reg.0, ((1 << 96) - 1) << 4);
+    assert_eq!(reg.signed(), -1);
+}
+
+#[test]
+fn word_array_storage() {
+    let mut desc = Descriptor([0; 3]).with_key(KEY);
+    assert_eq!(desc.0, [KEY as u64, (KEY >> 64) as u64, 0]);
+    assert_eq!(desc.key(), KEY);
+    assert_eq!(desc.tail(), KEY >> 64);
+
+    desc = Descriptor([0; 3]).with_iv(KEY);
+    let iv = KEY & ((1 << 100) - 1);
+    assert_eq!(desc.iv(), iv);
+    assert_eq!(
+        desc.0,
+        [(iv << 40) as u64, (iv >> 24) as u64, (iv >> 88) as u64]
+    );
+
+    desc = Descriptor([0; 3]).with_tail(KEY);
+    assert_eq!(desc.0, [0, KEY as u64, (KEY >> 64) as u64]);
+    assert_eq!(desc.tail(), KEY);
+
+    desc = Descriptor([0; 3]).with_signed(-2);
+    assert_eq!(desc.signed(), -2);
+    assert_eq!(desc.0, [0xE << 60, u64::MAX, 0xFFFF_FFFF]);
+}
+
+#[test]
+fn byte_array_storage() {
+    let desc = ByteDescriptor([0; 20]).with_key(KEY).with_nonce(u128::MAX);
+    assert_eq!(desc.key(), KEY);
+    assert_eq!(desc.nonce(), (1 << 28) - 1);
+    assert_eq!(desc.0[0], 0xF0);
+    assert_eq!(desc.0[19], 0xFF);
+}
@@ -0,0 +1,31 @@
+use proc_bitfield::{access::AlreadyWritten, bitfield};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct OtpCfg(pub u16): Debug {
+        pub boot_mode: u8 [write_once configured] @ 0..3,
+        pub secure: bool [write_once configured] @ 3,
+        pub key_slot: u8 [get u16, write_once key_written] @ 4..8,
+        pub configured: bool [read_only] @ 14,
+        pub key_written: bool [read_only] @ 15,
+    }
+}
+
+#[test]
+fn write_once_basic_functionality() {
+    let mut cfg = OtpCfg(0);
+    assert_eq!(cfg.set_boot_mode(5), Ok(()));
+    assert_eq!(cfg.0, 0x4005);
+    assert!(cfg.configured());
+    assert_eq!(cfg.set_boot_mode(2), Err(AlreadyWritten));
+    assert_eq!(cfg.set_secure(true), Err(AlreadyWritten));
+    assert_eq!(cfg.boot_mode(), 5);
+
+    assert_eq!(cfg.with_key_slot(3).map(|cfg| cfg.0), Ok(0xC035));
+    let cfg = cfg.with_key_slot(3).unwrap();
+    assert_eq!(cfg.with_key_slot(4), Err(AlreadyWritten));
+    assert_eq!(cfg.key_slot(), 3_u16);
+
+    assert_eq!(OtpCfg(0).with_secure(true), Ok(OtpCfg(0x4008)));
+    assert_eq!(AlreadyWritten.to_string(), "field has already been written");
+}